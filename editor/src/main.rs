@@ -2,16 +2,23 @@
 use fyrox::event_loop::EventLoop;
 use fyroxed_base::{Editor, StartupData};
 use station_iapetus::{
-    bot::BotKind,
+    bot::{
+        patrol::{IdleActivity, PatrolMode},
+        BotKind,
+    },
     character::{Character, HitBox},
     door::{DoorDirection, DoorState},
     elevator::call_button::CallButtonKind,
+    faction::Faction,
     inventory::{Inventory, ItemEntry},
     level::{
+        ambush::{AmbushActivation, AmbushBot},
+        area_effect::AreaEffectKind,
         item::ItemKind,
+        navigation::NavigationLinkKind,
         spawn::DefaultWeapon,
         trigger::TriggerKind,
-        turret::{Barrel, Hostility, ShootMode},
+        turret::{Barrel, ShootMode},
     },
     player::camera::CameraController,
     weapon::{definition::WeaponKind, projectile::ProjectileKind},
@@ -31,7 +38,7 @@ fn main() {
     let editors = &editor.inspector.property_editors;
     editors.register_inheritable_enum::<DoorState, _>();
     editors.register_inheritable_enum::<DoorDirection, _>();
-    editors.register_inheritable_enum::<Hostility, _>();
+    editors.register_inheritable_enum::<Faction, _>();
     editors.register_inheritable_enum::<ShootMode, _>();
     editors.register_inheritable_enum::<WeaponKind, _>();
     editors.register_inheritable_enum::<ItemKind, _>();
@@ -39,6 +46,11 @@ fn main() {
     editors.register_inheritable_enum::<CallButtonKind, _>();
     editors.register_inheritable_enum::<ProjectileKind, _>();
     editors.register_inheritable_enum::<TriggerKind, _>();
+    editors.register_inheritable_enum::<AreaEffectKind, _>();
+    editors.register_inheritable_enum::<PatrolMode, _>();
+    editors.register_inheritable_enum::<IdleActivity, _>();
+    editors.register_inheritable_enum::<AmbushActivation, _>();
+    editors.register_inheritable_enum::<NavigationLinkKind, _>();
     editors.register_inheritable_inspectable::<Inventory>();
     editors.register_inheritable_inspectable::<ItemEntry>();
     editors.register_inheritable_inspectable::<Barrel>();
@@ -49,6 +61,7 @@ fn main() {
     editors.register_inheritable_vec_collection::<HitBox>();
     editors.register_inheritable_vec_collection::<DefaultWeapon>();
     editors.register_inheritable_vec_collection::<ItemEntry>();
+    editors.register_inheritable_vec_collection::<AmbushBot>();
 
     editor.add_game_plugin(GameConstructor);
     editor.run(event_loop)
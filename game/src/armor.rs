@@ -0,0 +1,54 @@
+//! Suit armor. Armor absorbs a share of incoming damage depending on its type, suit tiers
+//! raise maximum amount of health and armor. Tiers are defined in `data/configs/armor.ron`.
+
+use crate::weapon::projectile::DamageKind;
+use fyrox::lazy_static::lazy_static;
+use serde::Deserialize;
+use std::{collections::HashMap, fs::File};
+
+#[derive(Deserialize, Debug)]
+pub struct ArmorTierDefinition {
+    pub name: String,
+    pub max_health: f32,
+    pub max_armor: f32,
+    /// Share of damage (0..1) that is absorbed by armor, per damage kind.
+    pub absorption: HashMap<DamageKind, f32>,
+}
+
+impl ArmorTierDefinition {
+    pub fn absorption(&self, kind: DamageKind) -> f32 {
+        self.absorption
+            .get(&kind)
+            .cloned()
+            .unwrap_or_default()
+            .clamp(0.0, 1.0)
+    }
+}
+
+#[derive(Deserialize, Default)]
+pub struct ArmorTierContainer {
+    tiers: Vec<ArmorTierDefinition>,
+}
+
+impl ArmorTierContainer {
+    pub fn new() -> Self {
+        let file = File::open("data/configs/armor.ron").unwrap();
+        ron::de::from_reader(file).unwrap()
+    }
+}
+
+lazy_static! {
+    static ref DEFINITIONS: ArmorTierContainer = ArmorTierContainer::new();
+}
+
+pub fn armor_tier_definition(tier: u32) -> &'static ArmorTierDefinition {
+    let tiers = &DEFINITIONS.tiers;
+    tiers
+        .get(tier as usize)
+        .or_else(|| tiers.last())
+        .expect("At least one armor tier must be defined!")
+}
+
+pub fn max_armor_tier() -> u32 {
+    (DEFINITIONS.tiers.len() as u32).saturating_sub(1)
+}
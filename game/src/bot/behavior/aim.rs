@@ -77,10 +77,10 @@ impl AimOnTarget {
         dt: f32,
         body: Handle<Node>,
     ) -> bool {
-        if self.yaw.angle.is_nan() {
-            let local_look = scene.graph[model].look_vector();
-            self.yaw.angle = local_look.x.atan2(local_look.z);
-        }
+        // Body could be rotated by other behaviors (when investigating noises for example), so
+        // always start from the actual orientation.
+        let local_look = scene.graph[model].look_vector();
+        self.yaw.angle = local_look.x.atan2(local_look.z);
 
         let angle = self.yaw.angle();
 
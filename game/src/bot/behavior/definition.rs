@@ -0,0 +1,285 @@
+//! Behavior trees are described in `data/configs/bots.ron` for every kind of bot, this module
+//! contains description of tree nodes, validation of trees and conversion to actual trees.
+
+use crate::bot::{
+    behavior::{
+        aim::AimOnTarget,
+        death::{IsDead, StayDead},
+        find::FindTarget,
+        grab::GrabTarget,
+        hear::{HearNoise, InvestigateNoise},
+        melee::{CanMeleeAttack, DoMeleeAttack},
+        movement::{MoveToTarget, Retreat},
+        patrol::Patrol,
+        reanimate::{FindCorpse, ReanimateCorpse},
+        search::SearchTarget,
+        shoot::{CanShootTarget, ShootTarget},
+        threat::{NeedsThreatenTarget, ThreatenTarget},
+        Action,
+    },
+    BotDefinition, BotKind,
+};
+use fyrox::{
+    core::pool::Handle,
+    scene::node::Node,
+    utils::behavior::{composite::CompositeNode, leaf::LeafNode, BehaviorNode, BehaviorTree},
+};
+use serde::Deserialize;
+
+#[derive(Deserialize, Debug, Clone)]
+pub enum BehaviorNodeDefinition {
+    /// Runs children one by one until one of them succeeds or is running.
+    Selector(Vec<BehaviorNodeDefinition>),
+    /// Runs children one by one until one of them fails or is running.
+    Sequence(Vec<BehaviorNodeDefinition>),
+    IsDead,
+    StayDead,
+    FindTarget,
+    MoveToTarget {
+        min_distance: f32,
+    },
+    CanMeleeAttack,
+    AimOnTarget,
+    DoMeleeAttack,
+    /// Grabs the player within the range, the player must break free in `struggle_time` seconds
+    /// or take the damage. `chance` is a probability of an attempt, attempts are made every
+    /// `cooldown` seconds.
+    GrabTarget {
+        range: f32,
+        chance: f32,
+        struggle_time: f32,
+        damage: f32,
+        cooldown: f32,
+    },
+    CanShootTarget,
+    ShootTarget,
+    NeedsThreatenTarget,
+    ThreatenTarget,
+    HearNoise,
+    InvestigateNoise,
+    SearchTarget,
+    Patrol,
+    /// Runs away from the target for `duration` seconds after a successful melee hit.
+    Retreat {
+        distance: f32,
+        duration: f32,
+    },
+    /// Selects the closest corpse within the radius.
+    FindCorpse {
+        radius: f32,
+    },
+    /// Reanimates the selected corpse into a bot of the given kind.
+    ReanimateCorpse {
+        kind: BotKind,
+        duration: f32,
+    },
+}
+
+impl BehaviorNodeDefinition {
+    fn name(&self) -> &'static str {
+        match self {
+            Self::Selector(_) => "Selector",
+            Self::Sequence(_) => "Sequence",
+            Self::IsDead => "IsDead",
+            Self::StayDead => "StayDead",
+            Self::FindTarget => "FindTarget",
+            Self::MoveToTarget { .. } => "MoveToTarget",
+            Self::CanMeleeAttack => "CanMeleeAttack",
+            Self::AimOnTarget => "AimOnTarget",
+            Self::DoMeleeAttack => "DoMeleeAttack",
+            Self::GrabTarget { .. } => "GrabTarget",
+            Self::CanShootTarget => "CanShootTarget",
+            Self::ShootTarget => "ShootTarget",
+            Self::NeedsThreatenTarget => "NeedsThreatenTarget",
+            Self::ThreatenTarget => "ThreatenTarget",
+            Self::HearNoise => "HearNoise",
+            Self::InvestigateNoise => "InvestigateNoise",
+            Self::SearchTarget => "SearchTarget",
+            Self::Patrol => "Patrol",
+            Self::Retreat { .. } => "Retreat",
+            Self::FindCorpse { .. } => "FindCorpse",
+            Self::ReanimateCorpse { .. } => "ReanimateCorpse",
+        }
+    }
+
+    /// Leaves that work with current target and must be placed after `FindTarget`.
+    fn requires_target(&self) -> bool {
+        matches!(
+            self,
+            Self::MoveToTarget { .. }
+                | Self::CanMeleeAttack
+                | Self::AimOnTarget
+                | Self::DoMeleeAttack
+                | Self::GrabTarget { .. }
+                | Self::CanShootTarget
+                | Self::ShootTarget
+                | Self::NeedsThreatenTarget
+                | Self::ThreatenTarget
+                | Self::Retreat { .. }
+        )
+    }
+
+    fn contains(&self, predicate: &dyn Fn(&Self) -> bool) -> bool {
+        predicate(self)
+            || match self {
+                Self::Selector(children) | Self::Sequence(children) => {
+                    children.iter().any(|c| c.contains(predicate))
+                }
+                _ => false,
+            }
+    }
+
+    /// Checks that the tree can be used by a bot with given definition.
+    pub fn validate(&self, definition: &BotDefinition) -> Result<(), String> {
+        if !self.contains(&|n| matches!(n, Self::IsDead)) {
+            return Err(
+                "the tree must have IsDead node, otherwise dead bots won't stop".to_owned(),
+            );
+        }
+
+        self.validate_node(definition, false)
+    }
+
+    /// `has_target` tells whether the node is guaranteed to be ticked only when there's a target.
+    fn validate_node(&self, definition: &BotDefinition, has_target: bool) -> Result<(), String> {
+        if self.requires_target() && !has_target {
+            return Err(format!(
+                "{} requires a target, put it in a sequence after FindTarget",
+                self.name()
+            ));
+        }
+
+        match self {
+            Self::Selector(children) | Self::Sequence(children) => {
+                if children.is_empty() {
+                    return Err(format!("{} must have at least one child", self.name()));
+                }
+
+                let mut has_target = has_target;
+                for child in children {
+                    child
+                        .validate_node(definition, has_target)
+                        .map_err(|e| format!("{} -> {}", self.name(), e))?;
+
+                    // Next nodes of a sequence are ticked only if FindTarget succeeded.
+                    if matches!(self, Self::Sequence(_)) && matches!(child, Self::FindTarget) {
+                        has_target = true;
+                    }
+                }
+            }
+            Self::MoveToTarget { min_distance } => {
+                if *min_distance <= 0.0 {
+                    return Err("MoveToTarget min_distance must be positive".to_owned());
+                }
+            }
+            Self::Retreat { distance, duration } => {
+                if *distance <= 0.0 || *duration <= 0.0 {
+                    return Err("Retreat distance and duration must be positive".to_owned());
+                }
+            }
+            Self::FindCorpse { radius } => {
+                if *radius <= 0.0 {
+                    return Err("FindCorpse radius must be positive".to_owned());
+                }
+            }
+            Self::ReanimateCorpse { duration, .. } => {
+                if *duration <= 0.0 {
+                    return Err("ReanimateCorpse duration must be positive".to_owned());
+                }
+            }
+            Self::GrabTarget {
+                range,
+                chance,
+                struggle_time,
+                damage,
+                cooldown,
+            } => {
+                if *range <= 0.0 || *struggle_time <= 0.0 {
+                    return Err("GrabTarget range and struggle_time must be positive".to_owned());
+                }
+                if *damage < 0.0 || *cooldown < 0.0 {
+                    return Err("GrabTarget damage and cooldown must not be negative".to_owned());
+                }
+                if !(0.0..=1.0).contains(chance) {
+                    return Err("GrabTarget chance must be in [0; 1] range".to_owned());
+                }
+                if definition.grab_animation.is_none() {
+                    return Err(
+                        "GrabTarget can't be used by a bot without grab_animation".to_owned()
+                    );
+                }
+            }
+            Self::CanShootTarget | Self::ShootTarget => {
+                if !definition.can_use_weapons {
+                    return Err(format!(
+                        "{} can't be used by a bot that can't use weapons",
+                        self.name()
+                    ));
+                }
+            }
+            _ => (),
+        }
+
+        Ok(())
+    }
+
+    pub fn build(
+        &self,
+        tree: &mut BehaviorTree<Action>,
+        spine: Handle<Node>,
+    ) -> Handle<BehaviorNode<Action>> {
+        let action = match self {
+            Self::Selector(children) | Self::Sequence(children) => {
+                let children = children
+                    .iter()
+                    .map(|c| c.build(tree, spine))
+                    .collect::<Vec<_>>();
+                let node = if matches!(self, Self::Selector(_)) {
+                    CompositeNode::new_selector(children)
+                } else {
+                    CompositeNode::new_sequence(children)
+                };
+                return node.add_to(tree);
+            }
+            Self::IsDead => return IsDead::new_action(tree),
+            Self::StayDead => return StayDead::new_action(tree),
+            Self::FindTarget => Action::FindTarget(FindTarget::default()),
+            Self::MoveToTarget { min_distance } => Action::MoveToTarget(MoveToTarget {
+                min_distance: *min_distance,
+            }),
+            Self::CanMeleeAttack => Action::CanMeleeAttack(CanMeleeAttack),
+            Self::AimOnTarget => AimOnTarget::new_action(spine),
+            Self::DoMeleeAttack => Action::DoMeleeAttack(DoMeleeAttack::default()),
+            Self::GrabTarget {
+                range,
+                chance,
+                struggle_time,
+                damage,
+                cooldown,
+            } => Action::GrabTarget(GrabTarget::new(
+                *range,
+                *chance,
+                *struggle_time,
+                *damage,
+                *cooldown,
+            )),
+            Self::CanShootTarget => Action::CanShootTarget(CanShootTarget),
+            Self::ShootTarget => Action::ShootTarget(ShootTarget),
+            Self::NeedsThreatenTarget => Action::NeedsThreatenTarget(NeedsThreatenTarget),
+            Self::ThreatenTarget => Action::ThreatenTarget(ThreatenTarget::default()),
+            Self::HearNoise => Action::HearNoise(HearNoise),
+            Self::InvestigateNoise => Action::InvestigateNoise(InvestigateNoise::default()),
+            Self::SearchTarget => Action::SearchTarget(SearchTarget::default()),
+            Self::Patrol => Action::Patrol(Patrol::default()),
+            Self::Retreat { distance, duration } => {
+                Action::Retreat(Retreat::new(*distance, *duration))
+            }
+            Self::FindCorpse { radius } => Action::FindCorpse(FindCorpse { radius: *radius }),
+            Self::ReanimateCorpse { kind, duration } => {
+                Action::ReanimateCorpse(ReanimateCorpse::new(*kind, *duration))
+            }
+        };
+
+        LeafNode::new(action).add_to(tree)
+    }
+}
@@ -1,6 +1,10 @@
 use crate::{
-    bot::{behavior::BehaviorContext, Bot, BotHostility, Target},
+    bot::{
+        behavior::{hear::belongs_to_actor, BehaviorContext},
+        Target, TargetMemory,
+    },
     character::{try_get_character_ref, Character},
+    level::spatial::SpatialCategory,
 };
 use fyrox::{
     core::{
@@ -10,8 +14,11 @@ use fyrox::{
         visitor::prelude::*,
     },
     scene::{
-        collider::{ColliderShape, InteractionGroups},
-        graph::{physics::RayCastOptions, Graph},
+        collider::InteractionGroups,
+        graph::{
+            physics::{Intersection, RayCastOptions},
+            Graph,
+        },
         node::Node,
     },
     utils::behavior::{Behavior, Status},
@@ -23,6 +30,9 @@ pub struct FindTarget {
 }
 
 impl FindTarget {
+    /// Max distance at which the bot notices targets that are out of its sight.
+    const HEARING_DISTANCE: f32 = 1.6;
+
     fn update_frustum(&mut self, position: Vector3<f32>, graph: &Graph, model: Handle<Node>) {
         let head_pos = position + Vector3::new(0.0, 0.4, 0.0);
         let up = graph[model].up_vector();
@@ -35,6 +45,31 @@ impl FindTarget {
     }
 }
 
+/// Checks that there is nothing except actors between given points.
+fn has_line_of_sight(
+    graph: &Graph,
+    from: Vector3<f32>,
+    to: Vector3<f32>,
+    query_buffer: &mut Vec<Intersection>,
+) -> bool {
+    let ray = Ray::from_two_points(from, to);
+    graph.physics.cast_ray(
+        RayCastOptions {
+            ray_origin: Point3::from(ray.origin),
+            ray_direction: ray.dir,
+            groups: InteractionGroups::default(),
+            max_len: ray.dir.norm(),
+            sort_results: true,
+        },
+        query_buffer,
+    );
+
+    // Capsules and hit boxes of actors (including the target itself) do not block sight.
+    query_buffer
+        .iter()
+        .all(|hit| belongs_to_actor(graph, hit.collider))
+}
+
 impl<'a> Behavior<'a> for FindTarget {
     type Context = BehaviorContext<'a>;
 
@@ -43,16 +78,38 @@ impl<'a> Behavior<'a> for FindTarget {
 
         self.update_frustum(position, &ctx.scene.graph, ctx.model);
 
+        let mut query_buffer = Vec::default();
+
         // Check if existing target is valid.
         if let Some(target) = ctx.target {
-            for &actor_handle in ctx.actors {
-                if actor_handle != ctx.bot_handle && actor_handle == target.handle {
-                    if let Some(character) = try_get_character_ref(actor_handle, &ctx.scene.graph) {
-                        if character.health > 0.0 {
-                            target.position = character.position(&ctx.scene.graph);
-                            return Status::Success;
-                        }
+            if let Some(character) = ctx
+                .actors
+                .iter()
+                .find(|&&a| a != ctx.bot_handle && a == target.handle)
+                .and_then(|&a| try_get_character_ref(a, &ctx.scene.graph))
+            {
+                if character.health > 0.0 {
+                    let target_position = character.position(&ctx.scene.graph);
+                    let target_velocity = ctx.scene.graph[character.body].as_rigid_body().lin_vel();
+
+                    if has_line_of_sight(
+                        &ctx.scene.graph,
+                        target_position,
+                        position,
+                        &mut query_buffer,
+                    ) {
+                        target.position = target_position;
+                        *ctx.noise_position = None;
+                        *ctx.target_memory = None;
+                        return Status::Success;
                     }
+
+                    // Target went out of sight, remember where it was last seen.
+                    *ctx.target_memory = Some(TargetMemory::new(
+                        target.handle,
+                        target_position,
+                        target_velocity,
+                    ));
                 }
             }
         }
@@ -60,11 +117,16 @@ impl<'a> Behavior<'a> for FindTarget {
         // Reset target and try to find new one.
         *ctx.target = None;
         let mut closest_distance = f32::MAX;
-        let mut query_buffer = Vec::default();
-        'target_loop: for &actor_handle in ctx
-            .actors
-            .iter()
-            .filter(|actor_handle| **actor_handle != ctx.bot_handle)
+        let bot_handle = ctx.bot_handle;
+        let spatial_index = ctx.spatial_index;
+        'target_loop: for actor_handle in spatial_index
+            .in_frustum(&self.frustum, SpatialCategory::Actor)
+            .chain(spatial_index.in_radius(
+                position,
+                Self::HEARING_DISTANCE,
+                SpatialCategory::Actor,
+            ))
+            .filter(|actor_handle| *actor_handle != bot_handle)
         {
             let character_node = &ctx.scene.graph[actor_handle];
 
@@ -79,55 +141,30 @@ impl<'a> Behavior<'a> for FindTarget {
             }
 
             // Check hostility.
-            match ctx.definition.hostility {
-                BotHostility::OtherSpecies => {
-                    if let Some(bot) = character_node.try_get_script::<Bot>() {
-                        if bot.kind == ctx.kind {
-                            continue 'target_loop;
-                        }
-                    }
-                }
-                BotHostility::Player => {
-                    if character_node.has_script::<Bot>() {
-                        continue 'target_loop;
-                    }
-                }
-                BotHostility::Everyone => {}
+            if !ctx
+                .factions
+                .is_hostile(ctx.character.faction, character.faction)
+            {
+                continue 'target_loop;
             }
 
             // Check each target for two criteria:
             // 1) Is close enough to bot ("can hear")
             // 2) Is visible to bot ("can see")
             let distance = position.metric_distance(&character_node.global_position());
-            if distance != 0.0 && distance < 1.6
+            if distance != 0.0 && distance < Self::HEARING_DISTANCE
                 || self
                     .frustum
                     .is_contains_point(character_node.global_position())
             {
-                let ray = Ray::from_two_points(character_node.global_position(), position);
-                ctx.scene.graph.physics.cast_ray(
-                    RayCastOptions {
-                        ray_origin: Point3::from(ray.origin),
-                        ray_direction: ray.dir,
-                        groups: InteractionGroups::default(),
-                        max_len: ray.dir.norm(),
-                        sort_results: true,
-                    },
+                if !has_line_of_sight(
+                    &ctx.scene.graph,
+                    character_node.global_position(),
+                    position,
                     &mut query_buffer,
-                );
-
-                'hit_loop: for hit in query_buffer.iter() {
-                    let collider = ctx.scene.graph[hit.collider].as_collider();
-
-                    if let ColliderShape::Capsule(_) = collider.shape() {
-                        // Prevent setting self as target.
-                        if ctx.character.capsule_collider == hit.collider {
-                            continue 'hit_loop;
-                        }
-                    } else {
-                        // Target is behind something.
-                        continue 'target_loop;
-                    }
+                ) {
+                    // Target is behind something.
+                    continue 'target_loop;
                 }
 
                 if distance < closest_distance {
@@ -141,10 +178,13 @@ impl<'a> Behavior<'a> for FindTarget {
         }
 
         if ctx.target.is_some() {
+            // There is no need to investigate noises or search when there is a target.
+            *ctx.noise_position = None;
+            *ctx.target_memory = None;
             Status::Success
         } else {
-            // Keep looking.
-            Status::Running
+            // Let the bot do something else, like investigating noises.
+            Status::Failure
         }
     }
 }
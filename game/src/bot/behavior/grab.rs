@@ -0,0 +1,100 @@
+use crate::{bot::behavior::BehaviorContext, player::Player, utils::is_probability_event_occurred};
+use fyrox::{
+    core::{pool::Handle, visitor::prelude::*},
+    scene::{graph::Graph, node::Node},
+    utils::behavior::{Behavior, Status},
+};
+
+/// Grabs the player and holds them until they break free or the time of the struggle runs out.
+/// Fails if the target can't be grabbed right now, so it can be followed by a regular melee
+/// attack in a selector.
+#[derive(Default, Debug, PartialEq, Visit, Clone)]
+pub struct GrabTarget {
+    range: f32,
+    /// Probability of a grab attempt to be made.
+    chance: f32,
+    struggle_time: f32,
+    damage: f32,
+    cooldown: f32,
+    /// Time left until the next attempt, counts down only while the bot is able to attack.
+    cooldown_timer: f32,
+    is_holding: bool,
+}
+
+fn try_get_player_mut(handle: Handle<Node>, graph: &mut Graph) -> Option<&mut Player> {
+    graph
+        .try_get_mut(handle)
+        .and_then(|n| n.try_get_script_mut::<Player>())
+}
+
+impl GrabTarget {
+    pub fn new(range: f32, chance: f32, struggle_time: f32, damage: f32, cooldown: f32) -> Self {
+        Self {
+            range,
+            chance,
+            struggle_time,
+            damage,
+            cooldown,
+            cooldown_timer: 0.0,
+            is_holding: false,
+        }
+    }
+}
+
+impl<'a> Behavior<'a> for GrabTarget {
+    type Context = BehaviorContext<'a>;
+
+    fn tick(&mut self, context: &mut Self::Context) -> Status {
+        let (target_handle, target_position) = match context.target.as_ref() {
+            Some(target) => (target.handle, target.position),
+            None => return Status::Failure,
+        };
+
+        if self.is_holding {
+            if try_get_player_mut(target_handle, &mut context.scene.graph)
+                .map_or(false, |player| player.hold_grab(context.bot_handle))
+            {
+                context.is_grabbing = true;
+                return Status::Running;
+            }
+
+            // The player broke free or died.
+            self.is_holding = false;
+            self.cooldown_timer = self.cooldown;
+            return Status::Success;
+        }
+
+        self.cooldown_timer -= context.dt;
+        if self.cooldown_timer > 0.0 {
+            return Status::Failure;
+        }
+
+        let position = context.character.position(&context.scene.graph);
+        if position.metric_distance(&target_position) > self.range {
+            return Status::Failure;
+        }
+
+        self.cooldown_timer = self.cooldown;
+
+        if !is_probability_event_occurred(self.chance) {
+            return Status::Failure;
+        }
+
+        if try_get_player_mut(target_handle, &mut context.scene.graph).map_or(false, |player| {
+            player.try_grab(context.bot_handle, self.struggle_time, self.damage)
+        }) {
+            self.is_holding = true;
+            context.is_grabbing = true;
+
+            // The grab is paired with the struggle animation of the player, both start together.
+            let grab_animation = context.upper_body_machine.grab_animation;
+            if grab_animation.is_some() {
+                context.scene.animations.get_mut(grab_animation).rewind();
+            }
+
+            Status::Running
+        } else {
+            Status::Failure
+        }
+    }
+}
@@ -0,0 +1,157 @@
+use crate::{
+    bot::behavior::{
+        movement::{look_along_path, move_to},
+        BehaviorContext,
+    },
+    character::try_get_character_ref,
+    faction::try_get_faction,
+    sound::NoiseKind,
+};
+use fyrox::{
+    core::{
+        algebra::{Point3, Vector3},
+        math::{ray::Ray, SmoothAngle},
+        pool::Handle,
+        visitor::prelude::*,
+    },
+    scene::{
+        collider::InteractionGroups,
+        graph::{physics::RayCastOptions, Graph},
+        node::Node,
+    },
+    utils::behavior::{Behavior, Status},
+};
+
+/// Each wall between a bot and a source of noise reduces loudness by this factor.
+const WALL_ATTENUATION: f32 = 0.35;
+
+/// Checks whether the node is a part of an actor: its capsule, a hit box or any other collider
+/// attached to its skeleton.
+pub fn belongs_to_actor(graph: &Graph, node: Handle<Node>) -> bool {
+    let mut current = node;
+    while current.is_some() {
+        if try_get_character_ref(current, graph).is_some() {
+            return true;
+        }
+        current = graph[current].parent();
+    }
+    false
+}
+
+fn wall_attenuation(graph: &Graph, listener: Vector3<f32>, source: Vector3<f32>) -> f32 {
+    let mut query_buffer = Vec::default();
+    let ray = Ray::from_two_points(listener, source);
+    graph.physics.cast_ray(
+        RayCastOptions {
+            ray_origin: Point3::from(ray.origin),
+            ray_direction: ray.dir,
+            groups: InteractionGroups::default(),
+            max_len: ray.dir.norm(),
+            sort_results: false,
+        },
+        &mut query_buffer,
+    );
+
+    query_buffer
+        .iter()
+        // Actors (including the listener itself) are not walls.
+        .filter(|hit| !belongs_to_actor(graph, hit.collider))
+        .fold(1.0, |loudness, _| loudness * WALL_ATTENUATION)
+}
+
+/// Listens for noises around and remembers the position of the most audible one. Succeeds if
+/// there is a noise to investigate.
+#[derive(Default, Debug, PartialEq, Visit, Clone)]
+pub struct HearNoise;
+
+impl<'a> Behavior<'a> for HearNoise {
+    type Context = BehaviorContext<'a>;
+
+    fn tick(&mut self, ctx: &mut Self::Context) -> Status {
+        if ctx.target.is_some() {
+            return Status::Failure;
+        }
+
+        let head_position = ctx.character.position(&ctx.scene.graph) + Vector3::new(0.0, 0.4, 0.0);
+
+        let mut most_audible = None;
+        let mut max_audibility = 0.0;
+        for noise in ctx.sound_manager.noises().iter() {
+            if noise.emitter == ctx.bot_handle {
+                continue;
+            }
+
+            // Footsteps of friends are not interesting, but friends shooting at something are.
+            if let NoiseKind::FootStep | NoiseKind::Door = noise.kind {
+                if let Some(faction) = try_get_faction(noise.emitter, &ctx.scene.graph) {
+                    if !ctx.factions.is_hostile(ctx.character.faction, faction) {
+                        continue;
+                    }
+                }
+            }
+
+            let distance = head_position.metric_distance(&noise.position);
+            if distance > noise.loudness {
+                continue;
+            }
+
+            let audibility = noise.loudness
+                * wall_attenuation(&ctx.scene.graph, head_position, noise.position)
+                - distance;
+            if audibility > max_audibility {
+                max_audibility = audibility;
+                most_audible = Some(noise.position);
+            }
+        }
+
+        if most_audible.is_some() {
+            *ctx.noise_position = most_audible;
+        }
+
+        if ctx.noise_position.is_some() {
+            Status::Success
+        } else {
+            Status::Failure
+        }
+    }
+}
+
+/// Turns the bot toward a heard noise and moves it to the source of the noise.
+#[derive(Debug, PartialEq, Visit, Clone)]
+pub struct InvestigateNoise {
+    yaw: SmoothAngle,
+}
+
+impl Default for InvestigateNoise {
+    fn default() -> Self {
+        Self {
+            yaw: SmoothAngle {
+                angle: 0.0,
+                target: 0.0,
+                speed: 180.0f32.to_radians(),
+            },
+        }
+    }
+}
+
+impl<'a> Behavior<'a> for InvestigateNoise {
+    type Context = BehaviorContext<'a>;
+
+    fn tick(&mut self, ctx: &mut Self::Context) -> Status {
+        let noise_position = match *ctx.noise_position {
+            Some(noise_position) => noise_position,
+            None => return Status::Failure,
+        };
+
+        let status = move_to(ctx, Some(noise_position), 1.0);
+
+        look_along_path(ctx, &mut self.yaw, noise_position);
+
+        if status == Status::Success {
+            // Nothing found at the place.
+            *ctx.noise_position = None;
+        }
+
+        status
+    }
+}
@@ -1,10 +1,29 @@
 use crate::{
-    bot::{behavior::BehaviorContext, upper_body::UpperBodyMachine, BotDefinition},
-    character::{try_get_character_mut, CharacterCommand},
+    bot::{
+        behavior::BehaviorContext, try_get_bot_mut, upper_body::UpperBodyMachine,
+        AttackAnimationDefinition, BotCommand, BotDefinition,
+    },
+    character::{try_get_character_mut, try_get_character_ref, CharacterCommand, HitBox},
+    damage::DamageSource,
+    level::spatial::SpatialCategory,
 };
 use fyrox::{
-    asset::core::rand::prelude::IteratorRandom,
-    core::{rand::Rng, visitor::prelude::*},
+    asset::core::rand::prelude::{IteratorRandom, SliceRandom},
+    core::{
+        algebra::{Point3, Vector3},
+        pool::Handle,
+        rand::Rng,
+        visitor::prelude::*,
+    },
+    scene::{
+        collider::InteractionGroups,
+        graph::{
+            physics::{Intersection, RayCastOptions},
+            Graph,
+        },
+        node::Node,
+        rigidbody::{RigidBody, RigidBodyType},
+    },
     utils::behavior::{Behavior, Status},
 };
 
@@ -12,6 +31,176 @@ use fyrox::{
 pub struct DoMeleeAttack {
     attack_timeout: f32,
     attack_animation_index: u32,
+    /// Position of the hand at the previous tick, the hand is swept from it to its current
+    /// position.
+    #[visit(skip)]
+    last_hand_position: Option<Vector3<f32>>,
+    /// Actors and props that were hit by current attack, each of them can be hit only once.
+    #[visit(skip)]
+    struck: Vec<Handle<Node>>,
+}
+
+/// Casts rays along the segment from the center and from the edges of a sphere of the given
+/// radius, so it works as a (rough) sweep of the sphere. Returns intersections with every
+/// collider the sphere has touched.
+fn sweep_sphere(
+    graph: &Graph,
+    begin: Vector3<f32>,
+    end: Vector3<f32>,
+    radius: f32,
+) -> Vec<Intersection> {
+    let direction = end - begin;
+    let axis = match direction.try_normalize(f32::EPSILON) {
+        Some(axis) => axis,
+        None => return Default::default(),
+    };
+    let side = axis
+        .cross(&Vector3::y())
+        .try_normalize(f32::EPSILON)
+        .unwrap_or_else(Vector3::x);
+    let up = side.cross(&axis);
+
+    let mut intersections = Vec::<Intersection>::new();
+    let mut query_buffer = Vec::default();
+    for offset in [Vector3::default(), side, -side, up, -up] {
+        graph.physics.cast_ray(
+            RayCastOptions {
+                ray_origin: Point3::from(begin + offset.scale(radius)),
+                ray_direction: direction,
+                groups: InteractionGroups::default(),
+                max_len: direction.norm(),
+                sort_results: false,
+            },
+            &mut query_buffer,
+        );
+
+        for intersection in query_buffer.drain(..) {
+            if intersections
+                .iter()
+                .all(|i| i.collider != intersection.collider)
+            {
+                intersections.push(intersection);
+            }
+        }
+    }
+    intersections
+}
+
+/// Finds an actor whose hit box uses the collider. Actors without hit boxes are hit by their
+/// capsule, capsules of other actors are ignored (`None` is returned for them as a hit box).
+fn find_struck_actor(
+    graph: &Graph,
+    actors: impl Iterator<Item = Handle<Node>>,
+    collider: Handle<Node>,
+) -> Option<(Handle<Node>, Option<HitBox>)> {
+    for actor in actors {
+        if let Some(character) = try_get_character_ref(actor, graph) {
+            if let Some(hit_box) = character.hit_boxes.iter().find(|h| h.collider == collider) {
+                return Some((actor, Some(*hit_box)));
+            }
+            if character.capsule_collider == collider {
+                return Some((actor, None));
+            }
+        }
+    }
+    None
+}
+
+impl DoMeleeAttack {
+    /// Max distance from the hand to an actor that can be hit by the hand.
+    const REACH: f32 = 3.0;
+    /// Impulse that a melee hit applies to props.
+    const PROP_IMPULSE: f32 = 3.0;
+
+    /// Sweeps the hand from its previous position and hits actors and props it touched.
+    fn sweep(
+        &mut self,
+        context: &mut BehaviorContext,
+        begin: Vector3<f32>,
+        end: Vector3<f32>,
+        attack: &AttackAnimationDefinition,
+    ) {
+        let direction = (end - begin)
+            .try_normalize(f32::EPSILON)
+            .unwrap_or_default();
+
+        for intersection in sweep_sphere(&context.scene.graph, begin, end, attack.hit_radius) {
+            let graph = &mut context.scene.graph;
+
+            let actors = context
+                .spatial_index
+                .in_radius(end, Self::REACH, SpatialCategory::Actor);
+            if let Some((actor, hit_box)) = find_struck_actor(graph, actors, intersection.collider)
+            {
+                if actor == context.bot_handle || self.struck.contains(&actor) {
+                    continue;
+                }
+
+                let character = match try_get_character_ref(actor, graph) {
+                    Some(character) => character,
+                    None => continue,
+                };
+
+                // A capsule of an actor with hit boxes is not a body part.
+                if hit_box.is_none() && !character.hit_boxes.is_empty() {
+                    continue;
+                }
+
+                // Friends in the way are not hurt.
+                if context
+                    .factions
+                    .is_friendly(context.character.faction, character.faction)
+                {
+                    continue;
+                }
+
+                self.struck.push(actor);
+
+                let damage = attack
+                    .damage
+                    .scale(hit_box.map_or(1.0, |h| h.damage_factor));
+                if let Some(character) = try_get_character_mut(actor, graph) {
+                    character.push_command(CharacterCommand::Damage {
+                        source: DamageSource::Actor(context.bot_handle),
+                        hitbox: hit_box,
+                        amount: damage.amount(),
+                        kind: damage.kind(),
+                        critical_shot_probability: 0.0,
+                    });
+                    character.push_command(CharacterCommand::HitReaction { direction });
+                }
+
+                if let Some(hit_box) = hit_box {
+                    if let Some(bot) = try_get_bot_mut(actor, graph) {
+                        bot.commands_queue.push_back(BotCommand::HandleImpact {
+                            handle: hit_box.bone,
+                            impact_point: intersection.position.coords,
+                            direction,
+                        });
+                    }
+                }
+
+                *context.time_since_melee_hit = 0.0;
+            } else {
+                // Push props that were hit.
+                let body = graph[intersection.collider].parent();
+                if self.struck.contains(&body) {
+                    continue;
+                }
+
+                if let Some(rigid_body) = graph
+                    .try_get_mut(body)
+                    .and_then(|n| n.cast_mut::<RigidBody>())
+                {
+                    if rigid_body.body_type() == RigidBodyType::Dynamic {
+                        rigid_body.wake_up();
+                        rigid_body.apply_impulse(direction.scale(Self::PROP_IMPULSE));
+                        self.struck.push(body);
+                    }
+                }
+            }
+        }
+    }
 }
 
 fn can_shoot(upper_body_machine: &UpperBodyMachine, definition: &BotDefinition) -> bool {
@@ -34,9 +223,11 @@ impl<'a> Behavior<'a> for DoMeleeAttack {
             // HACK: setting this to false messes up animation, so set speed to 0.0.
             attack_animation.set_enabled(true).set_speed(0.0).rewind();
 
-            self.attack_animation_index = fyrox::core::rand::thread_rng()
-                .gen_range(0..context.upper_body_machine.attack_animations.len())
-                as u32;
+            let mut rng = fyrox::core::rand::thread_rng();
+            self.attack_animation_index = match context.attacks.choose(&mut rng) {
+                Some(&attack) => attack as u32,
+                None => rng.gen_range(0..context.upper_body_machine.attack_animations.len()) as u32,
+            };
 
             context
                 .scene
@@ -46,10 +237,11 @@ impl<'a> Behavior<'a> for DoMeleeAttack {
                         [self.attack_animation_index as usize],
                 )
                 .set_enabled(true)
-                .set_speed(1.3)
+                .set_speed(1.3 * context.character.stasis_factor())
                 .rewind();
 
             context.is_attacking = true;
+            self.struck.clear();
         }
 
         if self.attack_timeout < 0.0 && attack_animation_ended {
@@ -59,34 +251,39 @@ impl<'a> Behavior<'a> for DoMeleeAttack {
 
         context.attack_animation_index = self.attack_animation_index as usize;
 
-        // Apply damage to target from melee attack
-        if let Some(target) = context.target.as_ref() {
+        let hand_position = context
+            .scene
+            .graph
+            .try_get(context.hand)
+            .map(|hand| hand.global_position());
+
+        // Melee attack hits whatever the hand touches during the hit window of the attack.
+        let status = if context.target.is_some() {
+            let is_striking = context.upper_body_machine.machine.active_state()
+                == context.upper_body_machine.attack_state
+                && !can_shoot(context.upper_body_machine, context.definition);
+
+            let definition = context.definition;
+            let attack = &definition.attack_animations[self.attack_animation_index as usize];
+            let animation = &context.scene.animations[context.upper_body_machine.attack_animations
+                [self.attack_animation_index as usize]];
+            let time = animation.get_time_position();
+            let in_hit_window = animation.is_enabled()
+                && (time - attack.timestamp).abs() <= attack.hit_duration * 0.5;
+
+            if is_striking && in_hit_window {
+                if let (Some(begin), Some(end)) = (self.last_hand_position, hand_position) {
+                    self.sweep(context, begin, end, attack);
+                }
+            }
+
             while let Some(event) = context
                 .scene
                 .animations
                 .get_mut(current_attack_animation)
                 .pop_event()
             {
-                if event.signal_id == UpperBodyMachine::HIT_SIGNAL
-                    && context.upper_body_machine.machine.active_state()
-                        == context.upper_body_machine.attack_state
-                    && !can_shoot(context.upper_body_machine, context.definition)
-                {
-                    if let Some(character) =
-                        try_get_character_mut(target.handle, &mut context.scene.graph)
-                    {
-                        character.push_command(CharacterCommand::Damage {
-                            who: Default::default(),
-                            hitbox: None,
-                            /// TODO: Find hit box maybe?
-                            amount: context.definition.attack_animations
-                                [self.attack_animation_index as usize]
-                                .damage
-                                .amount(),
-                            critical_shot_probability: 0.0,
-                        });
-                    }
-
+                if event.signal_id == UpperBodyMachine::HIT_SIGNAL && is_striking {
                     if let Some(attack_sound) = context
                         .definition
                         .attack_sounds
@@ -108,7 +305,11 @@ impl<'a> Behavior<'a> for DoMeleeAttack {
             Status::Success
         } else {
             Status::Failure
-        }
+        };
+
+        self.last_hand_position = hand_position;
+
+        status
     }
 }
 
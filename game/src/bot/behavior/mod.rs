@@ -4,38 +4,51 @@ use crate::{
         behavior::{
             aim::AimOnTarget,
             death::{IsDead, StayDead},
+            definition::BehaviorNodeDefinition,
             find::FindTarget,
+            grab::GrabTarget,
+            hear::{HearNoise, InvestigateNoise},
             melee::{CanMeleeAttack, DoMeleeAttack},
-            movement::MoveToTarget,
+            movement::{MoveToTarget, Retreat},
+            patrol::Patrol,
+            reanimate::{FindCorpse, ReanimateCorpse},
+            search::SearchTarget,
             shoot::{CanShootTarget, ShootTarget},
             threat::{NeedsThreatenTarget, ThreatenTarget},
         },
         lower_body::LowerBodyMachine,
+        navigation::LinkTraversal,
+        patrol::{IdleActivity, PatrolState},
         upper_body::UpperBodyMachine,
-        BotDefinition, BotKind, Target,
+        BotDefinition, BotKind, Target, TargetMemory,
     },
     character::Character,
+    faction::FactionRelations,
+    level::{corpse::CorpseContainer, spatial::SpatialIndex},
     utils::BodyImpactHandler,
     MessageSender,
 };
 use fyrox::{
-    core::{math::SmoothAngle, pool::Handle, visitor::prelude::*},
+    core::{algebra::Vector3, math::SmoothAngle, pool::Handle, visitor::prelude::*},
+    engine::resource_manager::ResourceManager,
     scene::{node::Node, Scene},
     utils::{
-        behavior::{
-            composite::{CompositeNode, CompositeNodeKind},
-            leaf::LeafNode,
-            Behavior, BehaviorTree, Status,
-        },
+        behavior::{Behavior, BehaviorTree, Status},
         navmesh::NavmeshAgent,
     },
 };
 
 pub mod aim;
 pub mod death;
+pub mod definition;
 pub mod find;
+pub mod grab;
+pub mod hear;
 pub mod melee;
 pub mod movement;
+pub mod patrol;
+pub mod reanimate;
+pub mod search;
 pub mod shoot;
 pub mod threat;
 
@@ -49,10 +62,18 @@ pub enum Action {
     CanMeleeAttack(CanMeleeAttack),
     AimOnTarget(AimOnTarget),
     DoMeleeAttack(DoMeleeAttack),
+    GrabTarget(GrabTarget),
     CanShootTarget(CanShootTarget),
     ShootTarget(ShootTarget),
     NeedsThreatenTarget(NeedsThreatenTarget),
     ThreatenTarget(ThreatenTarget),
+    HearNoise(HearNoise),
+    InvestigateNoise(InvestigateNoise),
+    SearchTarget(SearchTarget),
+    Patrol(Patrol),
+    Retreat(Retreat),
+    FindCorpse(FindCorpse),
+    ReanimateCorpse(ReanimateCorpse),
 }
 
 impl Default for Action {
@@ -70,6 +91,7 @@ impl<'a> Behavior<'a> for Action {
             Action::FindTarget(v) => v.tick(context),
             Action::MoveToTarget(v) => v.tick(context),
             Action::DoMeleeAttack(v) => v.tick(context),
+            Action::GrabTarget(v) => v.tick(context),
             Action::ShootTarget(v) => v.tick(context),
             Action::CanMeleeAttack(v) => v.tick(context),
             Action::IsDead(v) => v.tick(context),
@@ -78,6 +100,13 @@ impl<'a> Behavior<'a> for Action {
             Action::CanShootTarget(v) => v.tick(context),
             Action::NeedsThreatenTarget(v) => v.tick(context),
             Action::ThreatenTarget(v) => v.tick(context),
+            Action::HearNoise(v) => v.tick(context),
+            Action::InvestigateNoise(v) => v.tick(context),
+            Action::SearchTarget(v) => v.tick(context),
+            Action::Patrol(v) => v.tick(context),
+            Action::Retreat(v) => v.tick(context),
+            Action::FindCorpse(v) => v.tick(context),
+            Action::ReanimateCorpse(v) => v.tick(context),
         }
     }
 }
@@ -85,6 +114,9 @@ impl<'a> Behavior<'a> for Action {
 pub struct BehaviorContext<'a> {
     pub scene: &'a mut Scene,
     pub actors: &'a [Handle<Node>],
+    pub spatial_index: &'a SpatialIndex,
+    pub factions: &'a FactionRelations,
+    pub navigation_links: &'a [Handle<Node>],
     pub bot_handle: Handle<Node>,
     pub sender: &'a MessageSender,
     pub dt: f32,
@@ -92,27 +124,48 @@ pub struct BehaviorContext<'a> {
     pub upper_body_machine: &'a UpperBodyMachine,
     pub lower_body_machine: &'a LowerBodyMachine,
     pub target: &'a mut Option<Target>,
+    /// Position of a noise that should be investigated.
+    pub noise_position: &'a mut Option<Vector3<f32>>,
+    pub target_memory: &'a mut Option<TargetMemory>,
+    pub patrol_route: Handle<Node>,
+    pub patrol: &'a mut PatrolState,
     pub definition: &'static BotDefinition,
     pub character: &'a mut Character,
     pub kind: BotKind,
     pub agent: &'a mut NavmeshAgent,
+    /// Off-mesh link that the bot is traversing.
+    pub link_traversal: &'a mut Option<LinkTraversal>,
     pub impact_handler: &'a BodyImpactHandler,
     pub model: Handle<Node>,
+    /// Hand bone that is used to find what melee attacks hit.
+    pub hand: Handle<Node>,
     pub restoration_time: f32,
     pub v_recoil: &'a mut SmoothAngle,
     pub h_recoil: &'a mut SmoothAngle,
     pub move_speed: f32,
     pub target_move_speed: &'a mut f32,
     pub threaten_timeout: &'a mut f32,
+    /// Time passed since the last melee hit of the bot.
+    pub time_since_melee_hit: &'a mut f32,
+    /// Indices of attack animations that the bot can use, empty means all of them.
+    pub attacks: &'static [usize],
     pub sound_manager: &'a SoundManager,
+    pub resource_manager: &'a ResourceManager,
+    pub corpses: &'a CorpseContainer,
+    /// A corpse that the bot is going to reanimate.
+    pub corpse: &'a mut Handle<Node>,
 
     // Output
     pub attack_animation_index: usize,
     pub movement_speed_factor: f32,
     pub is_moving: bool,
     pub is_attacking: bool,
+    /// The bot holds a grabbed target.
+    pub is_grabbing: bool,
     pub is_aiming_weapon: bool,
     pub is_screaming: bool,
+    pub idle_activity: Option<IdleActivity>,
+    pub is_reanimating: bool,
 }
 
 #[derive(Default, Debug, Visit, Clone)]
@@ -121,61 +174,10 @@ pub struct BotBehavior {
 }
 
 impl BotBehavior {
-    pub fn new(spine: Handle<Node>, definition: &BotDefinition) -> Self {
+    pub fn new(spine: Handle<Node>, behavior: &BehaviorNodeDefinition) -> Self {
         let mut tree = BehaviorTree::new();
 
-        let entry = CompositeNode::new_selector(vec![
-            CompositeNode::new_sequence(vec![
-                IsDead::new_action(&mut tree),
-                StayDead::new_action(&mut tree),
-            ])
-            .add_to(&mut tree),
-            CompositeNode::new_sequence(vec![
-                LeafNode::new(Action::FindTarget(FindTarget::default())).add_to(&mut tree),
-                CompositeNode::new_sequence(vec![
-                    LeafNode::new(AimOnTarget::new_action(spine)).add_to(&mut tree),
-                    CompositeNode::new(
-                        CompositeNodeKind::Selector,
-                        vec![
-                            CompositeNode::new_sequence(vec![
-                                LeafNode::new(Action::NeedsThreatenTarget(
-                                    NeedsThreatenTarget::default(),
-                                ))
-                                .add_to(&mut tree),
-                                LeafNode::new(Action::ThreatenTarget(ThreatenTarget::default()))
-                                    .add_to(&mut tree),
-                            ])
-                            .add_to(&mut tree),
-                            CompositeNode::new_sequence(vec![
-                                LeafNode::new(Action::CanShootTarget(CanShootTarget))
-                                    .add_to(&mut tree),
-                                LeafNode::new(Action::MoveToTarget(MoveToTarget {
-                                    min_distance: 4.0,
-                                }))
-                                .add_to(&mut tree),
-                                LeafNode::new(Action::ShootTarget(ShootTarget)).add_to(&mut tree),
-                            ])
-                            .add_to(&mut tree),
-                            CompositeNode::new_sequence(vec![
-                                LeafNode::new(Action::MoveToTarget(MoveToTarget {
-                                    min_distance: definition.close_combat_distance,
-                                }))
-                                .add_to(&mut tree),
-                                LeafNode::new(Action::CanMeleeAttack(CanMeleeAttack))
-                                    .add_to(&mut tree),
-                                LeafNode::new(Action::DoMeleeAttack(DoMeleeAttack::default()))
-                                    .add_to(&mut tree),
-                            ])
-                            .add_to(&mut tree),
-                        ],
-                    )
-                    .add_to(&mut tree),
-                ])
-                .add_to(&mut tree),
-            ])
-            .add_to(&mut tree),
-        ])
-        .add_to(&mut tree);
+        let entry = behavior.build(&mut tree, spine);
 
         tree.set_entry_node(entry);
 
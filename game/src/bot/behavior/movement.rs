@@ -1,10 +1,24 @@
 use crate::{
-    bot::{behavior::BehaviorContext, lower_body::LowerBodyMachine, upper_body::UpperBodyMachine},
+    bot::{
+        behavior::BehaviorContext,
+        coordinator,
+        lower_body::LowerBodyMachine,
+        navigation::{traverse_link, LinkTraversal},
+        upper_body::UpperBodyMachine,
+    },
     character::HitBox,
+    level::navigation::{
+        closest_navmesh, closest_navmesh_point, first_link_on_route, navmesh_mut, NavigationLink,
+    },
+    sound::Noise,
     utils::BodyImpactHandler,
 };
 use fyrox::{
-    core::{algebra::Vector3, visitor::prelude::*},
+    core::{
+        algebra::{UnitQuaternion, Vector3},
+        math::SmoothAngle,
+        visitor::prelude::*,
+    },
     scene::Scene,
     utils::behavior::{Behavior, Status},
 };
@@ -14,6 +28,35 @@ pub struct MoveToTarget {
     pub min_distance: f32,
 }
 
+/// Runs away from the target for some time after a successful melee hit, allows to make
+/// hit-and-run attackers.
+#[derive(Debug, PartialEq, Visit, Clone)]
+pub struct Retreat {
+    distance: f32,
+    duration: f32,
+    yaw: SmoothAngle,
+}
+
+impl Default for Retreat {
+    fn default() -> Self {
+        Self::new(5.0, 2.0)
+    }
+}
+
+impl Retreat {
+    pub fn new(distance: f32, duration: f32) -> Self {
+        Self {
+            distance,
+            duration,
+            yaw: SmoothAngle {
+                angle: 0.0,
+                target: 0.0,
+                speed: 360.0f32.to_radians(),
+            },
+        }
+    }
+}
+
 fn calculate_movement_speed_factor(
     upper_body_machine: &UpperBodyMachine,
     hit_boxes: &[HitBox],
@@ -41,64 +84,202 @@ impl<'a> Behavior<'a> for MoveToTarget {
     type Context = BehaviorContext<'a>;
 
     fn tick(&mut self, context: &mut Self::Context) -> Status {
-        context.movement_speed_factor = calculate_movement_speed_factor(
-            context.upper_body_machine,
-            &context.character.hit_boxes,
-            context.impact_handler,
+        let (destination, tolerance) = match context.target.as_ref() {
+            Some(target) => {
+                let destination = coordinator::attack_position(
+                    &context.scene.graph,
+                    context.actors,
+                    context.bot_handle,
+                    target,
+                    0.5 * self.min_distance,
+                );
+
+                // The bot must stop within `min_distance` from the target itself, not from its
+                // attack position, otherwise it could stop out of reach of its attacks.
+                let tolerance = self.min_distance - destination.metric_distance(&target.position);
+
+                (Some(destination), tolerance)
+            }
+            None => (None, self.min_distance),
+        };
+
+        move_to(context, destination, tolerance)
+    }
+}
+
+impl<'a> Behavior<'a> for Retreat {
+    type Context = BehaviorContext<'a>;
+
+    fn tick(&mut self, context: &mut Self::Context) -> Status {
+        if *context.time_since_melee_hit >= self.duration {
+            return Status::Failure;
+        }
+
+        let target_position = match context.target.as_ref() {
+            Some(target) => target.position,
+            None => return Status::Failure,
+        };
+
+        let position = context.character.position(&context.scene.graph);
+        let away = (position - target_position)
+            .try_normalize(f32::EPSILON)
+            .unwrap_or_else(|| -context.scene.graph[context.model].look_vector());
+        let destination = closest_navmesh_point(
             context.scene,
+            position + Vector3::new(away.x, 0.0, away.z).scale(self.distance),
         );
 
-        let body = context.scene.graph[context.character.body].as_rigid_body_mut();
-        let position = body.global_position();
+        move_to(context, Some(destination), 0.5);
+        look_along_path(context, &mut self.yaw, destination);
 
-        *context.target_move_speed = context.definition.walk_speed * context.movement_speed_factor;
+        Status::Running
+    }
+}
 
-        context.agent.set_speed(context.move_speed);
-        let navmesh = context.scene.navmeshes.iter_mut().next().unwrap();
-        context.agent.set_position(position);
+/// Moves bot along a path to the destination, keeps moving to the last destination if there is
+/// none. Returns [`Status::Success`] if the bot is within `min_distance` from the destination.
+pub fn move_to(
+    context: &mut BehaviorContext,
+    destination: Option<Vector3<f32>>,
+    min_distance: f32,
+) -> Status {
+    context.movement_speed_factor = calculate_movement_speed_factor(
+        context.upper_body_machine,
+        &context.character.hit_boxes,
+        context.impact_handler,
+        context.scene,
+    ) * context.character.stasis_factor();
 
-        if let Some(target) = context.target.as_ref() {
-            context.agent.set_target(target.position);
-            let _ = context.agent.update(context.dt, navmesh);
+    let position = context.character.position(&context.scene.graph);
+
+    *context.target_move_speed = context.definition.walk_speed * context.movement_speed_factor;
+
+    // Off-mesh links take control over the bot until it reaches the other end of a link.
+    if let Some(mut traversal) = context.link_traversal.take() {
+        if !traverse_link(context, &mut traversal) {
+            *context.link_traversal = Some(traversal);
         }
+        emit_step_sounds(context);
+        return Status::Running;
+    }
 
-        let has_reached_destination =
-            context.agent.target().metric_distance(&position) <= self.min_distance;
-        if has_reached_destination {
-            body.set_lin_vel(Vector3::new(0.0, body.lin_vel().y, 0.0));
-        } else {
-            let mut vel = (context.agent.position() - position).scale(1.0 / context.dt);
-            vel.y = body.lin_vel().y;
-            body.set_lin_vel(vel);
+    let navmesh_index = match closest_navmesh(context.scene, position) {
+        Some((index, _)) => index,
+        None => {
+            // There's nowhere to go.
+            context.is_moving = false;
+            return Status::Failure;
         }
+    };
+
+    // Destination could be on other navmesh, in this case the bot must go to the first link
+    // on a route to the navmesh.
+    let mut link_entrance = None;
+    if let Some(destination) = destination {
+        if let Some((destination_navmesh, _)) = closest_navmesh(context.scene, destination) {
+            if destination_navmesh != navmesh_index {
+                if let Some((link, reversed)) = first_link_on_route(
+                    &context.scene.graph,
+                    context.navigation_links,
+                    navmesh_index,
+                    destination_navmesh,
+                ) {
+                    let entrance = context.scene.graph[link]
+                        .try_get_script::<NavigationLink>()
+                        .map(|l| l.ends(link, &context.scene.graph, reversed).entrance)
+                        .unwrap_or(destination);
 
-        // Emit step sounds from walking animation.
-        if context.lower_body_machine.is_walking() {
-            while let Some(event) = context
-                .scene
-                .animations
-                .get_mut(context.lower_body_machine.walk_animation)
-                .pop_event()
-            {
-                if event.signal_id == LowerBodyMachine::STEP_SIGNAL {
-                    let begin = context.scene.graph[context.model].global_position()
-                        + Vector3::new(0.0, 0.5, 0.0);
-
-                    context.character.footstep_ray_check(
-                        begin,
-                        context.scene,
-                        context.sound_manager,
-                    );
+                    if position.metric_distance(&entrance) <= LinkTraversal::START_DISTANCE {
+                        *context.link_traversal = Some(LinkTraversal::new(link, reversed));
+                    }
+
+                    link_entrance = Some(entrance);
                 }
             }
         }
+    }
 
-        if has_reached_destination {
-            context.is_moving = false;
-            Status::Success
-        } else {
-            context.is_moving = true;
-            Status::Running
+    context.agent.set_speed(context.move_speed);
+    context.agent.set_position(position);
+
+    if let Some(destination) = link_entrance.or(destination) {
+        context.agent.set_target(destination);
+        if let Some(navmesh) = navmesh_mut(context.scene, navmesh_index) {
+            let _ = context.agent.update(context.dt, navmesh);
+        }
+    }
+
+    let has_reached_destination = link_entrance.is_none()
+        && context.agent.target().metric_distance(&position) <= min_distance;
+
+    let body = context.scene.graph[context.character.body].as_rigid_body_mut();
+    if has_reached_destination {
+        body.set_lin_vel(Vector3::new(0.0, body.lin_vel().y, 0.0));
+    } else {
+        let mut vel = (context.agent.position() - position).scale(1.0 / context.dt);
+        vel.y = body.lin_vel().y;
+        body.set_lin_vel(vel);
+    }
+
+    emit_step_sounds(context);
+
+    if has_reached_destination {
+        context.is_moving = false;
+        Status::Success
+    } else {
+        context.is_moving = true;
+        Status::Running
+    }
+}
+
+/// Emits step sounds from walking animation.
+fn emit_step_sounds(context: &mut BehaviorContext) {
+    if context.lower_body_machine.is_walking() {
+        while let Some(event) = context
+            .scene
+            .animations
+            .get_mut(context.lower_body_machine.walk_animation)
+            .pop_event()
+        {
+            if event.signal_id == LowerBodyMachine::STEP_SIGNAL {
+                let begin = context.scene.graph[context.model].global_position()
+                    + Vector3::new(0.0, 0.5, 0.0);
+
+                context.character.footstep_ray_check(
+                    context.bot_handle,
+                    begin,
+                    context.scene,
+                    context.sound_manager,
+                    Noise::WALK_LOUDNESS * context.movement_speed_factor,
+                );
+            }
         }
     }
 }
+
+/// Smoothly turns the bot to where it goes, or to the `fallback` point if it is standing.
+pub fn look_along_path(
+    context: &mut BehaviorContext,
+    yaw: &mut SmoothAngle,
+    fallback: Vector3<f32>,
+) {
+    let position = context.character.position(&context.scene.graph);
+
+    let look_dir = match (context.agent.position() - position).try_normalize(0.01) {
+        Some(direction) if context.is_moving => direction,
+        _ => fallback - position,
+    };
+
+    let local_look = context.scene.graph[context.model].look_vector();
+    yaw.angle = local_look.x.atan2(local_look.z);
+    yaw.set_target(look_dir.x.atan2(look_dir.z))
+        .update(context.dt);
+
+    if let Some(body) = context.scene.graph.try_get_mut(context.character.body) {
+        body.local_transform_mut()
+            .set_rotation(UnitQuaternion::from_axis_angle(
+                &Vector3::y_axis(),
+                yaw.angle(),
+            ));
+    }
+}
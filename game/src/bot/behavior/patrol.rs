@@ -0,0 +1,126 @@
+use crate::bot::{
+    behavior::{
+        movement::{look_along_path, move_to},
+        BehaviorContext,
+    },
+    patrol::{PatrolRoute, Waypoint},
+};
+use fyrox::{
+    core::{math::SmoothAngle, visitor::prelude::*},
+    rand::{seq::SliceRandom, thread_rng, Rng},
+    utils::behavior::{Behavior, Status},
+};
+
+/// Moves the bot along its patrol route (if any) and does idle activities at route points. The
+/// bot also makes idle sounds from time to time.
+#[derive(Debug, PartialEq, Visit, Clone)]
+pub struct Patrol {
+    yaw: SmoothAngle,
+}
+
+impl Default for Patrol {
+    fn default() -> Self {
+        Self {
+            yaw: SmoothAngle {
+                angle: 0.0,
+                target: 0.0,
+                speed: 90.0f32.to_radians(),
+            },
+        }
+    }
+}
+
+impl Patrol {
+    /// Max distance from a point at which the bot can do an activity of the point.
+    const ACTIVITY_DISTANCE: f32 = 1.5;
+
+    fn play_idle_sounds(ctx: &mut BehaviorContext) {
+        ctx.patrol.idle_sound_timer -= ctx.dt;
+        if ctx.patrol.idle_sound_timer <= 0.0 {
+            ctx.patrol.idle_sound_timer = thread_rng().gen_range(6.0..15.0);
+
+            if let Some(sound) = ctx.definition.idle_sounds.choose(&mut thread_rng()) {
+                let position = ctx.character.position(&ctx.scene.graph);
+                ctx.sound_manager
+                    .play_sound(&mut ctx.scene.graph, sound, position, 0.8, 1.0, 0.6);
+            }
+        }
+    }
+}
+
+impl<'a> Behavior<'a> for Patrol {
+    type Context = BehaviorContext<'a>;
+
+    fn tick(&mut self, ctx: &mut Self::Context) -> Status {
+        Self::play_idle_sounds(ctx);
+
+        let route = match ctx
+            .scene
+            .graph
+            .try_get(ctx.patrol_route)
+            .and_then(|n| n.try_get_script::<PatrolRoute>())
+        {
+            Some(route) if !route.points.is_empty() => route,
+            // Nothing to do, just stand still.
+            _ => return Status::Running,
+        };
+
+        let index = (ctx.patrol.point_index as usize).min(route.points.len() - 1);
+        let mut forward = ctx.patrol.forward;
+        let next_index = route.next_point(index, &mut forward) as u32;
+
+        let (destination, look, waypoint) = match ctx.scene.graph.try_get(*route.points[index]) {
+            Some(point) => (
+                point.global_position(),
+                point.look_vector(),
+                point
+                    .try_get_script::<Waypoint>()
+                    .map(|w| (*w.activity, *w.duration)),
+            ),
+            None => {
+                // Point was deleted, skip it.
+                ctx.patrol.point_index = next_index;
+                ctx.patrol.forward = forward;
+                return Status::Running;
+            }
+        };
+
+        let position = ctx.character.position(&ctx.scene.graph);
+
+        if let Some(activity) = ctx.patrol.activity {
+            // The bot could be distracted by something, so it must return to the point first.
+            if position.metric_distance(&destination) <= Self::ACTIVITY_DISTANCE {
+                ctx.idle_activity = Some(activity);
+
+                look_along_path(ctx, &mut self.yaw, position + ctx.patrol.activity_look);
+
+                if let Some(time_left) = ctx.patrol.activity_time_left.as_mut() {
+                    *time_left -= ctx.dt;
+                    if *time_left <= 0.0 {
+                        ctx.patrol.activity = None;
+                        ctx.patrol.point_index = next_index;
+                        ctx.patrol.forward = forward;
+                    }
+                }
+
+                return Status::Running;
+            }
+        }
+
+        if move_to(ctx, Some(destination), 0.5) == Status::Success && ctx.patrol.activity.is_none()
+        {
+            if let Some((activity, duration)) = waypoint {
+                ctx.patrol.activity = Some(activity);
+                ctx.patrol.activity_time_left = if duration > 0.0 { Some(duration) } else { None };
+                ctx.patrol.activity_look = look;
+            } else {
+                ctx.patrol.point_index = next_index;
+                ctx.patrol.forward = forward;
+            }
+        }
+
+        look_along_path(ctx, &mut self.yaw, destination);
+
+        Status::Running
+    }
+}
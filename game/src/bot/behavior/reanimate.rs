@@ -0,0 +1,171 @@
+use crate::{
+    bot::{
+        behavior::{
+            movement::{look_along_path, move_to},
+            BehaviorContext,
+        },
+        patrol::IdleActivity,
+        Bot, BotKind,
+    },
+    level::corpse::{corpse_position, CorpseState},
+};
+use fyrox::{
+    core::{
+        algebra::{UnitQuaternion, Vector3},
+        math::SmoothAngle,
+        pool::Handle,
+        visitor::prelude::*,
+    },
+    rand::{seq::SliceRandom, thread_rng},
+    scene::node::Node,
+    utils::behavior::{Behavior, Status},
+};
+use std::cmp::Ordering;
+
+/// Selects the closest corpse that could be reanimated.
+#[derive(Default, Debug, PartialEq, Visit, Clone)]
+pub struct FindCorpse {
+    pub radius: f32,
+}
+
+impl<'a> Behavior<'a> for FindCorpse {
+    type Context = BehaviorContext<'a>;
+
+    fn tick(&mut self, ctx: &mut Self::Context) -> Status {
+        let self_handle = ctx.bot_handle;
+        let available = |state: CorpseState, reanimator: Handle<Node>| {
+            state == CorpseState::Intact
+                || state == CorpseState::Reanimating && reanimator == self_handle
+        };
+
+        // Keep the current corpse while it is available.
+        if let Some(corpse) = ctx.corpses.get(*ctx.corpse) {
+            if available(corpse.state, corpse.reanimator) {
+                return Status::Success;
+            }
+        }
+
+        let position = ctx.character.position(&ctx.scene.graph);
+
+        *ctx.corpse = ctx
+            .corpses
+            .corpses()
+            .into_iter()
+            .filter(|c| available(c.state, c.reanimator))
+            .map(|c| {
+                let distance = corpse_position(&ctx.scene.graph, c.node).metric_distance(&position);
+                (c.node, distance)
+            })
+            .filter(|(_, distance)| *distance <= self.radius)
+            .min_by(|(_, a), (_, b)| a.partial_cmp(b).unwrap_or(Ordering::Equal))
+            .map(|(node, _)| node)
+            .unwrap_or_default();
+
+        if ctx.corpse.is_some() {
+            Status::Success
+        } else {
+            Status::Failure
+        }
+    }
+}
+
+/// Moves the bot to the selected corpse and reanimates it, the corpse turns into a bot of the
+/// given kind.
+#[derive(Debug, PartialEq, Visit, Clone)]
+pub struct ReanimateCorpse {
+    pub kind: BotKind,
+    pub duration: f32,
+    yaw: SmoothAngle,
+}
+
+impl Default for ReanimateCorpse {
+    fn default() -> Self {
+        Self::new(BotKind::Zombie, 5.0)
+    }
+}
+
+impl ReanimateCorpse {
+    /// Max distance from the corpse at which the bot is able to reanimate it.
+    const DISTANCE: f32 = 1.0;
+
+    pub fn new(kind: BotKind, duration: f32) -> Self {
+        Self {
+            kind,
+            duration,
+            yaw: SmoothAngle {
+                angle: 0.0,
+                target: 0.0,
+                speed: 180.0f32.to_radians(),
+            },
+        }
+    }
+}
+
+impl<'a> Behavior<'a> for ReanimateCorpse {
+    type Context = BehaviorContext<'a>;
+
+    fn tick(&mut self, ctx: &mut Self::Context) -> Status {
+        let corpse = *ctx.corpse;
+        if !ctx.scene.graph.is_valid_handle(corpse) {
+            *ctx.corpse = Handle::NONE;
+            return Status::Failure;
+        }
+
+        let corpse_position = corpse_position(&ctx.scene.graph, corpse);
+
+        if move_to(ctx, Some(corpse_position), Self::DISTANCE) != Status::Success {
+            look_along_path(ctx, &mut self.yaw, corpse_position);
+            return Status::Running;
+        }
+
+        look_along_path(ctx, &mut self.yaw, corpse_position);
+
+        let progress = match ctx.corpses.reanimate(corpse, ctx.bot_handle, ctx.dt) {
+            Some(progress) => progress,
+            None => {
+                *ctx.corpse = Handle::NONE;
+                return Status::Failure;
+            }
+        };
+
+        ctx.is_reanimating = true;
+        ctx.idle_activity = Some(IdleActivity::EatCorpse);
+
+        if progress < self.duration {
+            return Status::Running;
+        }
+
+        // The corpse is turned into a new bot at the same place.
+        let look = corpse_position - ctx.character.position(&ctx.scene.graph);
+        let rotation = UnitQuaternion::face_towards(
+            &Vector3::new(look.x, 0.0, look.z)
+                .try_normalize(f32::EPSILON)
+                .unwrap_or_else(Vector3::z),
+            &Vector3::y(),
+        );
+        ctx.scene.graph.remove_node(corpse);
+        ctx.corpses.unregister(corpse);
+        *ctx.corpse = Handle::NONE;
+
+        Bot::add_to_scene(
+            ctx.scene,
+            self.kind,
+            ctx.resource_manager,
+            corpse_position,
+            rotation,
+        );
+
+        if let Some(scream_sound) = ctx.definition.scream_sounds.choose(&mut thread_rng()) {
+            ctx.sound_manager.play_sound(
+                &mut ctx.scene.graph,
+                scream_sound,
+                corpse_position,
+                1.0,
+                1.0,
+                1.0,
+            );
+        }
+
+        Status::Success
+    }
+}
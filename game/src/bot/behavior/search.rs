@@ -0,0 +1,74 @@
+use crate::{
+    bot::behavior::{
+        movement::{look_along_path, move_to},
+        BehaviorContext,
+    },
+    level::navigation::{closest_navmesh_point, random_navmesh_point},
+};
+use fyrox::{
+    core::{algebra::Vector3, math::SmoothAngle, visitor::prelude::*},
+    utils::behavior::{Behavior, Status},
+};
+
+/// Moves the bot to the place where it saw its target last time (with some prediction) and then
+/// searches random points around it until the bot forgets about the target.
+#[derive(Debug, PartialEq, Visit, Clone)]
+pub struct SearchTarget {
+    yaw: SmoothAngle,
+}
+
+impl Default for SearchTarget {
+    fn default() -> Self {
+        Self {
+            yaw: SmoothAngle {
+                angle: 0.0,
+                target: 0.0,
+                speed: 180.0f32.to_radians(),
+            },
+        }
+    }
+}
+
+impl SearchTarget {
+    /// How far ahead (in seconds) the bot predicts movement of a lost target.
+    const PREDICTION_TIME: f32 = 1.5;
+    const SEARCH_RADIUS: f32 = 8.0;
+}
+
+impl<'a> Behavior<'a> for SearchTarget {
+    type Context = BehaviorContext<'a>;
+
+    fn tick(&mut self, ctx: &mut Self::Context) -> Status {
+        let memory = match ctx.target_memory.as_mut() {
+            Some(memory) => memory,
+            None => return Status::Failure,
+        };
+
+        let destination = if memory.searching {
+            if memory.search_point.is_none() {
+                memory.search_point =
+                    random_navmesh_point(ctx.scene, memory.position, Self::SEARCH_RADIUS);
+            }
+            memory.search_point.unwrap_or(memory.position)
+        } else {
+            let velocity = Vector3::new(memory.velocity.x, 0.0, memory.velocity.z);
+            closest_navmesh_point(
+                ctx.scene,
+                memory.position + velocity.scale(Self::PREDICTION_TIME),
+            )
+        };
+
+        if move_to(ctx, Some(destination), 1.0) == Status::Success {
+            if let Some(memory) = ctx.target_memory.as_mut() {
+                // Predicted position is checked, pick next point to search at.
+                memory.searching = true;
+                memory.search_point = None;
+            }
+        }
+
+        look_along_path(ctx, &mut self.yaw, destination);
+
+        // Keep searching until the target is forgotten.
+        Status::Running
+    }
+}
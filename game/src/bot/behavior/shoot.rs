@@ -24,24 +24,14 @@ impl<'a> Behavior<'a> for ShootTarget {
 
             context.is_aiming_weapon = true;
 
-            let weapon = weapon_ref(weapon_handle, &context.scene.graph);
-            if weapon.can_shoot(context.elapsed_time as f32) {
-                let ammo_per_shot = weapon.definition.ammo_consumption_per_shot;
-
-                if context
-                    .character
-                    .inventory
-                    .try_extract_exact_items(ItemKind::Ammo, ammo_per_shot)
-                    == ammo_per_shot
-                {
-                    context
-                        .v_recoil
-                        .set_target(weapon.definition.gen_v_recoil_angle());
-                    context
-                        .h_recoil
-                        .set_target(weapon.definition.gen_h_recoil_angle());
+            let weapon = weapon_mut(weapon_handle, &mut context.scene.graph);
+            // Bots never overheat their weapons, they just wait until a weapon cools down.
+            if weapon.can_shoot(context.elapsed_time as f32) && !weapon.is_about_to_overheat() {
+                if weapon.try_consume_ammo(&mut context.character.inventory) {
+                    context.v_recoil.set_target(weapon.gen_v_recoil_angle());
+                    context.h_recoil.set_target(weapon.gen_h_recoil_angle());
 
-                    weapon_mut(weapon_handle, &mut context.scene.graph).request_shot(None);
+                    weapon.request_shot(None);
 
                     return Status::Success;
                 } else {
@@ -68,9 +58,10 @@ impl<'a> Behavior<'a> for CanShootTarget {
         {
             let weapon_handle = *weapon;
             let weapon = weapon_ref(weapon_handle, &context.scene.graph);
-            let ammo_per_shot = weapon.definition.ammo_consumption_per_shot;
+            let ammo_per_shot = weapon.ammo_required_for_shot();
 
             if context.restoration_time <= 0.0
+                && !weapon.is_overheated()
                 && context.definition.can_use_weapons
                 && context
                     .character
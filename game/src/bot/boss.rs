@@ -0,0 +1,103 @@
+//! Bosses are bots with several phases. Each phase starts when health of the boss drops below
+//! some fraction of its max health and has its own behavior, set of attacks and weak points.
+
+use crate::{
+    bot::{behavior::definition::BehaviorNodeDefinition, BotDefinition},
+    character::HitBox,
+};
+use fyrox::scene::graph::Graph;
+use serde::Deserialize;
+
+#[derive(Deserialize, Debug)]
+pub struct BossPhaseDefinition {
+    /// Fraction of max health of the boss at which the phase starts, `(0; 1]`. The first phase
+    /// must start at `1.0`.
+    pub health_threshold: f32,
+    pub behavior: BehaviorNodeDefinition,
+    /// Indices of attack animations that can be used in the phase, empty means all attacks.
+    #[serde(default)]
+    pub attacks: Vec<usize>,
+    /// Names of hit box bones (or colliders) that take damage in the phase, other hit boxes are
+    /// invulnerable. Empty means the boss can be damaged as usual.
+    #[serde(default)]
+    pub weak_points: Vec<String>,
+}
+
+#[derive(Deserialize, Debug)]
+pub struct BossDefinition {
+    /// Name of the boss shown above its health bar.
+    pub name: String,
+    pub phases: Vec<BossPhaseDefinition>,
+}
+
+impl BossDefinition {
+    pub fn validate(&self, definition: &BotDefinition) -> Result<(), String> {
+        if self.phases.is_empty() {
+            return Err("boss must have at least one phase".to_owned());
+        }
+
+        if self.phases[0].health_threshold != 1.0 {
+            return Err("first boss phase must have 1.0 health threshold".to_owned());
+        }
+
+        for (index, phase) in self.phases.iter().enumerate() {
+            if phase.health_threshold <= 0.0 || phase.health_threshold > 1.0 {
+                return Err(format!(
+                    "health threshold of boss phase {} must be in (0; 1] range",
+                    index
+                ));
+            }
+
+            if index > 0 && phase.health_threshold >= self.phases[index - 1].health_threshold {
+                return Err(format!(
+                    "health thresholds of boss phases must descend, but phase {} does not",
+                    index
+                ));
+            }
+
+            if let Some(attack) = phase
+                .attacks
+                .iter()
+                .find(|&&attack| attack >= definition.attack_animations.len())
+            {
+                return Err(format!(
+                    "boss phase {} uses attack {} that does not exist",
+                    index, attack
+                ));
+            }
+
+            phase
+                .behavior
+                .validate(definition)
+                .map_err(|e| format!("boss phase {}: {}", index, e))?;
+        }
+
+        Ok(())
+    }
+
+    /// Returns index of a phase for the given fraction of max health.
+    pub fn phase_index(&self, health_fraction: f32) -> usize {
+        self.phases
+            .iter()
+            .rposition(|phase| health_fraction <= phase.health_threshold)
+            .unwrap_or_default()
+    }
+}
+
+impl BossPhaseDefinition {
+    /// Checks whether the hit box takes damage in the phase, `None` means the damage was not
+    /// caused by a hit (an explosion for example).
+    pub fn is_vulnerable(&self, hitbox: Option<&HitBox>, graph: &Graph) -> bool {
+        if self.weak_points.is_empty() {
+            return true;
+        }
+
+        hitbox.map_or(false, |hitbox| {
+            [hitbox.bone, hitbox.collider].iter().any(|&node| {
+                graph.try_get(node).map_or(false, |node| {
+                    self.weak_points.iter().any(|name| name == node.name())
+                })
+            })
+        })
+    }
+}
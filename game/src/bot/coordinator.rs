@@ -0,0 +1,58 @@
+//! Group behavior of bots: alerting allies and spreading attackers around their target.
+
+use crate::bot::{Bot, Target};
+use fyrox::{
+    core::{algebra::Vector3, pool::Handle},
+    scene::{graph::Graph, node::Node},
+};
+use std::cmp::Ordering;
+
+/// Returns a point around the target at which the bot should attack it. Every bot that attacks
+/// the same target gets its own point on a circle around the target, points are assigned in
+/// the order of current angles of attackers around the target, so they won't cross paths.
+pub fn attack_position(
+    graph: &Graph,
+    actors: &[Handle<Node>],
+    self_handle: Handle<Node>,
+    target: &Target,
+    distance: f32,
+) -> Vector3<f32> {
+    let angle_around_target = |actor: Handle<Node>| {
+        let offset = graph[actor].global_position() - target.position;
+        offset.z.atan2(offset.x)
+    };
+
+    let mut attackers = actors
+        .iter()
+        .filter(|&&actor| {
+            // Script of the bot itself is not available while it is updating.
+            actor == self_handle
+                || graph
+                    .try_get(actor)
+                    .and_then(|n| n.try_get_script::<Bot>())
+                    .map_or(false, |bot| {
+                        !bot.is_dead()
+                            && bot
+                                .target
+                                .as_ref()
+                                .map_or(false, |t| t.handle == target.handle)
+                    })
+        })
+        .map(|&actor| (actor, angle_around_target(actor)))
+        .collect::<Vec<_>>();
+
+    if attackers.len() < 2 {
+        return target.position;
+    }
+
+    attackers.sort_by(|a, b| a.1.partial_cmp(&b.1).unwrap_or(Ordering::Equal));
+
+    let index = attackers
+        .iter()
+        .position(|(actor, _)| *actor == self_handle)
+        .unwrap_or_default();
+
+    let angle = attackers[0].1 + index as f32 * std::f32::consts::TAU / attackers.len() as f32;
+
+    target.position + Vector3::new(angle.cos(), 0.0, angle.sin()).scale(distance)
+}
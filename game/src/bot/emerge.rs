@@ -0,0 +1,34 @@
+//! Emerging of a bot from a hiding place (a vent for example). The bot is moved from the hiding
+//! place to an exit point ignoring physics, it does nothing else while emerging.
+
+use fyrox::core::{algebra::Vector3, visitor::prelude::*};
+
+#[derive(Visit, Default, Debug, Clone)]
+pub struct Emerge {
+    from: Vector3<f32>,
+    to: Vector3<f32>,
+    duration: f32,
+    elapsed: f32,
+}
+
+impl Emerge {
+    pub fn new(from: Vector3<f32>, to: Vector3<f32>, duration: f32) -> Self {
+        Self {
+            from,
+            to,
+            duration,
+            elapsed: 0.0,
+        }
+    }
+
+    /// Advances the emerging and returns new position of the bot.
+    pub fn update(&mut self, dt: f32) -> Vector3<f32> {
+        self.elapsed += dt;
+        let t = (self.elapsed / self.duration.max(f32::EPSILON)).min(1.0);
+        self.from.lerp(&self.to, t)
+    }
+
+    pub fn is_finished(&self) -> bool {
+        self.elapsed >= self.duration
+    }
+}
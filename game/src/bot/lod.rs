@@ -0,0 +1,163 @@
+//! Level of detail of bot AI. Bots that are far from the player or out of the player's view update
+//! their behavior less often (time-sliced across frames) and dormant bots do not update at all.
+//! Skipped time is accumulated and applied to timers of a bot on its next update.
+
+use fyrox::core::{algebra::Vector3, visitor::prelude::*};
+use std::{cell::Cell, fmt::Display, time::Duration};
+
+#[derive(Copy, Clone, PartialEq, Eq, Debug, Visit)]
+#[repr(u32)]
+pub enum BotLod {
+    /// Updates every frame.
+    Full = 0,
+    Reduced = 1,
+    Distant = 2,
+    /// Does not update until the player comes closer.
+    Dormant = 3,
+}
+
+impl Default for BotLod {
+    fn default() -> Self {
+        Self::Full
+    }
+}
+
+impl BotLod {
+    const FULL_DISTANCE: f32 = 15.0;
+    const REDUCED_DISTANCE: f32 = 35.0;
+    const DORMANT_DISTANCE: f32 = 70.0;
+    /// Half of the angle of the view of the player, bots outside of it are considered off-screen.
+    const HALF_VIEW_ANGLE: f32 = 60.0;
+
+    /// Selects level of detail of a bot at the position. `view` is position and look direction
+    /// of the player's camera, engaged bots (fighting, traversing links, etc.) always update
+    /// every frame.
+    pub fn select(
+        position: Vector3<f32>,
+        view: Option<(Vector3<f32>, Vector3<f32>)>,
+        engaged: bool,
+    ) -> Self {
+        let (eye, look) = match view {
+            Some(view) if !engaged => view,
+            _ => return Self::Full,
+        };
+
+        let to_bot = position - eye;
+        let distance = to_bot.norm();
+
+        if distance > Self::DORMANT_DISTANCE {
+            return Self::Dormant;
+        }
+
+        let lod = if distance > Self::REDUCED_DISTANCE {
+            Self::Distant
+        } else if distance > Self::FULL_DISTANCE {
+            Self::Reduced
+        } else {
+            Self::Full
+        };
+
+        let on_screen = to_bot.angle(&look) <= Self::HALF_VIEW_ANGLE.to_radians();
+        match lod {
+            Self::Full if !on_screen => Self::Reduced,
+            Self::Reduced if !on_screen => Self::Distant,
+            _ => lod,
+        }
+    }
+
+    /// Returns amount of frames between updates, `None` means that the bot does not update.
+    pub fn update_interval(self) -> Option<u32> {
+        match self {
+            Self::Full => Some(1),
+            Self::Reduced => Some(3),
+            Self::Distant => Some(8),
+            Self::Dormant => None,
+        }
+    }
+}
+
+#[derive(Debug, Visit, Default, Clone)]
+pub struct LodState {
+    pub lod: BotLod,
+    /// Frame counter, its initial value spreads updates of bots across frames.
+    frame: u32,
+    /// Time passed since the last update.
+    skipped_time: f32,
+}
+
+impl LodState {
+    /// Max time step of behavior and animations, longer periods are applied only to timers.
+    pub const MAX_STEP: f32 = 0.25;
+
+    /// Spreads updates of bots across frames, bots with different slots update in different
+    /// frames. Skipped time is preserved, so a loaded bot catches up on its first update.
+    pub fn set_slot(&mut self, slot: u32) {
+        self.frame = slot;
+    }
+
+    /// Advances the state by a frame and returns time since the last update if the bot must be
+    /// updated in this frame.
+    pub fn advance(&mut self, lod: BotLod, dt: f32) -> Option<f32> {
+        self.lod = lod;
+        self.frame = self.frame.wrapping_add(1);
+        self.skipped_time += dt;
+
+        match lod.update_interval() {
+            Some(interval) if self.frame % interval == 0 => {
+                Some(std::mem::take(&mut self.skipped_time))
+            }
+            _ => None,
+        }
+    }
+}
+
+/// Time spent on bot updates and amount of bots per level of detail, bots register themselves
+/// during their update.
+#[derive(Default, Debug)]
+pub struct BotStatistics {
+    update_time: Cell<Duration>,
+    counts: Cell<[u32; 4]>,
+    last_update_time: Duration,
+    last_counts: [u32; 4],
+    accumulated_update_time: Duration,
+    accumulated_frames: u32,
+}
+
+impl BotStatistics {
+    pub fn register(&self, lod: BotLod, time: Duration) {
+        self.update_time.set(self.update_time.get() + time);
+        let mut counts = self.counts.get();
+        counts[lod as usize] += 1;
+        self.counts.set(counts);
+    }
+
+    /// Finishes the frame, statistics of the frame are shown until the next one.
+    pub fn flush(&mut self) {
+        self.last_update_time = self.update_time.take();
+        self.last_counts = self.counts.take();
+        self.accumulated_update_time += self.last_update_time;
+        self.accumulated_frames += 1;
+    }
+
+    /// Returns average time spent on bot updates per frame since the previous call.
+    pub fn take_average_update_time(&mut self) -> Duration {
+        let average = self
+            .accumulated_update_time
+            .checked_div(self.accumulated_frames)
+            .unwrap_or_default();
+        self.accumulated_update_time = Duration::default();
+        self.accumulated_frames = 0;
+        average
+    }
+}
+
+impl Display for BotStatistics {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let [full, reduced, distant, dormant] = self.last_counts;
+        write!(
+            f,
+            "Bots: {} full, {} reduced, {} distant, {} dormant\nBots Update Time: {:?}",
+            full, reduced, distant, dormant, self.last_update_time
+        )
+    }
+}
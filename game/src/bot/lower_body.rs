@@ -1,10 +1,10 @@
 use crate::{
-    bot::{clean_machine, BotDefinition},
-    utils::create_play_animation_state,
+    bot::{clean_machine, make_idle_activity_state, BotDefinition},
+    utils::{create_play_animation_state, model_map::ModelMap},
 };
 use fyrox::{
     animation::{
-        machine::{Machine, Parameter, State, Transition},
+        machine::{Machine, Parameter, PoseNode, State, Transition},
         Animation, AnimationSignal,
     },
     core::{
@@ -30,6 +30,10 @@ pub struct LowerBodyMachineInput {
     pub scream: bool,
     pub dead: bool,
     pub movement_speed_factor: f32,
+    /// Scale of animations speed, used to slow down bots in stasis.
+    pub time_scale: f32,
+    /// Index of current idle activity in the bot definition.
+    pub idle_activity: Option<u32>,
 }
 
 impl LowerBodyMachine {
@@ -43,6 +47,11 @@ impl LowerBodyMachine {
     const SCREAM_TO_IDLE: &'static str = "ScreamToIdle";
     const WALK_TO_DYING: &'static str = "WalkToDying";
     const IDLE_TO_DYING: &'static str = "IdleToDying";
+    const IDLE_TO_ACTIVITY: &'static str = "IdleToActivity";
+    const ACTIVITY_TO_IDLE: &'static str = "ActivityToIdle";
+    const ACTIVITY_TO_DYING: &'static str = "ActivityToDying";
+
+    const ACTIVITY_INDEX: &'static str = "ActivityIndex";
 
     pub async fn new(
         resource_manager: ResourceManager,
@@ -55,11 +64,16 @@ impl LowerBodyMachine {
             walk_animation_resource,
             scream_animation_resource,
             dying_animation_resource,
+            activity_resources,
         ) = fyrox::core::futures::join!(
             resource_manager.request_model(&definition.idle_animation,),
             resource_manager.request_model(&definition.walk_animation,),
             resource_manager.request_model(&definition.scream_animation,),
             resource_manager.request_model(&definition.dying_animation,),
+            ModelMap::new(
+                definition.idle_activities.iter().map(|a| &a.animation),
+                resource_manager.clone()
+            ),
         );
 
         let mut machine = Machine::new(model);
@@ -110,6 +124,18 @@ impl LowerBodyMachine {
             .set_enabled(false)
             .set_speed(1.0);
 
+        let (activity_state, _) = make_idle_activity_state(
+            &mut machine,
+            scene,
+            model,
+            Self::ACTIVITY_INDEX,
+            definition
+                .idle_activities
+                .iter()
+                .map(|a| activity_resources[&a.animation].clone())
+                .collect(),
+        );
+
         scene.animations[walk_animation]
             .add_signal(AnimationSignal::new(Self::STEP_SIGNAL, 0.3))
             .add_signal(AnimationSignal::new(Self::STEP_SIGNAL, 0.6));
@@ -171,6 +197,30 @@ impl LowerBodyMachine {
             Self::IDLE_TO_DYING,
         ));
 
+        if activity_state.is_some() {
+            machine.add_transition(Transition::new(
+                "Idle->Activity",
+                idle_state,
+                activity_state,
+                0.3,
+                Self::IDLE_TO_ACTIVITY,
+            ));
+            machine.add_transition(Transition::new(
+                "Activity->Idle",
+                activity_state,
+                idle_state,
+                0.3,
+                Self::ACTIVITY_TO_IDLE,
+            ));
+            machine.add_transition(Transition::new(
+                "Activity->Dying",
+                activity_state,
+                dying_state,
+                0.2,
+                Self::ACTIVITY_TO_DYING,
+            ));
+        }
+
         machine.set_entry_state(idle_state);
 
         Self {
@@ -187,6 +237,12 @@ impl LowerBodyMachine {
     }
 
     pub fn apply(&mut self, scene: &mut Scene, dt: f32, input: LowerBodyMachineInput) {
+        for node in self.machine.nodes() {
+            if let PoseNode::PlayAnimation(node) = node {
+                scene.animations[node.animation].set_speed(input.time_scale);
+            }
+        }
+
         self.machine
             .set_parameter(Self::IDLE_TO_WALK, Parameter::Rule(input.walk))
             .set_parameter(Self::WALK_TO_IDLE, Parameter::Rule(!input.walk))
@@ -196,6 +252,19 @@ impl LowerBodyMachine {
             .set_parameter(Self::SCREAM_TO_IDLE, Parameter::Rule(!input.scream))
             .set_parameter(Self::WALK_TO_DYING, Parameter::Rule(input.dead))
             .set_parameter(Self::IDLE_TO_DYING, Parameter::Rule(input.dead))
+            .set_parameter(
+                Self::IDLE_TO_ACTIVITY,
+                Parameter::Rule(input.idle_activity.is_some() && !input.walk),
+            )
+            .set_parameter(
+                Self::ACTIVITY_TO_IDLE,
+                Parameter::Rule(input.idle_activity.is_none()),
+            )
+            .set_parameter(Self::ACTIVITY_TO_DYING, Parameter::Rule(input.dead))
+            .set_parameter(
+                Self::ACTIVITY_INDEX,
+                Parameter::Index(input.idle_activity.unwrap_or_default()),
+            )
             .evaluate_pose(&scene.animations, dt)
             .apply(&mut scene.graph);
     }
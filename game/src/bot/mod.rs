@@ -1,22 +1,35 @@
 use crate::{
     bot::{
-        behavior::{BehaviorContext, BotBehavior},
+        behavior::{definition::BehaviorNodeDefinition, BehaviorContext, BotBehavior},
+        boss::{BossDefinition, BossPhaseDefinition},
+        emerge::Emerge,
+        lod::{BotLod, LodState},
         lower_body::{LowerBodyMachine, LowerBodyMachineInput},
+        navigation::{open_or_bash_door, LinkTraversal},
+        patrol::{IdleActivityDefinition, PatrolState},
         upper_body::{UpperBodyMachine, UpperBodyMachineInput},
     },
-    character::{Character, CharacterCommand},
+    character::{try_get_character_ref, Character, CharacterCommand},
     current_level_mut, current_level_ref,
-    door::{door_mut, door_ref, DoorContainer},
+    door::{door_mut, Door, DoorLock},
+    faction::{Faction, FactionRelations},
     game_ref,
     inventory::{Inventory, ItemEntry},
-    level::item::ItemKind,
+    level::{
+        director::director_settings,
+        item::ItemKind,
+        spatial::{SpatialCategory, SpatialIndex},
+    },
+    player::Player,
     sound::SoundManager,
     utils::{is_probability_event_occurred, BodyImpactHandler},
     weapon::projectile::Damage,
-    Weapon,
 };
 use fyrox::{
-    animation::machine::{Machine, PoseNode},
+    animation::{
+        machine::{node::blend::IndexedBlendInput, Machine, PoseNode, State},
+        Animation,
+    },
     core::{
         algebra::{Point3, UnitQuaternion, Vector3},
         arrayvec::ArrayVec,
@@ -25,9 +38,10 @@ use fyrox::{
         inspect::prelude::*,
         math::SmoothAngle,
         pool::Handle,
-        rand::{seq::IteratorRandom, Rng},
+        rand::Rng,
         reflect::Reflect,
         uuid::{uuid, Uuid},
+        variable::InheritableVariable,
         visitor::{Visit, VisitResult, Visitor},
     },
     engine::resource_manager::ResourceManager,
@@ -35,6 +49,7 @@ use fyrox::{
     lazy_static::lazy_static,
     rand,
     rand::prelude::SliceRandom,
+    resource::model::Model,
     scene::{
         self,
         debug::SceneDrawingContext,
@@ -42,7 +57,7 @@ use fyrox::{
             physics::{Intersection, RayCastOptions},
             Graph,
         },
-        node::{Node, TypeUuidProvider},
+        node::{Node, NodeHandle, TypeUuidProvider},
         rigidbody::RigidBody,
         Scene,
     },
@@ -54,11 +69,18 @@ use std::{
     collections::{HashMap, VecDeque},
     fs::File,
     ops::{Deref, DerefMut},
+    time::Instant,
 };
 use strum_macros::{AsRefStr, EnumString, EnumVariantNames};
 
 mod behavior;
+mod boss;
+mod coordinator;
+mod emerge;
+pub mod lod;
 mod lower_body;
+mod navigation;
+pub mod patrol;
 mod upper_body;
 
 #[derive(
@@ -81,6 +103,13 @@ pub enum BotKind {
     Mutant = 0,
     Parasite = 1,
     Zombie = 2,
+    MutantBoss = 3,
+}
+
+impl Default for BotKind {
+    fn default() -> Self {
+        Self::Mutant
+    }
 }
 
 impl BotKind {
@@ -89,24 +118,48 @@ impl BotKind {
             BotKind::Mutant => "Mutant",
             BotKind::Parasite => "Parasite",
             BotKind::Zombie => "Zombie",
+            BotKind::MutantBoss => "Mutant Boss",
         }
     }
 }
 
-#[derive(Deserialize, Copy, Clone, PartialOrd, PartialEq, Ord, Eq, Hash, Debug)]
-#[repr(u32)]
-pub enum BotHostility {
-    Everyone = 0,
-    OtherSpecies = 1,
-    Player = 2,
-}
-
 #[derive(Debug, Visit, Default, Clone)]
 pub struct Target {
     position: Vector3<f32>,
     handle: Handle<Node>,
 }
 
+/// What a bot remembers about a target that went out of sight.
+#[derive(Debug, Visit, Default, Clone)]
+pub struct TargetMemory {
+    handle: Handle<Node>,
+    /// Position where the target was seen last time.
+    position: Vector3<f32>,
+    /// Velocity of the target at the moment when it was seen last time, used to predict where
+    /// the target went.
+    velocity: Vector3<f32>,
+    /// Time left before the bot forgets about the target.
+    time_remaining: f32,
+    /// Whether the bot has checked the predicted position and now searches nearby points.
+    searching: bool,
+    search_point: Option<Vector3<f32>>,
+}
+
+impl TargetMemory {
+    pub const DURATION: f32 = 15.0;
+
+    pub fn new(handle: Handle<Node>, position: Vector3<f32>, velocity: Vector3<f32>) -> Self {
+        Self {
+            handle,
+            position,
+            velocity,
+            time_remaining: Self::DURATION,
+            searching: false,
+            search_point: None,
+        }
+    }
+}
+
 #[derive(Debug, Clone)]
 pub enum BotCommand {
     HandleImpact {
@@ -114,6 +167,11 @@ pub enum BotCommand {
         impact_point: Vector3<f32>,
         direction: Vector3<f32>,
     },
+    /// An ally has found a target and asks for help.
+    Alert {
+        target: Handle<Node>,
+        position: Vector3<f32>,
+    },
 }
 
 #[derive(Visit, Reflect, Inspect, Debug, Clone)]
@@ -121,7 +179,35 @@ pub struct Bot {
     #[reflect(hidden)]
     #[inspect(skip)]
     target: Option<Target>,
+    #[visit(optional)]
+    #[reflect(hidden)]
+    #[inspect(skip)]
+    noise_position: Option<Vector3<f32>>,
+    #[visit(optional)]
+    #[reflect(hidden)]
+    #[inspect(skip)]
+    target_memory: Option<TargetMemory>,
     pub kind: BotKind,
+    #[visit(optional)]
+    #[inspect(description = "A node with Patrol Route script, can be empty.")]
+    patrol_route: InheritableVariable<Handle<Node>>,
+    #[visit(optional)]
+    #[inspect(
+        description = "Doors that are locked while the bot fights and unlocked when it dies."
+    )]
+    arena_doors: Vec<NodeHandle>,
+    #[visit(optional)]
+    #[reflect(hidden)]
+    #[inspect(skip)]
+    arena_locked: bool,
+    #[visit(optional)]
+    #[reflect(hidden)]
+    #[inspect(skip)]
+    boss_phase: u32,
+    #[visit(optional)]
+    #[reflect(hidden)]
+    #[inspect(skip)]
+    patrol: PatrolState,
     model: Handle<Node>,
     character: Character,
     #[visit(skip)]
@@ -139,6 +225,14 @@ pub struct Bot {
     #[reflect(hidden)]
     #[inspect(skip)]
     agent: NavmeshAgent,
+    #[visit(optional)]
+    #[reflect(hidden)]
+    #[inspect(skip)]
+    link_traversal: Option<LinkTraversal>,
+    #[visit(optional)]
+    #[reflect(hidden)]
+    #[inspect(skip)]
+    corpse: Handle<Node>,
     head_exploded: bool,
     #[visit(skip)]
     #[reflect(hidden)]
@@ -153,6 +247,38 @@ pub struct Bot {
     move_speed: f32,
     target_move_speed: f32,
     threaten_timeout: f32,
+    #[visit(optional)]
+    #[reflect(hidden)]
+    #[inspect(skip)]
+    alert_timeout: f32,
+    #[visit(optional)]
+    #[reflect(hidden)]
+    #[inspect(skip)]
+    time_since_melee_hit: f32,
+    #[visit(optional)]
+    #[reflect(hidden)]
+    #[inspect(skip)]
+    lod: LodState,
+    /// Faction set at runtime, it replaces the faction of the bot kind.
+    #[visit(optional)]
+    #[reflect(hidden)]
+    #[inspect(skip)]
+    faction_override: Option<Faction>,
+    /// Set while the bot emerges from an ambush spawner.
+    #[visit(optional)]
+    #[reflect(hidden)]
+    #[inspect(skip)]
+    emerge: Option<Emerge>,
+    /// Hand bone that is used to find what melee attacks hit.
+    #[visit(skip)]
+    #[reflect(hidden)]
+    #[inspect(skip)]
+    hand: Handle<Node>,
+    /// Time until the next hit of a door on the way that the bot can't open.
+    #[visit(optional)]
+    #[reflect(hidden)]
+    #[inspect(skip)]
+    door_bash_timer: f32,
     #[visit(skip)]
     #[reflect(hidden)]
     #[inspect(skip)]
@@ -180,14 +306,23 @@ impl Default for Bot {
         Self {
             character: Default::default(),
             kind: BotKind::Mutant,
+            patrol_route: Default::default(),
+            arena_doors: Default::default(),
+            arena_locked: false,
+            boss_phase: 0,
+            patrol: Default::default(),
             model: Default::default(),
             target: Default::default(),
+            noise_position: Default::default(),
+            target_memory: Default::default(),
             definition: Self::get_definition(BotKind::Mutant),
             lower_body_machine: Default::default(),
             upper_body_machine: Default::default(),
             restoration_time: 0.0,
             hips: Default::default(),
             agent: Default::default(),
+            link_traversal: Default::default(),
+            corpse: Default::default(),
             head_exploded: false,
             impact_handler: Default::default(),
             behavior: Default::default(),
@@ -197,6 +332,13 @@ impl Default for Bot {
             move_speed: 0.0,
             target_move_speed: 0.0,
             threaten_timeout: 0.0,
+            alert_timeout: 0.0,
+            time_since_melee_hit: f32::MAX,
+            lod: Default::default(),
+            faction_override: None,
+            emerge: None,
+            hand: Default::default(),
+            door_bash_timer: 0.0,
             commands_queue: Default::default(),
         }
     }
@@ -207,6 +349,11 @@ pub struct AttackAnimationDefinition {
     path: String,
     stick_timestamp: f32,
     timestamp: f32,
+    /// Duration of the window around `timestamp` in which the attack hits whatever the hand
+    /// touches.
+    hit_duration: f32,
+    /// Radius of the sphere that is swept along the hand during the hit window.
+    hit_radius: f32,
     damage: Damage,
     speed: f32,
 }
@@ -226,12 +373,18 @@ pub struct BotDefinition {
     pub hips: String,
     pub v_aim_angle_hack: f32,
     pub can_use_weapons: bool,
-    pub close_combat_distance: f32,
     pub pain_sounds: Vec<String>,
     pub scream_sounds: Vec<String>,
     pub idle_sounds: Vec<String>,
     pub attack_sounds: Vec<String>,
-    pub hostility: BotHostility,
+    pub faction: Faction,
+    /// Damage dealt to a door per hit when the bot can't open it, zero - the bot never bashes
+    /// doors.
+    pub door_damage: f32,
+    /// Animations of activities that a bot can do at patrol route points, activities without
+    /// animation are played as idle.
+    #[serde(default)]
+    pub idle_activities: Vec<IdleActivityDefinition>,
 
     // Animations.
     pub idle_animation: String,
@@ -240,6 +393,21 @@ pub struct BotDefinition {
     pub walk_animation: String,
     pub aim_animation: String,
     pub dying_animation: String,
+    /// Animation that is played while the bot holds a grabbed target, required by `GrabTarget`.
+    #[serde(default)]
+    pub grab_animation: Option<String>,
+    /// Animation that is played while the bot emerges from an ambush spawner (crawling out of
+    /// a vent for example). None of the bots has such a clip yet, so they emerge playing their
+    /// usual animations.
+    #[serde(default)]
+    pub emerge_animation: Option<String>,
+
+    /// Behavior tree of the bot, validated when definitions are loaded.
+    pub behavior: BehaviorNodeDefinition,
+
+    /// Phases of a boss, each phase replaces the behavior tree of the bot.
+    #[serde(default)]
+    pub boss: Option<BossDefinition>,
 }
 
 #[derive(Deserialize, Default)]
@@ -250,7 +418,19 @@ pub struct BotDefinitionsContainer {
 impl BotDefinitionsContainer {
     pub fn new() -> Self {
         let file = File::open("data/configs/bots.ron").unwrap();
-        ron::de::from_reader(file).unwrap()
+        let container: Self = ron::de::from_reader(file).unwrap();
+
+        for (kind, definition) in container.map.iter() {
+            if let Err(error) = definition.behavior.validate(definition) {
+                panic!("Invalid behavior of {:?} bot: {}", kind, error);
+            }
+
+            if let Some(Err(error)) = definition.boss.as_ref().map(|b| b.validate(definition)) {
+                panic!("Invalid boss definition of {:?} bot: {}", kind, error);
+            }
+        }
+
+        container
     }
 }
 
@@ -259,6 +439,17 @@ lazy_static! {
 }
 
 impl Bot {
+    /// Max distance at which allies can hear an alert.
+    const ALERT_RADIUS: f32 = 15.0;
+    /// Min time between alerts, prevents spamming with screams.
+    const ALERT_INTERVAL: f32 = 10.0;
+    /// Time for which a bot stops attacking after a melee hit.
+    const HIT_REACTION_TIME: f32 = 0.5;
+    /// Max distance to a door in front of the bot, that the bot will try to open.
+    const DOOR_CHECK_DISTANCE: f32 = 1.5;
+    /// Damage that a corpse of the bot must take to be dismembered.
+    const DISMEMBER_DAMAGE: f32 = 100.0;
+
     pub fn get_definition(kind: BotKind) -> &'static BotDefinition {
         DEFINITIONS.map.get(&kind).unwrap()
     }
@@ -277,22 +468,148 @@ impl Bot {
 
         let node = &mut scene.graph[bot];
 
-        assert!(node.has_script::<Bot>());
-
         node.local_transform_mut()
             .set_position(position)
             .set_rotation(rotation);
 
+        let script = node
+            .try_get_script_mut::<Bot>()
+            .expect("Bot model must have Bot script!");
+
+        // Several kinds of bots could share the same model (bosses for example), so the kind,
+        // health and size of the model must be replaced. This is done only once when the bot is
+        // spawned, bots placed in the editor keep their own values and saved bots keep their state.
+        let definition = Self::get_definition(kind);
+        script.kind = kind;
+        script.health = definition.health;
+        script.last_health = definition.health;
+        let model = script.model;
+
+        let transform = scene.graph[model].local_transform_mut();
+        let scale = **transform.scale();
+        transform.set_scale(scale.scale(definition.scale));
+
         bot
     }
 
+    /// Returns current phase of the bot if it is a boss.
+    fn boss_phase(&self) -> Option<&'static BossPhaseDefinition> {
+        self.definition
+            .boss
+            .as_ref()
+            .and_then(|boss| boss.phases.get(self.boss_phase as usize))
+    }
+
+    fn behavior_definition(&self) -> &'static BehaviorNodeDefinition {
+        self.boss_phase()
+            .map_or(&self.definition.behavior, |phase| &phase.behavior)
+    }
+
+    /// Returns name of the boss and the fraction of its remaining health, if the bot is a boss
+    /// that fights at the moment.
+    pub fn boss_status(&self) -> Option<(&'static str, f32)> {
+        let boss = self.definition.boss.as_ref()?;
+        if self.is_dead() || self.target.is_none() {
+            return None;
+        }
+        Some((
+            boss.name.as_str(),
+            (self.health / self.definition.health).clamp(0.0, 1.0),
+        ))
+    }
+
+    /// Switches phase of the boss when its health drops below phase threshold.
+    fn update_boss_phase(&mut self, scene: &mut Scene, sound_manager: &SoundManager) {
+        let boss = match self.definition.boss.as_ref() {
+            Some(boss) if !self.is_dead() => boss,
+            _ => return,
+        };
+
+        let phase = boss.phase_index(self.health / self.definition.health) as u32;
+        if phase == self.boss_phase {
+            return;
+        }
+
+        self.boss_phase = phase;
+        self.behavior = BotBehavior::new(self.spine, self.behavior_definition());
+        // Let the boss scream about the new phase.
+        self.threaten_timeout = 0.0;
+
+        if let Some(scream_sound) = self
+            .definition
+            .scream_sounds
+            .choose(&mut rand::thread_rng())
+        {
+            let position = self.position(&scene.graph);
+            sound_manager.play_sound(&mut scene.graph, scream_sound, position, 1.0, 1.0, 1.0);
+        }
+    }
+
+    /// Makes the bot emerge from a hiding place (a vent for example), it moves from `from` to
+    /// `to` during `duration` seconds playing its emerge animation.
+    pub fn emerge(&mut self, from: Vector3<f32>, to: Vector3<f32>, duration: f32) {
+        self.emerge = Some(Emerge::new(from, to, duration));
+    }
+
+    /// Moves emerging bot, returns true if the bot is still emerging.
+    fn update_emerge(&mut self, graph: &mut Graph, dt: f32) -> bool {
+        if self.character.is_dead() {
+            self.emerge = None;
+        }
+
+        let emerge = match self.emerge.as_mut() {
+            Some(emerge) => emerge,
+            None => return false,
+        };
+
+        let position = emerge.update(dt);
+        let body = graph[self.character.body].as_rigid_body_mut();
+        body.set_lin_vel(Vector3::default());
+        body.local_transform_mut().set_position(position);
+
+        if emerge.is_finished() {
+            self.emerge = None;
+        }
+
+        true
+    }
+
+    /// Locks arena doors when the bot starts fighting and unlocks them when it dies.
+    fn update_arena_lock(&mut self, graph: &mut Graph) {
+        let lock = !self.is_dead() && self.target.is_some();
+        // Losing the target does not open the arena, only death does.
+        if self.arena_locked == lock || (!lock && !self.is_dead()) {
+            return;
+        }
+
+        self.arena_locked = lock;
+
+        for door in self.arena_doors.iter() {
+            if let Some(door) = graph
+                .try_get_mut(**door)
+                .and_then(|n| n.try_get_script_mut::<Door>())
+            {
+                door.set_locked(DoorLock::Arena, lock);
+            }
+        }
+    }
+
     #[allow(clippy::unnecessary_to_owned)] // false positive
-    fn check_doors(&mut self, scene: &mut Scene, door_container: &DoorContainer) {
-        if let Some(target) = self.target.as_ref() {
+    fn check_doors(
+        &mut self,
+        scene: &mut Scene,
+        spatial_index: &SpatialIndex,
+        self_handle: Handle<Node>,
+        dt: f32,
+    ) {
+        // Check doors in the direction of movement, so the bot will open (or bash) doors on its
+        // path.
+        let velocity = scene.graph[self.body].as_rigid_body().lin_vel();
+        if let Some(direction) = Vector3::new(velocity.x, 0.0, velocity.z).try_normalize(0.1) {
             let mut query_storage = ArrayVec::<Intersection, 64>::new();
 
             let position = self.position(&scene.graph);
-            let ray_direction = target.position - position;
+            let ray_direction = direction.scale(Self::DOOR_CHECK_DISTANCE);
 
             scene.graph.physics.cast_ray(
                 RayCastOptions {
@@ -306,20 +623,21 @@ impl Bot {
             );
 
             for intersection in query_storage {
-                for &door_handle in &door_container.doors {
-                    let door = door_ref(door_handle, &scene.graph);
-
-                    let close_enough = position.metric_distance(&door.initial_position()) < 1.25;
-                    if !close_enough {
-                        continue;
-                    }
-
+                for door_handle in spatial_index.in_radius(position, 1.25, SpatialCategory::Door) {
                     for child in scene.graph[door_handle].children().to_vec() {
                         if let Some(rigid_body) = scene.graph[child].cast::<RigidBody>() {
                             for collider in rigid_body.children().to_vec() {
                                 if collider == intersection.collider {
-                                    let has_key = self.inventory.has_key();
-                                    door_mut(door_handle, &mut scene.graph).try_open(has_key);
+                                    open_or_bash_door(
+                                        door_mut(door_handle, &mut scene.graph),
+                                        &self.character.inventory,
+                                        self.definition.door_damage,
+                                        self_handle,
+                                        &mut self.door_bash_timer,
+                                        dt,
+                                    );
+                                    // Only the closest door blocks the way.
+                                    return;
                                 }
                             }
                         }
@@ -350,10 +668,71 @@ impl Bot {
         // context.draw_frustum(&self.frustum, Color::from_rgba(0, 200, 0, 255)); TODO
     }
 
+    pub fn is_targeting(&self, handle: Handle<Node>) -> bool {
+        self.target.as_ref().map_or(false, |t| t.handle == handle)
+    }
+
+    /// Moves the bot to another faction, current target is forgotten.
+    pub fn set_faction(&mut self, faction: Faction) {
+        self.faction_override = Some(faction);
+        self.faction = faction;
+        self.target = None;
+        self.target_memory = None;
+    }
+
     pub fn set_target(&mut self, handle: Handle<Node>, position: Vector3<f32>) {
         self.target = Some(Target { position, handle });
     }
 
+    /// Screams and asks nearby allies to attack current target.
+    fn alert_allies(
+        &mut self,
+        scene: &mut Scene,
+        self_handle: Handle<Node>,
+        actors: &[Handle<Node>],
+        factions: &FactionRelations,
+        sound_manager: &SoundManager,
+    ) {
+        let target = match self.target.as_ref() {
+            Some(target) if self.alert_timeout <= 0.0 => target.clone(),
+            _ => return,
+        };
+
+        self.alert_timeout = Self::ALERT_INTERVAL;
+
+        let position = self.position(&scene.graph);
+
+        if let Some(scream_sound) = self
+            .definition
+            .scream_sounds
+            .choose(&mut rand::thread_rng())
+        {
+            sound_manager.play_sound(&mut scene.graph, scream_sound, position, 1.0, 1.0, 1.0);
+        }
+
+        let faction = self.faction;
+        for &actor in actors {
+            if actor == self_handle
+                || actor == target.handle
+                || scene.graph[actor]
+                    .global_position()
+                    .metric_distance(&position)
+                    > Self::ALERT_RADIUS
+            {
+                continue;
+            }
+
+            if let Some(ally) = try_get_bot_mut(actor, &mut scene.graph) {
+                if !ally.is_dead() && factions.is_friendly(faction, ally.faction) {
+                    ally.commands_queue.push_back(BotCommand::Alert {
+                        target: target.handle,
+                        position: target.position,
+                    });
+                }
+            }
+        }
+    }
+
     pub fn blow_up_head(&mut self, _graph: &mut Graph) {
         self.head_exploded = true;
 
@@ -385,33 +764,55 @@ impl Bot {
         resource_manager: &ResourceManager,
         sound_manager: &SoundManager,
     ) {
-        while let Some(command) =
-            self.character
-                .poll_command(scene, self_handle, resource_manager, sound_manager)
-        {
+        // Bosses can be invulnerable to everything except their weak points. Such hits are
+        // dropped before the character applies them, so they do not spend armor either.
+        if let Some(phase) = self.boss_phase() {
+            let mut attackers = Vec::new();
+            let graph = &scene.graph;
+            self.character.commands.retain(|command| match command {
+                CharacterCommand::Damage { source, hitbox, .. }
+                    if !phase.is_vulnerable(hitbox.as_ref(), graph) =>
+                {
+                    attackers.push(source.responsible_actor());
+                    false
+                }
+                _ => true,
+            });
+
+            // The boss still notices who attacks it.
+            for attacker in attackers {
+                if attacker != self_handle {
+                    if let Some(character) = try_get_character_ref(attacker, &scene.graph) {
+                        self.set_target(attacker, character.position(&scene.graph));
+                    }
+                }
+            }
+        }
+
+        loop {
+            let command = match self.character.poll_command(
+                scene,
+                self_handle,
+                resource_manager,
+                sound_manager,
+            ) {
+                Some(command) => command,
+                None => break,
+            };
+
             if let CharacterCommand::Damage {
-                who,
+                source,
                 amount,
                 hitbox,
                 critical_shot_probability,
+                ..
             } = command
             {
-                if let Some(shooter_script) = scene.graph.try_get(who).and_then(|n| n.script()) {
-                    if let Some(character) = shooter_script.query_component_ref::<Character>() {
-                        self.set_target(who, character.position(&scene.graph));
-                    } else if let Some(weapon) = shooter_script.query_component_ref::<Weapon>() {
-                        if let Some(weapon_owner_script) =
-                            scene.graph.try_get(weapon.owner()).and_then(|n| n.script())
-                        {
-                            if let Some(character_owner) =
-                                weapon_owner_script.query_component_ref::<Character>()
-                            {
-                                self.set_target(
-                                    weapon.owner(),
-                                    character_owner.position(&scene.graph),
-                                );
-                            }
-                        }
+                // Retarget on whoever is responsible for the damage.
+                let attacker = source.responsible_actor();
+                if attacker != self_handle {
+                    if let Some(character) = try_get_character_ref(attacker, &scene.graph) {
+                        self.set_target(attacker, character.position(&scene.graph));
                     }
                 }
 
@@ -446,6 +847,11 @@ impl Bot {
                         );
                     }
                 }
+            } else if let CharacterCommand::HitReaction { .. } = command {
+                // Bots stagger after melee hits, except bosses.
+                if !self.is_dead() && self.definition.boss.is_none() {
+                    self.restoration_time = self.restoration_time.max(Self::HIT_REACTION_TIME);
+                }
             }
         }
 
@@ -458,11 +864,57 @@ impl Bot {
                 } => self
                     .impact_handler
                     .handle_impact(scene, handle, impact_point, direction),
+                BotCommand::Alert { target, position } => {
+                    // Do not switch targets in the middle of a fight.
+                    if self.target.is_none() && target != self_handle {
+                        self.set_target(target, position);
+                        // Alerted bots do not alert others, otherwise the whole level will be
+                        // alerted in a chain.
+                        self.alert_timeout = Self::ALERT_INTERVAL;
+                    }
+                }
             }
         }
     }
 }
 
+/// Creates a state that plays an animation of current idle activity, the animation is selected
+/// by given index parameter. Returns `Handle::NONE` if a bot has no idle activities.
+fn make_idle_activity_state(
+    machine: &mut Machine,
+    scene: &mut Scene,
+    model: Handle<Node>,
+    index_parameter: &str,
+    resources: Vec<Model>,
+) -> (Handle<State>, Vec<Handle<Animation>>) {
+    if resources.is_empty() {
+        return (Handle::NONE, Default::default());
+    }
+
+    let animations = resources
+        .into_iter()
+        .map(|resource| *resource.retarget_animations(model, scene).get(0).unwrap())
+        .collect::<Vec<_>>();
+
+    let poses = animations
+        .iter()
+        .map(|&animation| IndexedBlendInput {
+            blend_time: 0.3,
+            pose_source: machine.add_node(PoseNode::make_play_animation(animation)),
+        })
+        .collect::<Vec<_>>();
+
+    let node = machine.add_node(PoseNode::make_blend_animations_by_index(
+        index_parameter.to_owned(),
+        poses,
+    ));
+
+    (
+        machine.add_state(State::new("IdleActivity", node)),
+        animations,
+    )
+}
+
 fn clean_machine(machine: &Machine, scene: &mut Scene) {
     for node in machine.nodes() {
         if let PoseNode::PlayAnimation(node) = node {
@@ -500,15 +952,18 @@ impl ScriptTrait for Bot {
             (ItemKind::Medkit, 1),
             (ItemKind::Medpack, 1),
         ];
-        let mut items =
-            if let Some((item, count)) = possible_item.iter().choose(&mut rand::thread_rng()) {
-                vec![ItemEntry {
-                    kind: *item,
-                    amount: *count,
-                }]
-            } else {
-                Default::default()
-            };
+        let level = current_level_ref(context.plugins).unwrap();
+        let mut items = if let Some((item, count)) = level
+            .director
+            .choose_loot(director_settings(&level.map_path), &possible_item)
+        {
+            vec![ItemEntry {
+                kind: *item,
+                amount: *count,
+            }]
+        } else {
+            Default::default()
+        };
 
         if self.definition.can_use_weapons {
             items.push(ItemEntry {
@@ -523,7 +978,13 @@ impl ScriptTrait for Bot {
             .with_position(context.scene.graph[context.handle].global_position())
             .with_speed(self.definition.walk_speed)
             .build();
-        self.behavior = BotBehavior::new(self.spine, self.definition);
+        self.behavior = BotBehavior::new(self.spine, self.behavior_definition());
+        self.hand = context
+            .scene
+            .graph
+            .find_by_name(self.model, &self.definition.weapon_hand_name);
+        self.lod.set_slot(context.handle.index());
+        self.faction = self.faction_override.unwrap_or(self.definition.faction);
 
         current_level_mut(context.plugins)
             .unwrap()
@@ -540,13 +1001,19 @@ impl ScriptTrait for Bot {
             if let Some(position) = level.actors.iter().position(|a| *a == context.node_handle) {
                 level.actors.remove(position);
             }
+            level.corpses.unregister(context.node_handle);
         }
     }
 
     fn on_update(&mut self, ctx: &mut ScriptContext) {
+        let update_start = Instant::now();
+
         let game = game_ref(ctx.plugins);
         let level = current_level_ref(ctx.plugins).unwrap();
 
+        let prev_target = self.target.as_ref().map(|t| t.handle);
+
+        // Commands are handled every frame, so damage is never delayed by level of detail.
         self.poll_commands(
             ctx.scene,
             ctx.handle,
@@ -554,89 +1021,215 @@ impl ScriptTrait for Bot {
             &level.sound_manager,
         );
 
+        let lod = if level.disable_bot_lod {
+            BotLod::Full
+        } else {
+            let engaged = !self.is_dead()
+                && (self.target.is_some()
+                    || self.link_traversal.is_some()
+                    || self.corpse.is_some()
+                    || self.emerge.is_some());
+            let view = ctx
+                .scene
+                .graph
+                .try_get(level.player)
+                .and_then(|p| p.try_get_script::<Player>())
+                .and_then(|p| p.view(&ctx.scene.graph));
+            BotLod::select(self.position(&ctx.scene.graph), view, engaged)
+        };
+
+        let prev_lod = self.lod.lod;
+        let elapsed = match self.lod.advance(lod, ctx.dt) {
+            Some(elapsed) => elapsed,
+            None => {
+                if lod == BotLod::Dormant && prev_lod != BotLod::Dormant {
+                    // Frozen bot must not keep walking.
+                    let body = ctx.scene.graph[self.body].as_rigid_body_mut();
+                    body.set_lin_vel(Vector3::new(0.0, body.lin_vel().y, 0.0));
+                }
+                level.bot_statistics.register(lod, update_start.elapsed());
+                return;
+            }
+        };
+
+        self.update_stasis(elapsed);
+
+        // Everything that bot does is slowed down in stasis.
+        let timers_dt = elapsed * self.stasis_factor();
+        // Time skipped by bots with low level of detail is fully applied to timers only, behavior
+        // and animations are updated with limited step.
+        let dt = timers_dt.min(LodState::MAX_STEP);
+
+        self.update_boss_phase(ctx.scene, &level.sound_manager);
+
+        let attacks = self
+            .boss_phase()
+            .map(|phase| phase.attacks.as_slice())
+            .unwrap_or_default();
+
+        let is_emerging = self.update_emerge(&mut ctx.scene.graph, dt);
+
         let movement_speed_factor;
         let is_attacking;
+        let is_grabbing;
         let is_moving;
         let is_aiming;
         let attack_animation_index;
         let is_screaming;
+        let idle_activity;
+        let is_reanimating;
         {
             let mut behavior_ctx = BehaviorContext {
                 scene: ctx.scene,
                 actors: &level.actors,
+                spatial_index: &level.spatial_index,
+                factions: &level.factions,
+                navigation_links: &level.navigation_links,
                 bot_handle: ctx.handle,
                 sender: &game.message_sender,
-                dt: ctx.dt,
+                dt,
                 elapsed_time: ctx.elapsed_time,
                 upper_body_machine: &self.upper_body_machine,
                 lower_body_machine: &self.lower_body_machine,
                 target: &mut self.target,
+                noise_position: &mut self.noise_position,
+                target_memory: &mut self.target_memory,
+                patrol_route: *self.patrol_route,
+                patrol: &mut self.patrol,
                 definition: self.definition,
                 character: &mut self.character,
                 kind: self.kind,
                 agent: &mut self.agent,
+                link_traversal: &mut self.link_traversal,
                 impact_handler: &self.impact_handler,
                 model: self.model,
+                hand: self.hand,
                 restoration_time: self.restoration_time,
                 v_recoil: &mut self.v_recoil,
                 h_recoil: &mut self.h_recoil,
                 target_move_speed: &mut self.target_move_speed,
                 move_speed: self.move_speed,
                 threaten_timeout: &mut self.threaten_timeout,
+                time_since_melee_hit: &mut self.time_since_melee_hit,
+                attacks,
                 sound_manager: &level.sound_manager,
+                resource_manager: ctx.resource_manager,
+                corpses: &level.corpses,
+                corpse: &mut self.corpse,
 
                 // Output
                 attack_animation_index: 0,
                 movement_speed_factor: 1.0,
                 is_moving: false,
                 is_attacking: false,
+                is_grabbing: false,
                 is_aiming_weapon: false,
                 is_screaming: false,
+                idle_activity: None,
+                is_reanimating: false,
             };
 
-            self.behavior.tree.tick(&mut behavior_ctx);
+            // Emerging bot is not controlled by its behavior.
+            if !is_emerging {
+                self.behavior.tree.tick(&mut behavior_ctx);
+            }
 
             movement_speed_factor = behavior_ctx.movement_speed_factor;
             is_attacking = behavior_ctx.is_attacking;
+            is_grabbing = behavior_ctx.is_grabbing;
             is_moving = behavior_ctx.is_moving;
             is_aiming = behavior_ctx.is_aiming_weapon;
             attack_animation_index = behavior_ctx.attack_animation_index;
             is_screaming = behavior_ctx.is_screaming;
+            is_reanimating = behavior_ctx.is_reanimating;
+            idle_activity = behavior_ctx.idle_activity.and_then(|activity| {
+                self.definition
+                    .idle_activities
+                    .iter()
+                    .position(|d| d.activity == activity)
+                    .map(|i| i as u32)
+            });
         }
 
-        self.restoration_time -= ctx.dt;
+        self.restoration_time -= timers_dt;
         self.move_speed += (self.target_move_speed - self.move_speed) * 0.1;
-        self.threaten_timeout -= ctx.dt;
+        self.threaten_timeout -= timers_dt;
+        self.alert_timeout -= timers_dt;
+        self.time_since_melee_hit += timers_dt;
+
+        // Whenever the bot gets new target (by seeing it or by being attacked by it), it asks
+        // allies for help.
+        let new_target = self.target.as_ref().map(|t| t.handle);
+        if new_target.is_some() && new_target != prev_target {
+            self.alert_allies(
+                ctx.scene,
+                ctx.handle,
+                &level.actors,
+                &level.factions,
+                &level.sound_manager,
+            );
+        }
 
-        self.check_doors(ctx.scene, &level.doors_container);
+        // Memory about a lost target fades over time.
+        if let Some(memory) = self.target_memory.as_mut() {
+            memory.time_remaining -= timers_dt;
+        }
+        self.target_memory = self.target_memory.take().filter(|m| m.time_remaining > 0.0);
+
+        // Reanimation is interrupted if the bot is distracted by something (or killed).
+        if !is_reanimating && self.corpse.is_some() {
+            level.corpses.interrupt(self.corpse, ctx.handle);
+        }
+
+        if self.is_dead() {
+            level.corpses.register(ctx.handle);
+            if self.head_exploded || self.health < -Self::DISMEMBER_DAMAGE {
+                level.corpses.destroy(ctx.handle);
+            }
+        }
+
+        self.update_arena_lock(&mut ctx.scene.graph);
+
+        // Distant bots do not open doors, they will do it when the player comes closer.
+        if self.lod.lod == BotLod::Full {
+            self.check_doors(ctx.scene, &level.spatial_index, ctx.handle, dt);
+        }
+
+        let time_scale = self.stasis_factor();
 
         self.lower_body_machine.apply(
             ctx.scene,
-            ctx.dt,
+            dt,
             LowerBodyMachineInput {
                 walk: is_moving,
                 scream: is_screaming,
                 dead: self.is_dead(),
                 movement_speed_factor,
+                time_scale,
+                idle_activity,
             },
         );
 
         self.upper_body_machine.apply(
             ctx.scene,
-            ctx.dt,
+            dt,
             UpperBodyMachineInput {
                 attack: is_attacking,
+                grab: is_grabbing,
+                emerge: is_emerging,
                 walk: is_moving,
                 scream: is_screaming,
                 dead: self.is_dead(),
                 aim: is_aiming,
                 attack_animation_index: attack_animation_index as u32,
+                time_scale,
+                idle_activity,
             },
         );
-        self.impact_handler.update_and_apply(ctx.dt, ctx.scene);
+        self.impact_handler.update_and_apply(dt, ctx.scene);
 
-        self.v_recoil.update(ctx.dt);
-        self.h_recoil.update(ctx.dt);
+        self.v_recoil.update(dt);
+        self.h_recoil.update(dt);
 
         let spine_transform = ctx.scene.graph[self.spine].local_transform_mut();
         let rotation = **spine_transform.rotation();
@@ -657,6 +1250,10 @@ impl ScriptTrait for Bot {
                     .set_scale(Vector3::new(0.0, 0.0, 0.0));
             }
         }
+
+        level
+            .bot_statistics
+            .register(self.lod.lod, update_start.elapsed());
     }
 
     fn id(&self) -> Uuid {
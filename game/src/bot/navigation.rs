@@ -0,0 +1,258 @@
+//! Traversal of off-mesh links by bots, see [`crate::level::navigation`].
+
+use crate::{
+    bot::{behavior::BehaviorContext, patrol::IdleActivity},
+    door::{door_mut, Door, DoorState},
+    elevator::Elevator,
+    inventory::Inventory,
+    level::navigation::{NavigationLink, NavigationLinkKind},
+};
+use fyrox::{
+    core::{algebra::Vector3, pool::Handle, visitor::prelude::*},
+    scene::node::Node,
+};
+
+/// A link that the bot is traversing at the moment.
+#[derive(Debug, Visit, Default, Clone)]
+pub struct LinkTraversal {
+    pub link: Handle<Node>,
+    /// Traversal from the end of the link to its start.
+    pub reversed: bool,
+    /// Whether the bot is on the elevator platform.
+    boarded: bool,
+    /// Progress of jumps and crawls, `[0; 1]`.
+    progress: f32,
+    /// Time since the start of the traversal, used to give up on blocked links.
+    time: f32,
+    /// Time until the next hit of a door that the bot can't open.
+    #[visit(optional)]
+    bash_timer: f32,
+}
+
+impl LinkTraversal {
+    /// Max distance from an end of a link to start its traversal.
+    pub const START_DISTANCE: f32 = 0.6;
+    const EXIT_DISTANCE: f32 = 0.3;
+    const BOARD_DISTANCE: f32 = 0.5;
+    const JUMP_HEIGHT: f32 = 1.0;
+    const TIMEOUT: f32 = 20.0;
+
+    pub fn new(link: Handle<Node>, reversed: bool) -> Self {
+        Self {
+            link,
+            reversed,
+            boarded: false,
+            progress: 0.0,
+            time: 0.0,
+            bash_timer: 0.0,
+        }
+    }
+}
+
+/// Time between hits of a door that a bot can't open.
+const DOOR_BASH_INTERVAL: f32 = 1.2;
+
+/// Tries to open the door, locked doors (or doors with disabled panel) are bashed until they
+/// break. Returns `true` if the bot is bashing the door.
+pub fn open_or_bash_door(
+    door: &mut Door,
+    inventory: &Inventory,
+    damage: f32,
+    bot: Handle<Node>,
+    bash_timer: &mut f32,
+    dt: f32,
+) -> bool {
+    door.try_open(inventory);
+
+    if damage > 0.0 && door.can_be_bashed() && !door.can_be_opened(inventory) {
+        *bash_timer -= dt;
+        if *bash_timer <= 0.0 {
+            *bash_timer = DOOR_BASH_INTERVAL;
+            door.bash(damage, bot);
+        }
+        true
+    } else {
+        false
+    }
+}
+
+fn horizontal_distance(a: Vector3<f32>, b: Vector3<f32>) -> f32 {
+    Vector3::new(a.x - b.x, 0.0, a.z - b.z).norm()
+}
+
+/// Walks towards the point using physics, returns `true` if the point is reached.
+fn walk_to(context: &mut BehaviorContext, point: Vector3<f32>, speed: f32) -> bool {
+    let body = context.scene.graph[context.character.body].as_rigid_body_mut();
+    let position = body.global_position();
+
+    if horizontal_distance(position, point) <= LinkTraversal::EXIT_DISTANCE {
+        body.set_lin_vel(Vector3::new(0.0, body.lin_vel().y, 0.0));
+        return true;
+    }
+
+    let direction = Vector3::new(point.x - position.x, 0.0, point.z - position.z)
+        .try_normalize(f32::EPSILON)
+        .unwrap_or_default();
+    let mut velocity = direction.scale(speed);
+    velocity.y = body.lin_vel().y;
+    body.set_lin_vel(velocity);
+
+    // Make the bot look where it goes.
+    context.agent.set_position(position + direction);
+    context.is_moving = true;
+
+    false
+}
+
+fn stand_still(context: &mut BehaviorContext) {
+    let body = context.scene.graph[context.character.body].as_rigid_body_mut();
+    body.set_lin_vel(Vector3::new(0.0, body.lin_vel().y, 0.0));
+    context.is_moving = false;
+}
+
+/// Moves the bot from one end of the link to another along an arc (for jumps) or a straight line
+/// (for vents), ignoring any obstacles. Returns `true` when the bot has reached the exit.
+fn fly_to(
+    context: &mut BehaviorContext,
+    traversal: &mut LinkTraversal,
+    entrance: Vector3<f32>,
+    exit: Vector3<f32>,
+    speed: f32,
+    height: f32,
+) -> bool {
+    let length = entrance.metric_distance(&exit).max(f32::EPSILON);
+    traversal.progress = (traversal.progress + speed * context.dt / length).min(1.0);
+
+    let t = traversal.progress;
+    let position = entrance.lerp(&exit, t) + Vector3::new(0.0, 4.0 * height * t * (1.0 - t), 0.0);
+
+    let body = context.scene.graph[context.character.body].as_rigid_body_mut();
+    body.set_lin_vel(Vector3::default());
+    body.local_transform_mut().set_position(position);
+
+    context.agent.set_position(exit);
+    context.is_moving = true;
+
+    traversal.progress >= 1.0
+}
+
+/// Performs a traversal action of current link, returns `true` when the traversal is over
+/// (either done or failed).
+pub fn traverse_link(context: &mut BehaviorContext, traversal: &mut LinkTraversal) -> bool {
+    traversal.time += context.dt;
+    if traversal.time > LinkTraversal::TIMEOUT {
+        return true;
+    }
+
+    let (kind, device, ends) = match context
+        .scene
+        .graph
+        .try_get(traversal.link)
+        .and_then(|n| n.try_get_script::<NavigationLink>())
+    {
+        Some(link) => (
+            *link.kind,
+            *link.device,
+            link.ends(traversal.link, &context.scene.graph, traversal.reversed),
+        ),
+        None => return true,
+    };
+
+    let speed = context.definition.walk_speed * context.movement_speed_factor;
+
+    match kind {
+        NavigationLinkKind::Walk => walk_to(context, ends.exit, speed),
+        NavigationLinkKind::Jump => fly_to(
+            context,
+            traversal,
+            ends.entrance,
+            ends.exit,
+            2.0 * speed,
+            LinkTraversal::JUMP_HEIGHT,
+        ),
+        NavigationLinkKind::Vent => fly_to(
+            context,
+            traversal,
+            ends.entrance,
+            ends.exit,
+            0.5 * speed,
+            0.0,
+        ),
+        NavigationLinkKind::Door => {
+            let state = context
+                .scene
+                .graph
+                .try_get(device)
+                .and_then(|n| n.try_get_script::<Door>())
+                .map(|door| door.state());
+
+            match state {
+                // Door could be destroyed, so just walk through.
+                None | Some(DoorState::Opened) | Some(DoorState::Broken) => {
+                    walk_to(context, ends.exit, speed)
+                }
+                Some(_) => {
+                    if open_or_bash_door(
+                        door_mut(device, &mut context.scene.graph),
+                        &context.character.inventory,
+                        context.definition.door_damage,
+                        context.bot_handle,
+                        &mut traversal.bash_timer,
+                        context.dt,
+                    ) {
+                        context.idle_activity = Some(IdleActivity::BangDoor);
+                    }
+
+                    stand_still(context);
+                    false
+                }
+            }
+        }
+        NavigationLinkKind::Elevator => {
+            let platform = match context.scene.graph.try_get(device) {
+                Some(node) => node.global_position(),
+                None => return true,
+            };
+
+            let elevator = match context
+                .scene
+                .graph
+                .try_get_mut(device)
+                .and_then(|n| n.try_get_script_mut::<Elevator>())
+            {
+                Some(elevator) => elevator,
+                None => return true,
+            };
+
+            let idle = elevator.current_floor == elevator.dest_floor;
+
+            if traversal.boarded {
+                if idle && elevator.current_floor == ends.exit_floor {
+                    walk_to(context, ends.exit, speed)
+                } else {
+                    if idle {
+                        elevator.call_to(ends.exit_floor);
+                    }
+                    stand_still(context);
+                    false
+                }
+            } else if idle && elevator.current_floor == ends.entrance_floor {
+                let position = context.character.position(&context.scene.graph);
+                if horizontal_distance(position, platform) <= LinkTraversal::BOARD_DISTANCE {
+                    traversal.boarded = true;
+                    stand_still(context);
+                } else {
+                    walk_to(context, platform, speed);
+                }
+                false
+            } else {
+                // Do not interrupt the elevator while it is moving.
+                if idle {
+                    elevator.call_to(ends.entrance_floor);
+                }
+                stand_still(context);
+                false
+            }
+        }
+    }
+}
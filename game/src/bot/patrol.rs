@@ -0,0 +1,189 @@
+//! Patrol routes and idle activities allows to stage encounters: bots without a target walk
+//! along their routes and do something at route points instead of just standing still.
+
+use fyrox::{
+    core::{
+        algebra::Vector3,
+        inspect::prelude::*,
+        rand::{self, Rng},
+        reflect::Reflect,
+        uuid::{uuid, Uuid},
+        variable::InheritableVariable,
+        visitor::prelude::*,
+    },
+    impl_component_provider,
+    scene::node::{NodeHandle, TypeUuidProvider},
+    script::ScriptTrait,
+};
+use serde::Deserialize;
+use strum_macros::{AsRefStr, EnumString, EnumVariantNames};
+
+#[derive(
+    Copy,
+    Clone,
+    PartialEq,
+    Eq,
+    Debug,
+    Visit,
+    Reflect,
+    Inspect,
+    AsRefStr,
+    EnumString,
+    EnumVariantNames,
+)]
+#[repr(u32)]
+pub enum PatrolMode {
+    /// Go to the first point after the last one.
+    Loop = 0,
+    /// Go back through all points after the last one.
+    PingPong = 1,
+    /// Pick a random point each time.
+    Random = 2,
+}
+
+impl Default for PatrolMode {
+    fn default() -> Self {
+        Self::Loop
+    }
+}
+
+#[derive(
+    Deserialize,
+    Copy,
+    Clone,
+    PartialEq,
+    Eq,
+    Hash,
+    Debug,
+    Visit,
+    Reflect,
+    Inspect,
+    AsRefStr,
+    EnumString,
+    EnumVariantNames,
+)]
+#[repr(u32)]
+pub enum IdleActivity {
+    /// Just stand still and look around.
+    Stand = 0,
+    EatCorpse = 1,
+    BangDoor = 2,
+}
+
+impl Default for IdleActivity {
+    fn default() -> Self {
+        Self::Stand
+    }
+}
+
+#[derive(Deserialize, Debug)]
+pub struct IdleActivityDefinition {
+    pub activity: IdleActivity,
+    pub animation: String,
+}
+
+#[derive(Visit, Reflect, Inspect, Default, Debug, Clone)]
+pub struct PatrolRoute {
+    #[inspect(description = "Points of the route, a point may have Waypoint script.")]
+    pub points: Vec<NodeHandle>,
+
+    #[visit(optional)]
+    pub mode: InheritableVariable<PatrolMode>,
+}
+
+impl PatrolRoute {
+    /// Returns index of a point that goes after the given one. `forward` flag is used by
+    /// ping-pong routes to remember current direction of movement.
+    pub fn next_point(&self, current: usize, forward: &mut bool) -> usize {
+        let count = self.points.len();
+        if count < 2 {
+            return 0;
+        }
+
+        match *self.mode {
+            PatrolMode::Loop => (current + 1) % count,
+            PatrolMode::PingPong => {
+                if *forward && current + 1 >= count || !*forward && current == 0 {
+                    *forward = !*forward;
+                }
+                if *forward {
+                    current + 1
+                } else {
+                    current - 1
+                }
+            }
+            PatrolMode::Random => {
+                // Make sure that the bot won't stay at the same point.
+                (current + rand::thread_rng().gen_range(1..count)) % count
+            }
+        }
+    }
+}
+
+impl_component_provider!(PatrolRoute);
+
+impl TypeUuidProvider for PatrolRoute {
+    fn type_uuid() -> Uuid {
+        uuid!("6effd66d-574c-4f2b-bd0a-62eaf76f3f92")
+    }
+}
+
+impl ScriptTrait for PatrolRoute {
+    fn id(&self) -> Uuid {
+        Self::type_uuid()
+    }
+}
+
+/// Optional script for patrol route points, defines what a bot should do at the point.
+#[derive(Visit, Reflect, Inspect, Default, Debug, Clone)]
+pub struct Waypoint {
+    pub activity: InheritableVariable<IdleActivity>,
+
+    #[inspect(
+        description = "How long a bot will do the activity before moving to next point. \
+        Zero means forever.",
+        min_value = "0.0"
+    )]
+    pub duration: InheritableVariable<f32>,
+}
+
+impl_component_provider!(Waypoint);
+
+impl TypeUuidProvider for Waypoint {
+    fn type_uuid() -> Uuid {
+        uuid!("c557b445-c0c2-4bc8-a41a-a4c33cd31046")
+    }
+}
+
+impl ScriptTrait for Waypoint {
+    fn id(&self) -> Uuid {
+        Self::type_uuid()
+    }
+}
+
+/// Current progress of a bot on its patrol route.
+#[derive(Visit, Debug, Clone)]
+pub struct PatrolState {
+    pub point_index: u32,
+    pub forward: bool,
+    /// An activity the bot is doing at the current point.
+    pub activity: Option<IdleActivity>,
+    /// Time left to do the current activity, `None` means forever.
+    pub activity_time_left: Option<f32>,
+    /// A direction the bot looks at while doing the activity.
+    pub activity_look: Vector3<f32>,
+    pub idle_sound_timer: f32,
+}
+
+impl Default for PatrolState {
+    fn default() -> Self {
+        Self {
+            point_index: 0,
+            forward: true,
+            activity: None,
+            activity_time_left: None,
+            activity_look: Vector3::z(),
+            idle_sound_timer: 0.0,
+        }
+    }
+}
@@ -1,5 +1,5 @@
 use crate::{
-    bot::{clean_machine, BotDefinition},
+    bot::{clean_machine, make_idle_activity_state, BotDefinition},
     utils::{create_play_animation_state, model_map::ModelMap},
 };
 use fyrox::{
@@ -27,16 +27,24 @@ pub struct UpperBodyMachine {
     pub attack_state: Handle<State>,
     pub dying_animation: Handle<Animation>,
     pub scream_animation: Handle<Animation>,
+    #[visit(optional)]
+    pub grab_animation: Handle<Animation>,
 }
 
 #[derive(Debug)]
 pub struct UpperBodyMachineInput {
     pub attack: bool,
+    pub grab: bool,
+    pub emerge: bool,
     pub walk: bool,
     pub scream: bool,
     pub dead: bool,
     pub aim: bool,
     pub attack_animation_index: u32,
+    /// Scale of animations speed, used to slow down bots in stasis.
+    pub time_scale: f32,
+    /// Index of current idle activity in the bot definition.
+    pub idle_activity: Option<u32>,
 }
 
 pub struct AttackAnimation {
@@ -124,8 +132,19 @@ impl UpperBodyMachine {
     const ATTACK_TO_DYING: &'static str = "AttackToDying";
     const WALK_TO_DYING: &'static str = "WalkToDying";
     const IDLE_TO_DYING: &'static str = "IdleToDying";
+    const IDLE_TO_ACTIVITY: &'static str = "IdleToActivity";
+    const ACTIVITY_TO_IDLE: &'static str = "ActivityToIdle";
+    const ACTIVITY_TO_DYING: &'static str = "ActivityToDying";
+    const IDLE_TO_GRAB: &'static str = "IdleToGrab";
+    const WALK_TO_GRAB: &'static str = "WalkToGrab";
+    const GRAB_TO_IDLE: &'static str = "GrabToIdle";
+    const GRAB_TO_DYING: &'static str = "GrabToDying";
+    const IDLE_TO_EMERGE: &'static str = "IdleToEmerge";
+    const EMERGE_TO_IDLE: &'static str = "EmergeToIdle";
+    const EMERGE_TO_DYING: &'static str = "EmergeToDying";
 
     const ATTACK_INDEX: &'static str = "AttackIndex";
+    const ACTIVITY_INDEX: &'static str = "ActivityIndex";
 
     pub async fn new(
         resource_manager: ResourceManager,
@@ -141,6 +160,9 @@ impl UpperBodyMachine {
             &definition.dying_animation,
         ];
         resources.extend(definition.attack_animations.iter().map(|a| &a.path));
+        resources.extend(definition.idle_activities.iter().map(|a| &a.animation));
+        resources.extend(definition.grab_animation.iter());
+        resources.extend(definition.emerge_animation.iter());
 
         let resources = ModelMap::new(resources, resource_manager.clone()).await;
 
@@ -227,6 +249,48 @@ impl UpperBodyMachine {
             .set_loop(false)
             .set_enabled(false);
 
+        let (activity_state, activity_animations) = make_idle_activity_state(
+            &mut machine,
+            scene,
+            model,
+            Self::ACTIVITY_INDEX,
+            definition
+                .idle_activities
+                .iter()
+                .map(|a| resources[&a.animation].clone())
+                .collect(),
+        );
+
+        let (grab_animation, grab_state) = if let Some(grab_animation) = &definition.grab_animation
+        {
+            create_play_animation_state(
+                resources[grab_animation].clone(),
+                "Grab",
+                &mut machine,
+                scene,
+                model,
+            )
+        } else {
+            (Handle::NONE, Handle::NONE)
+        };
+
+        let (emerge_animation, emerge_state) =
+            if let Some(emerge_animation) = &definition.emerge_animation {
+                create_play_animation_state(
+                    resources[emerge_animation].clone(),
+                    "Emerge",
+                    &mut machine,
+                    scene,
+                    model,
+                )
+            } else {
+                (Handle::NONE, Handle::NONE)
+            };
+
+        if emerge_animation.is_some() {
+            scene.animations.get_mut(emerge_animation).set_loop(false);
+        }
+
         for leg_name in &[&definition.left_leg_name, &definition.right_leg_name] {
             let leg_node = scene.graph.find_by_name(model, leg_name);
 
@@ -236,6 +300,8 @@ impl UpperBodyMachine {
                 aim_animation,
                 scream_animation,
                 dying_animation,
+                grab_animation,
+                emerge_animation,
             ] {
                 // Some animations may be missing for some kinds of bots.
                 if animation.is_some() {
@@ -248,7 +314,7 @@ impl UpperBodyMachine {
             }
 
             // HACK. Move into upper loop.
-            for &attack_animation in attack_animations.iter() {
+            for &attack_animation in attack_animations.iter().chain(activity_animations.iter()) {
                 scene
                     .animations
                     .get_mut(attack_animation)
@@ -371,6 +437,85 @@ impl UpperBodyMachine {
             Self::IDLE_TO_DYING,
         ));
 
+        if activity_state.is_some() {
+            machine.add_transition(Transition::new(
+                "Idle->Activity",
+                idle_state,
+                activity_state,
+                0.3,
+                Self::IDLE_TO_ACTIVITY,
+            ));
+            machine.add_transition(Transition::new(
+                "Activity->Idle",
+                activity_state,
+                idle_state,
+                0.3,
+                Self::ACTIVITY_TO_IDLE,
+            ));
+            machine.add_transition(Transition::new(
+                "Activity->Dying",
+                activity_state,
+                dying_state,
+                0.2,
+                Self::ACTIVITY_TO_DYING,
+            ));
+        }
+
+        if grab_state.is_some() {
+            machine.add_transition(Transition::new(
+                "Idle->Grab",
+                idle_state,
+                grab_state,
+                0.2,
+                Self::IDLE_TO_GRAB,
+            ));
+            machine.add_transition(Transition::new(
+                "Walk->Grab",
+                walk_state,
+                grab_state,
+                0.2,
+                Self::WALK_TO_GRAB,
+            ));
+            machine.add_transition(Transition::new(
+                "Grab->Idle",
+                grab_state,
+                idle_state,
+                0.2,
+                Self::GRAB_TO_IDLE,
+            ));
+            machine.add_transition(Transition::new(
+                "Grab->Dying",
+                grab_state,
+                dying_state,
+                0.2,
+                Self::GRAB_TO_DYING,
+            ));
+        }
+
+        if emerge_state.is_some() {
+            machine.add_transition(Transition::new(
+                "Idle->Emerge",
+                idle_state,
+                emerge_state,
+                0.1,
+                Self::IDLE_TO_EMERGE,
+            ));
+            machine.add_transition(Transition::new(
+                "Emerge->Idle",
+                emerge_state,
+                idle_state,
+                0.3,
+                Self::EMERGE_TO_IDLE,
+            ));
+            machine.add_transition(Transition::new(
+                "Emerge->Dying",
+                emerge_state,
+                dying_state,
+                0.2,
+                Self::EMERGE_TO_DYING,
+            ));
+        }
+
         machine.set_entry_state(idle_state);
 
         Self {
@@ -380,6 +525,18 @@ impl UpperBodyMachine {
             dying_animation,
             scream_animation,
             attack_state,
+            grab_animation,
+        }
+    }
+
+    /// Attack animations are excluded, their speed is controlled by melee attack behavior.
+    fn apply_time_scale(&self, scene: &mut Scene, time_scale: f32) {
+        for node in self.machine.nodes() {
+            if let PoseNode::PlayAnimation(node) = node {
+                if !self.attack_animations.contains(&node.animation) {
+                    scene.animations[node.animation].set_speed(time_scale);
+                }
+            }
         }
     }
 
@@ -388,6 +545,8 @@ impl UpperBodyMachine {
     }
 
     pub fn apply(&mut self, scene: &mut Scene, dt: f32, input: UpperBodyMachineInput) {
+        self.apply_time_scale(scene, input.time_scale);
+
         let attack_animation_ended = scene.animations
             [self.attack_animations[input.attack_animation_index as usize]]
             .has_ended();
@@ -419,6 +578,26 @@ impl UpperBodyMachine {
             .set_parameter(Self::ATTACK_TO_DYING, Parameter::Rule(input.dead))
             .set_parameter(Self::WALK_TO_DYING, Parameter::Rule(input.dead))
             .set_parameter(Self::IDLE_TO_DYING, Parameter::Rule(input.dead))
+            .set_parameter(
+                Self::IDLE_TO_ACTIVITY,
+                Parameter::Rule(input.idle_activity.is_some() && !input.walk),
+            )
+            .set_parameter(
+                Self::ACTIVITY_TO_IDLE,
+                Parameter::Rule(input.idle_activity.is_none()),
+            )
+            .set_parameter(Self::ACTIVITY_TO_DYING, Parameter::Rule(input.dead))
+            .set_parameter(Self::IDLE_TO_GRAB, Parameter::Rule(input.grab))
+            .set_parameter(Self::WALK_TO_GRAB, Parameter::Rule(input.grab))
+            .set_parameter(Self::GRAB_TO_IDLE, Parameter::Rule(!input.grab))
+            .set_parameter(Self::GRAB_TO_DYING, Parameter::Rule(input.dead))
+            .set_parameter(Self::IDLE_TO_EMERGE, Parameter::Rule(input.emerge))
+            .set_parameter(Self::EMERGE_TO_IDLE, Parameter::Rule(!input.emerge))
+            .set_parameter(Self::EMERGE_TO_DYING, Parameter::Rule(input.dead))
+            .set_parameter(
+                Self::ACTIVITY_INDEX,
+                Parameter::Index(input.idle_activity.unwrap_or_default()),
+            )
             .evaluate_pose(&scene.animations, dt)
             .apply(&mut scene.graph);
     }
@@ -1,9 +1,12 @@
 use crate::{
+    armor::{armor_tier_definition, max_armor_tier},
     block_on,
+    damage::DamageSource,
+    faction::Faction,
     inventory::Inventory,
     level::item::{item_mut, ItemKind},
-    sound::{SoundKind, SoundManager},
-    weapon::{definition::WeaponKind, weapon_mut, weapon_ref},
+    sound::{Noise, NoiseKind, SoundKind, SoundManager},
+    weapon::{definition::WeaponKind, projectile::DamageKind, weapon_mut, weapon_ref},
     Item, Weapon,
 };
 use fyrox::{
@@ -35,16 +38,22 @@ pub enum CharacterCommand {
         count: u32,
     },
     Damage {
-        /// Actor who damaged target actor, can be Handle::NONE if damage came from environment
-        /// or not from any actor.
-        who: Handle<Node>,
+        /// Source of the damage, use `DamageSource::responsible_actor` to find out an actor
+        /// who damaged target actor.
+        source: DamageSource,
         /// A body part which was hit.
         hitbox: Option<HitBox>,
         /// Numeric value of damage.
         amount: f32,
+        /// Type of damage, defines how much of it will be absorbed by armor.
+        kind: DamageKind,
         /// Only takes effect iff damage was applied to a head hit box!
         critical_shot_probability: f32,
     },
+    /// Makes the character react to a melee hit, `direction` is the direction of the hit.
+    HitReaction {
+        direction: Vector3<f32>,
+    },
 }
 
 #[derive(Visit, Reflect, Inspect, Debug, Clone)]
@@ -59,6 +68,25 @@ pub struct Character {
     #[visit(optional)]
     pub hit_boxes: Vec<HitBox>,
     pub inventory: Inventory,
+    #[visit(optional)]
+    pub armor: f32,
+    #[visit(optional)]
+    pub armor_tier: u32,
+    /// Remaining time of stasis, character moves much slower while in stasis.
+    #[visit(optional)]
+    #[inspect(skip)]
+    #[reflect(hidden)]
+    pub stasis_timer: f32,
+    /// Source of the most recent damage, the killer if the character is dead.
+    #[visit(optional)]
+    #[inspect(skip)]
+    #[reflect(hidden)]
+    pub last_damage_source: DamageSource,
+    /// Faction of the character, it is set by the script of the character (see `Bot::set_faction`).
+    #[visit(skip)]
+    #[inspect(skip)]
+    #[reflect(hidden)]
+    pub faction: Faction,
     #[visit(skip)]
     #[inspect(skip)]
     #[reflect(hidden)]
@@ -77,12 +105,19 @@ impl Default for Character {
             weapon_pivot: Handle::NONE,
             hit_boxes: Default::default(),
             inventory: Default::default(),
+            armor: 0.0,
+            armor_tier: 0,
+            stasis_timer: 0.0,
+            last_damage_source: Default::default(),
+            faction: Default::default(),
             commands: Default::default(),
         }
     }
 }
 
 impl Character {
+    pub const STASIS_SPEED_FACTOR: f32 = 0.25;
+
     pub fn has_ground_contact(&self, graph: &Graph) -> bool {
         if let Some(collider) = graph
             .try_get(self.capsule_collider)
@@ -117,18 +152,82 @@ impl Character {
         self.health -= amount.abs();
     }
 
+    /// Applies damage taking armor plates of the hit box and suit armor into account.
+    pub fn apply_damage(&mut self, amount: f32, kind: DamageKind, hitbox: Option<HitBox>) {
+        let mut amount = amount.abs();
+
+        if let Some(hitbox) = hitbox {
+            if let Some(hitbox) = self
+                .hit_boxes
+                .iter_mut()
+                .find(|h| h.collider == hitbox.collider)
+            {
+                let absorbed = amount.min(hitbox.armor_plate);
+                hitbox.armor_plate -= absorbed;
+                amount -= absorbed;
+            }
+        }
+
+        let absorbed =
+            (amount * armor_tier_definition(self.armor_tier).absorption(kind)).min(self.armor);
+        self.armor -= absorbed;
+
+        self.damage(amount - absorbed);
+    }
+
+    pub fn max_health(&self) -> f32 {
+        armor_tier_definition(self.armor_tier).max_health
+    }
+
     pub fn heal(&mut self, amount: f32) {
-        self.health += amount.abs();
+        self.health = (self.health + amount.abs()).min(self.max_health());
+    }
 
-        if self.health > 150.0 {
-            self.health = 150.0;
-        }
+    pub fn armor(&self) -> f32 {
+        self.armor
+    }
+
+    pub fn max_armor(&self) -> f32 {
+        armor_tier_definition(self.armor_tier).max_armor
+    }
+
+    pub fn add_armor(&mut self, amount: f32) {
+        self.armor = (self.armor + amount.abs()).min(self.max_armor());
+    }
+
+    pub fn armor_tier(&self) -> u32 {
+        self.armor_tier
+    }
+
+    pub fn upgrade_armor_tier(&mut self) {
+        self.armor_tier = (self.armor_tier + 1).min(max_armor_tier());
     }
 
     pub fn is_dead(&self) -> bool {
         self.health <= 0.0
     }
 
+    pub fn apply_stasis(&mut self, duration: f32) {
+        self.stasis_timer = self.stasis_timer.max(duration);
+    }
+
+    pub fn is_in_stasis(&self) -> bool {
+        self.stasis_timer > 0.0
+    }
+
+    /// Returns a factor for movement speed of the character.
+    pub fn stasis_factor(&self) -> f32 {
+        if self.is_in_stasis() {
+            Self::STASIS_SPEED_FACTOR
+        } else {
+            1.0
+        }
+    }
+
+    pub fn update_stasis(&mut self, dt: f32) {
+        self.stasis_timer = (self.stasis_timer - dt).max(0.0);
+    }
+
     pub fn weapon_pivot(&self) -> Handle<Node> {
         self.weapon_pivot
     }
@@ -152,6 +251,7 @@ impl Character {
         match kind {
             ItemKind::Medkit => self.heal(40.0),
             ItemKind::Medpack => self.heal(20.0),
+            ItemKind::ArmorPlate => self.add_armor(Item::get_definition(kind).armor),
             // Non-consumable items.
             ItemKind::Ak47
             | ItemKind::PlasmaGun
@@ -160,7 +260,16 @@ impl Character {
             | ItemKind::Ammo
             | ItemKind::RailGun
             | ItemKind::Grenade
-            | ItemKind::MasterKey => (),
+            | ItemKind::IncendiaryGrenade
+            | ItemKind::EmpGrenade
+            | ItemKind::StasisGrenade
+            | ItemKind::MasterKey
+            | ItemKind::SecurityKeycard
+            | ItemKind::MedicalKeycard
+            | ItemKind::EngineeringKeycard
+            | ItemKind::UpgradeModule
+            | ItemKind::SuitUpgrade
+            | ItemKind::StasisCell => (),
         }
     }
 
@@ -214,6 +323,9 @@ impl Character {
                     match kind {
                         ItemKind::Medkit => self.inventory.add_item(ItemKind::Medkit, 1),
                         ItemKind::Medpack => self.inventory.add_item(ItemKind::Medpack, 1),
+                        ItemKind::ArmorPlate => self.inventory.add_item(ItemKind::ArmorPlate, 1),
+                        // Suit upgrade is installed right away.
+                        ItemKind::SuitUpgrade => self.upgrade_armor_tier(),
                         ItemKind::Ak47
                         | ItemKind::PlasmaGun
                         | ItemKind::M4
@@ -240,11 +352,23 @@ impl Character {
                         ItemKind::Ammo => {
                             self.inventory.add_item(ItemKind::Ammo, 24);
                         }
-                        ItemKind::Grenade => {
-                            self.inventory.add_item(ItemKind::Grenade, 1);
+                        ItemKind::Grenade
+                        | ItemKind::IncendiaryGrenade
+                        | ItemKind::EmpGrenade
+                        | ItemKind::StasisGrenade => {
+                            self.inventory.add_item(kind, 1);
+                        }
+                        ItemKind::MasterKey
+                        | ItemKind::SecurityKeycard
+                        | ItemKind::MedicalKeycard
+                        | ItemKind::EngineeringKeycard => {
+                            self.inventory.add_item(kind, 1);
                         }
-                        ItemKind::MasterKey => {
-                            self.inventory.add_item(ItemKind::MasterKey, 1);
+                        ItemKind::UpgradeModule => {
+                            self.inventory.add_item(ItemKind::UpgradeModule, 1);
+                        }
+                        ItemKind::StasisCell => {
+                            self.inventory.add_item(ItemKind::StasisCell, 1);
                         }
                     }
                 }
@@ -271,9 +395,20 @@ impl Character {
                         );
                     }
                 }
-                CharacterCommand::Damage { amount, .. } => {
-                    self.damage(amount);
+                CharacterCommand::Damage {
+                    source,
+                    amount,
+                    kind,
+                    hitbox,
+                    ..
+                } => {
+                    if !self.is_dead() {
+                        self.last_damage_source = source;
+                    }
+                    self.apply_damage(amount, kind, hitbox);
                 }
+                // Reaction depends on the kind of character, so it is handled by scripts.
+                CharacterCommand::HitReaction { .. } => (),
             }
 
             Some(command)
@@ -366,12 +501,23 @@ impl Character {
         &mut self.inventory
     }
 
+    /// Plays footstep sound of the surface under the actor and makes a noise with given
+    /// loudness, see [`Noise`] for more info.
     pub fn footstep_ray_check(
         &self,
+        self_handle: Handle<Node>,
         begin: Vector3<f32>,
         scene: &mut Scene,
         manager: &SoundManager,
+        loudness: f32,
     ) {
+        manager.emit_noise(Noise::new(
+            NoiseKind::FootStep,
+            begin,
+            loudness,
+            self_handle,
+        ));
+
         let mut query_buffer = Vec::new();
 
         let ray = Ray::from_two_points(begin, begin + Vector3::new(0.0, -100.0, 0.0));
@@ -412,6 +558,10 @@ pub struct HitBox {
     pub damage_factor: f32,
     pub movement_speed_factor: f32,
     pub is_head: bool,
+    /// Amount of damage an armor plate on this hit box can absorb before it breaks. Zero means
+    /// there is no plate.
+    #[visit(optional)]
+    pub armor_plate: f32,
 }
 
 impl HitBox {
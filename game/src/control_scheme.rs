@@ -44,8 +44,13 @@ pub struct ControlScheme {
     pub next_weapon: ControlButtonDefinition,
     pub prev_weapon: ControlButtonDefinition,
     pub run: ControlButtonDefinition,
+    pub dodge: ControlButtonDefinition,
+    pub struggle: ControlButtonDefinition,
     pub aim: ControlButtonDefinition,
     pub toss_grenade: ControlButtonDefinition,
+    pub cycle_throwable: ControlButtonDefinition,
+    pub use_stasis: ControlButtonDefinition,
+    pub kinesis: ControlButtonDefinition,
     pub journal: ControlButtonDefinition,
     pub flash_light: ControlButtonDefinition,
     pub grab_ak47: ControlButtonDefinition,
@@ -102,6 +107,14 @@ impl Default for ControlScheme {
                 description: "Run".to_string(),
                 button: ControlButton::Key(VirtualKeyCode::LShift),
             },
+            dodge: ControlButtonDefinition {
+                description: "Dodge".to_string(),
+                button: ControlButton::Key(VirtualKeyCode::C),
+            },
+            struggle: ControlButtonDefinition {
+                description: "Struggle".to_string(),
+                button: ControlButton::Key(VirtualKeyCode::X),
+            },
             aim: ControlButtonDefinition {
                 description: "Aim".to_string(),
                 button: ControlButton::Mouse(3),
@@ -110,6 +123,18 @@ impl Default for ControlScheme {
                 description: "Toss Grenade".to_string(),
                 button: ControlButton::Key(VirtualKeyCode::G),
             },
+            cycle_throwable: ControlButtonDefinition {
+                description: "Cycle Throwable".to_string(),
+                button: ControlButton::Key(VirtualKeyCode::T),
+            },
+            use_stasis: ControlButtonDefinition {
+                description: "Use Stasis".to_string(),
+                button: ControlButton::Key(VirtualKeyCode::Q),
+            },
+            kinesis: ControlButtonDefinition {
+                description: "Kinesis".to_string(),
+                button: ControlButton::Key(VirtualKeyCode::V),
+            },
             journal: ControlButtonDefinition {
                 description: "Journal".to_string(),
                 button: ControlButton::Key(VirtualKeyCode::J),
@@ -169,7 +194,7 @@ impl Default for ControlScheme {
 }
 
 impl ControlScheme {
-    pub fn buttons_mut(&mut self) -> [&mut ControlButtonDefinition; 24] {
+    pub fn buttons_mut(&mut self) -> [&mut ControlButtonDefinition; 29] {
         [
             &mut self.move_forward,
             &mut self.move_backward,
@@ -182,9 +207,14 @@ impl ControlScheme {
             &mut self.next_weapon,
             &mut self.prev_weapon,
             &mut self.run,
+            &mut self.dodge,
+            &mut self.struggle,
             &mut self.aim,
             &mut self.inventory,
             &mut self.toss_grenade,
+            &mut self.cycle_throwable,
+            &mut self.use_stasis,
+            &mut self.kinesis,
             &mut self.journal,
             &mut self.flash_light,
             &mut self.grab_pistol,
@@ -198,7 +228,7 @@ impl ControlScheme {
         ]
     }
 
-    pub fn buttons(&self) -> [&ControlButtonDefinition; 24] {
+    pub fn buttons(&self) -> [&ControlButtonDefinition; 29] {
         [
             &self.move_forward,
             &self.move_backward,
@@ -211,9 +241,14 @@ impl ControlScheme {
             &self.next_weapon,
             &self.prev_weapon,
             &self.run,
+            &self.dodge,
+            &self.struggle,
             &self.aim,
             &self.inventory,
             &self.toss_grenade,
+            &self.cycle_throwable,
+            &self.use_stasis,
+            &self.kinesis,
             &self.journal,
             &self.flash_light,
             &self.grab_pistol,
@@ -0,0 +1,175 @@
+//! Damage attribution. Every damage command carries a source, which allows victims to find out
+//! who attacked them (to retarget for example) and to show who killed the player.
+
+use crate::{
+    bot::Bot,
+    character::try_get_character_ref,
+    faction::{try_get_faction, Faction},
+    level::turret::Turret,
+    player::Player,
+    weapon::{projectile::ProjectileKind, Weapon},
+};
+use fyrox::{
+    core::{algebra::Vector3, pool::Handle, visitor::prelude::*},
+    scene::{graph::Graph, node::Node},
+};
+
+#[derive(Copy, Clone, PartialEq, Eq, Debug, Visit)]
+pub enum EnvironmentKind {
+    /// Instant death areas, like bottomless pits.
+    DeathZone,
+}
+
+impl Default for EnvironmentKind {
+    fn default() -> Self {
+        Self::DeathZone
+    }
+}
+
+impl EnvironmentKind {
+    pub fn description(self) -> &'static str {
+        match self {
+            EnvironmentKind::DeathZone => "Deadly Environment",
+        }
+    }
+}
+
+#[derive(Copy, Clone, PartialEq, Debug, Visit)]
+pub enum DamageSource {
+    /// Source is not known.
+    Unknown,
+    /// Direct damage from an actor, melee attacks for example.
+    Actor(Handle<Node>),
+    /// A shot from a weapon of an actor.
+    Weapon {
+        weapon: Handle<Node>,
+        owner: Handle<Node>,
+    },
+    Turret(Handle<Node>),
+    /// A projectile, `shooter` is a weapon, turret or actor that launched the projectile, and
+    /// `owner` is an actor responsible for the shot (if any).
+    Projectile {
+        kind: ProjectileKind,
+        shooter: Handle<Node>,
+        owner: Handle<Node>,
+    },
+    Environment(EnvironmentKind),
+}
+
+impl Default for DamageSource {
+    fn default() -> Self {
+        Self::Unknown
+    }
+}
+
+impl DamageSource {
+    /// Creates damage source from a handle of something that shoots - weapon, turret or actor.
+    pub fn from_shooter(shooter: Handle<Node>, graph: &Graph) -> Self {
+        match graph.try_get(shooter) {
+            Some(node) => {
+                if let Some(weapon) = node.try_get_script::<Weapon>() {
+                    Self::Weapon {
+                        weapon: shooter,
+                        owner: weapon.owner(),
+                    }
+                } else if node.has_script::<Turret>() {
+                    Self::Turret(shooter)
+                } else if try_get_character_ref(shooter, graph).is_some() {
+                    Self::Actor(shooter)
+                } else {
+                    Self::Unknown
+                }
+            }
+            None => Self::Unknown,
+        }
+    }
+
+    pub fn projectile(kind: ProjectileKind, shooter: Handle<Node>, graph: &Graph) -> Self {
+        Self::Projectile {
+            kind,
+            shooter,
+            owner: Self::from_shooter(shooter, graph).responsible_actor(),
+        }
+    }
+
+    /// Returns an actor responsible for the damage, can be `Handle::NONE` if damage came from
+    /// environment or turrets.
+    pub fn responsible_actor(&self) -> Handle<Node> {
+        match *self {
+            DamageSource::Actor(actor) => actor,
+            DamageSource::Weapon { owner, .. } => owner,
+            DamageSource::Projectile { owner, .. } => owner,
+            DamageSource::Unknown | DamageSource::Turret(_) | DamageSource::Environment(_) => {
+                Handle::NONE
+            }
+        }
+    }
+
+    /// Returns faction of the source, it is the faction of the responsible actor or of the turret
+    /// that caused the damage.
+    pub fn faction(&self, graph: &Graph) -> Option<Faction> {
+        let node = match *self {
+            DamageSource::Turret(turret) => turret,
+            DamageSource::Projectile { shooter, owner, .. } if owner.is_none() => shooter,
+            _ => self.responsible_actor(),
+        };
+
+        try_get_faction(node, graph)
+    }
+
+    /// Returns position of the source, if it is known.
+    pub fn position(&self, graph: &Graph) -> Option<Vector3<f32>> {
+        let node = match *self {
+            DamageSource::Actor(actor) => actor,
+            DamageSource::Weapon { weapon, .. } => weapon,
+            DamageSource::Turret(turret) => turret,
+            DamageSource::Projectile { shooter, .. } => shooter,
+            DamageSource::Unknown | DamageSource::Environment(_) => return None,
+        };
+
+        graph.try_get(node).map(|n| n.global_position())
+    }
+
+    /// Human-readable description of the source, used in "killed by" messages.
+    pub fn description(&self, graph: &Graph) -> String {
+        match *self {
+            DamageSource::Unknown => "Unknown".to_owned(),
+            DamageSource::Actor(actor) => actor_name(actor, graph),
+            DamageSource::Weapon { weapon, owner } => {
+                match graph
+                    .try_get(weapon)
+                    .and_then(|n| n.try_get_script::<Weapon>())
+                {
+                    Some(weapon) => {
+                        format!("{} ({})", actor_name(owner, graph), weapon.kind().as_ref())
+                    }
+                    None => actor_name(owner, graph),
+                }
+            }
+            DamageSource::Turret(_) => "Turret".to_owned(),
+            DamageSource::Projectile { kind, owner, .. } => {
+                if owner.is_some() {
+                    format!("{} ({})", actor_name(owner, graph), kind.as_ref())
+                } else {
+                    kind.as_ref().to_owned()
+                }
+            }
+            DamageSource::Environment(kind) => kind.description().to_owned(),
+        }
+    }
+}
+
+fn actor_name(actor: Handle<Node>, graph: &Graph) -> String {
+    match graph.try_get(actor) {
+        Some(node) => {
+            if let Some(bot) = node.try_get_script::<Bot>() {
+                bot.kind.as_ref().to_owned()
+            } else if node.has_script::<Player>() {
+                "Yourself".to_owned()
+            } else {
+                "Unknown".to_owned()
+            }
+        }
+        None => "Unknown".to_owned(),
+    }
+}
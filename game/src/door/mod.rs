@@ -1,4 +1,10 @@
-use crate::{character::character_ref, current_level_mut, game_mut};
+use crate::{
+    character::Character,
+    current_level_mut, game_mut,
+    inventory::Inventory,
+    level::{item::ItemKind, spatial::SpatialCategory},
+    sound::{Noise, NoiseKind},
+};
 use fyrox::{
     core::{
         algebra::Vector3,
@@ -29,6 +35,16 @@ use strum_macros::{AsRefStr, EnumString, EnumVariantNames};
 
 pub mod ui;
 
+/// A script that locks the door, several scripts could lock the same door at once and the door
+/// stays locked until all of them release it.
+#[derive(Copy, Clone, Eq, PartialEq, Debug)]
+pub enum DoorLock {
+    /// Arena of a boss, locked while the boss fights.
+    Arena,
+    /// A trigger, a quarantine lockdown for example.
+    Trigger,
+}
+
 #[derive(
     Copy,
     Clone,
@@ -88,7 +104,14 @@ struct OpenRequest {
     has_key: bool,
 }
 
-#[derive(Visit, Reflect, Inspect, Default, Debug, Clone)]
+#[derive(Debug, Clone, Default)]
+struct BashRequest {
+    damage: f32,
+    /// An actor that bashes the door.
+    source: Handle<Node>,
+}
+
+#[derive(Visit, Reflect, Inspect, Debug, Clone)]
 pub struct Door {
     #[inspect(
         description = "An array of handles to light sources that indicates state of the door."
@@ -111,15 +134,35 @@ pub struct Door {
     #[visit(optional)]
     open_offset_amount: InheritableVariable<f32>,
 
+    #[inspect(
+        description = "Current state of the door. Locked door can be opened only with a key."
+    )]
+    #[visit(optional)]
+    state: InheritableVariable<DoorState>,
+
+    #[inspect(
+        description = "A key that opens the door when it is locked. Master key opens any door."
+    )]
+    #[visit(optional)]
+    required_key: InheritableVariable<ItemKind>,
+
+    #[inspect(
+        description = "Amount of damage the door can take before it breaks. Zero - unbreakable.",
+        min_value = "0.0"
+    )]
+    #[visit(optional)]
+    strength: InheritableVariable<f32>,
+
     #[inspect(skip)]
     #[reflect(hidden)]
-    #[visit(skip)]
+    #[visit(optional)]
     offset: f32,
 
+    /// Total damage dealt to the door by bashing.
     #[inspect(skip)]
     #[reflect(hidden)]
-    #[visit(skip)]
-    state: DoorState,
+    #[visit(optional)]
+    damage_taken: f32,
 
     #[inspect(skip)]
     #[reflect(hidden)]
@@ -130,10 +173,71 @@ pub struct Door {
     #[visit(skip)]
     open_request: Option<OpenRequest>,
 
+    #[inspect(skip)]
+    #[reflect(hidden)]
+    #[visit(skip)]
+    bash_request: Option<BashRequest>,
+
     #[inspect(skip)]
     #[reflect(hidden)]
     #[visit(skip)]
     self_handle: Handle<Node>,
+
+    /// Remaining time while door panel is disabled (by EMP for example).
+    #[inspect(skip)]
+    #[reflect(hidden)]
+    #[visit(optional)]
+    panel_disable_timer: f32,
+
+    /// Remaining time while door is slowed down by stasis.
+    #[inspect(skip)]
+    #[reflect(hidden)]
+    #[visit(optional)]
+    stasis_timer: f32,
+
+    /// Door is locked by a boss arena, keys can't open it.
+    #[inspect(skip)]
+    #[reflect(hidden)]
+    #[visit(optional)]
+    arena_lock: bool,
+
+    /// Door is locked by a trigger, keys can't open it.
+    #[inspect(skip)]
+    #[reflect(hidden)]
+    #[visit(optional)]
+    trigger_lock: bool,
+
+    /// Door was closed (not locked) when a script lock was applied, so it must be unlocked
+    /// back when all script locks are lifted.
+    #[inspect(skip)]
+    #[reflect(hidden)]
+    #[visit(optional)]
+    unlock_on_script_release: bool,
+}
+
+impl Default for Door {
+    fn default() -> Self {
+        Self {
+            lights: Default::default(),
+            screens: Default::default(),
+            open_direction: Default::default(),
+            open_offset_amount: Default::default(),
+            state: Default::default(),
+            required_key: ItemKind::MasterKey.into(),
+            strength: 200.0.into(),
+            offset: 0.0,
+            damage_taken: 0.0,
+            initial_position: Default::default(),
+            open_request: None,
+            bash_request: None,
+            self_handle: Default::default(),
+            panel_disable_timer: 0.0,
+            stasis_timer: 0.0,
+            arena_lock: false,
+            trigger_lock: false,
+            unlock_on_script_release: false,
+        }
+    }
 }
 
 impl_component_provider!(Door);
@@ -146,7 +250,11 @@ impl TypeUuidProvider for Door {
 
 impl ScriptTrait for Door {
     fn on_init(&mut self, ctx: &mut ScriptContext) {
-        self.initial_position = ctx.scene.graph[ctx.handle].global_position();
+        // Saved door could be opened (or broken), so its closed position is restored from the
+        // saved offset.
+        let node = &ctx.scene.graph[ctx.handle];
+        self.initial_position =
+            node.global_position() - self.move_direction(node).scale(self.offset);
 
         current_level_mut(ctx.plugins)
             .expect("Level must exist!")
@@ -186,27 +294,25 @@ impl ScriptTrait for Door {
         let game = game_mut(ctx.plugins);
         let level = game.level.as_ref().unwrap();
 
-        let speed = 0.55;
-
-        let node = &ctx.scene.graph[ctx.handle];
-        let move_direction = match *self.open_direction {
-            DoorDirection::Side => node.look_vector(),
-            DoorDirection::Up => node.up_vector(),
+        let speed = if self.stasis_timer > 0.0 {
+            0.55 * Character::STASIS_SPEED_FACTOR
+        } else {
+            0.55
         };
+        self.stasis_timer = (self.stasis_timer - ctx.dt).max(0.0);
 
-        let mut closest_actor = None;
+        let node = &ctx.scene.graph[ctx.handle];
+        let move_direction = self.move_direction(node);
 
-        let someone_nearby = level.actors.iter().any(|a| {
-            let actor_position = character_ref(*a, &ctx.scene.graph).position(&ctx.scene.graph);
-            let close_enough = actor_position.metric_distance(&self.initial_position) < 1.25;
-            if close_enough {
-                closest_actor = Some(a);
-            }
-            close_enough
-        });
+        let closest_actor = level.spatial_index.closest(
+            self.initial_position,
+            Self::ACTOR_DISTANCE,
+            SpatialCategory::Actor,
+        );
+        let someone_nearby = closest_actor.is_some();
 
-        if !someone_nearby && self.state == DoorState::Opened {
-            self.state = DoorState::Closing;
+        if !someone_nearby && *self.state == DoorState::Opened {
+            self.state.set(DoorState::Closing);
             let position = node.global_position();
             level.sound_manager.play_sound(
                 &mut ctx.scene.graph,
@@ -218,31 +324,91 @@ impl ScriptTrait for Door {
             );
         }
 
-        if let Some(ui) = game.door_ui_container.get_ui_mut(ctx.handle) {
-            let text = match self.state {
-                DoorState::Opened => "Opened",
-                DoorState::Opening => "Opening...",
+        if self.is_script_locked() {
+            match *self.state {
+                DoorState::Opened | DoorState::Opening => {
+                    self.state.set(DoorState::Closing);
+                }
                 DoorState::Closed => {
-                    if someone_nearby {
-                        "Open?"
-                    } else {
-                        "Closed"
+                    self.state.set(DoorState::Locked);
+                    self.unlock_on_script_release = true;
+                }
+                _ => (),
+            }
+        }
+
+        if let Some(bash_request) = self.bash_request.take() {
+            if self.can_be_bashed() {
+                let position = self.actual_position(&ctx.scene.graph);
+
+                self.damage_taken += bash_request.damage;
+
+                let loudness = if self.damage_taken >= *self.strength {
+                    self.state.set(DoorState::Broken);
+
+                    level.sound_manager.play_sound(
+                        &mut ctx.scene.graph,
+                        "data/sounds/explosion.wav",
+                        position,
+                        0.4,
+                        1.0,
+                        1.0,
+                    );
+
+                    Noise::DOOR_BASH_LOUDNESS * 2.0
+                } else {
+                    Noise::DOOR_BASH_LOUDNESS
+                };
+
+                level.sound_manager.play_sound(
+                    &mut ctx.scene.graph,
+                    "data/sounds/bullet_impact_metal.ogg",
+                    position,
+                    1.0,
+                    1.0,
+                    1.0,
+                );
+
+                level.sound_manager.emit_noise(Noise::new(
+                    NoiseKind::Door,
+                    position,
+                    loudness,
+                    bash_request.source,
+                ));
+            }
+        }
+
+        self.panel_disable_timer = (self.panel_disable_timer - ctx.dt).max(0.0);
+
+        if let Some(ui) = game.door_ui_container.get_ui_mut(ctx.handle) {
+            let text = if self.is_panel_disabled() {
+                "No Signal"
+            } else {
+                match *self.state {
+                    DoorState::Opened => "Opened",
+                    DoorState::Opening => "Opening...",
+                    DoorState::Closed => {
+                        if someone_nearby {
+                            "Open?"
+                        } else {
+                            "Closed"
+                        }
                     }
+                    DoorState::Closing => "Closing..",
+                    DoorState::Locked => "Locked",
+                    DoorState::Broken => "Broken",
                 }
-                DoorState::Closing => "Closing..",
-                DoorState::Locked => "Locked",
-                DoorState::Broken => "Broken",
             };
 
             ui.set_text(text.to_owned());
         }
 
-        match self.state {
+        match *self.state {
             DoorState::Opening => {
                 if self.offset < *self.open_offset_amount {
                     self.offset += speed * ctx.dt;
                     if self.offset >= *self.open_offset_amount {
-                        self.state = DoorState::Opened;
+                        self.state.set(DoorState::Opened);
                         self.offset = *self.open_offset_amount;
                     }
                 }
@@ -253,7 +419,7 @@ impl ScriptTrait for Door {
                 if self.offset > 0.0 {
                     self.offset -= speed * ctx.dt;
                     if self.offset <= 0.0 {
-                        self.state = DoorState::Closed;
+                        self.state.set(DoorState::Closed);
                         self.offset = 0.0;
                     }
                 }
@@ -268,26 +434,39 @@ impl ScriptTrait for Door {
                 self.set_lights_enabled(&mut ctx.scene.graph, true);
                 self.set_lights_color(&mut ctx.scene.graph, Color::opaque(200, 0, 0));
             }
-            DoorState::Broken | DoorState::Opened => {
+            DoorState::Broken => {
+                // Broken door is forced open and stays like that forever.
+                self.offset =
+                    (self.offset + Self::FORCED_OPEN_SPEED * ctx.dt).min(*self.open_offset_amount);
+
+                self.set_lights_enabled(&mut ctx.scene.graph, false);
+            }
+            DoorState::Opened => {
                 self.set_lights_enabled(&mut ctx.scene.graph, false);
             }
         };
 
         if let Some(body) = ctx.scene.graph[ctx.handle].cast_mut::<RigidBody>() {
-            body.local_transform_mut().set_position(
-                self.initial_position
-                    + move_direction
-                        .try_normalize(f32::EPSILON)
-                        .unwrap_or_default()
-                        .scale(self.offset),
-            );
+            body.local_transform_mut()
+                .set_position(self.initial_position + move_direction.scale(self.offset));
         }
 
         if let Some(open_request) = self.open_request.take() {
             let position = self.actual_position(&ctx.scene.graph);
+            let mut opened = false;
 
-            if self.state == DoorState::Closed {
-                self.state = DoorState::Opening;
+            if self.is_panel_disabled() {
+                level.sound_manager.play_sound(
+                    &mut ctx.scene.graph,
+                    "data/sounds/door_deny.ogg",
+                    position,
+                    1.0,
+                    1.0,
+                    1.0,
+                );
+            } else if *self.state == DoorState::Closed {
+                self.state.set(DoorState::Opening);
+                opened = true;
 
                 level.sound_manager.play_sound(
                     &mut ctx.scene.graph,
@@ -297,9 +476,10 @@ impl ScriptTrait for Door {
                     1.0,
                     1.0,
                 );
-            } else if self.state == DoorState::Locked {
-                if open_request.has_key {
-                    self.state = DoorState::Opening;
+            } else if *self.state == DoorState::Locked {
+                if open_request.has_key && !self.is_script_locked() {
+                    self.state.set(DoorState::Opening);
+                    opened = true;
 
                     level.sound_manager.play_sound(
                         &mut ctx.scene.graph,
@@ -329,6 +509,16 @@ impl ScriptTrait for Door {
                     );
                 }
             }
+
+            if opened {
+                // Door is opened by the closest actor.
+                level.sound_manager.emit_noise(Noise::new(
+                    NoiseKind::Door,
+                    position,
+                    Noise::DOOR_LOUDNESS,
+                    closest_actor.unwrap_or_default(),
+                ));
+            }
         }
     }
 
@@ -338,6 +528,11 @@ impl ScriptTrait for Door {
 }
 
 impl Door {
+    /// Max distance from the door to an actor that keeps the door opened.
+    const ACTOR_DISTANCE: f32 = 1.25;
+    /// Speed (m/s) of a broken door that slides open.
+    const FORCED_OPEN_SPEED: f32 = 2.0;
+
     fn set_lights_color(&self, graph: &mut Graph, color: Color) {
         for &light in self.lights.iter() {
             if let Some(light_ref) = graph[*light].query_component_mut::<BaseLight>() {
@@ -352,6 +547,16 @@ impl Door {
         }
     }
 
+    /// Normalized direction in which the door slides open.
+    fn move_direction(&self, node: &Node) -> Vector3<f32> {
+        match *self.open_direction {
+            DoorDirection::Side => node.look_vector(),
+            DoorDirection::Up => node.up_vector(),
+        }
+        .try_normalize(f32::EPSILON)
+        .unwrap_or_default()
+    }
+
     pub fn initial_position(&self) -> Vector3<f32> {
         self.initial_position
     }
@@ -393,8 +598,104 @@ impl Door {
         }
     }
 
-    pub fn try_open(&mut self, has_key: bool) {
-        self.open_request = Some(OpenRequest { has_key });
+    pub fn state(&self) -> DoorState {
+        *self.state
+    }
+
+    fn is_script_locked(&self) -> bool {
+        self.arena_lock || self.trigger_lock
+    }
+
+    fn lock_flag_mut(&mut self, lock: DoorLock) -> &mut bool {
+        match lock {
+            DoorLock::Arena => &mut self.arena_lock,
+            DoorLock::Trigger => &mut self.trigger_lock,
+        }
+    }
+
+    /// Locks the door so it can't be opened even with a key, opened door is closed first.
+    /// Broken door can't be locked. The door is unlocked back only when no other script holds
+    /// its lock.
+    pub fn set_locked(&mut self, lock: DoorLock, locked: bool) {
+        *self.lock_flag_mut(lock) = locked;
+        if !self.is_script_locked()
+            && std::mem::take(&mut self.unlock_on_script_release)
+            && *self.state == DoorState::Locked
+        {
+            self.state.set(DoorState::Closed);
+        }
+    }
+
+    /// Lifts the lock of a trigger and unlocks the door even if it was locked by a designer, so
+    /// it can be opened without a key. A boss arena keeps the door locked until the boss dies.
+    /// Broken door stays broken.
+    pub fn unlock(&mut self) {
+        self.trigger_lock = false;
+        if *self.state != DoorState::Locked {
+            return;
+        }
+        if self.arena_lock {
+            self.unlock_on_script_release = true;
+        } else {
+            self.unlock_on_script_release = false;
+            self.state.set(DoorState::Closed);
+        }
+    }
+
+    pub fn try_open(&mut self, inventory: &Inventory) {
+        self.open_request = Some(OpenRequest {
+            has_key: inventory.has_key(*self.required_key),
+        });
+    }
+
+    /// Checks whether an actor with the given inventory is able to open the door (or walk through
+    /// it if it is already opened).
+    pub fn can_be_opened(&self, inventory: &Inventory) -> bool {
+        match *self.state {
+            DoorState::Broken => true,
+            DoorState::Locked => {
+                !self.is_script_locked()
+                    && !self.is_panel_disabled()
+                    && inventory.has_key(*self.required_key)
+            }
+            DoorState::Opened | DoorState::Opening | DoorState::Closed | DoorState::Closing => {
+                !self.is_panel_disabled()
+            }
+        }
+    }
+
+    /// Checks whether the door blocks the way and can be broken.
+    pub fn can_be_bashed(&self) -> bool {
+        *self.strength > 0.0
+            && matches!(
+                *self.state,
+                DoorState::Closed | DoorState::Closing | DoorState::Locked
+            )
+    }
+
+    /// Hits the door, it breaks and stays opened forever when the damage exceeds its strength.
+    /// Multiple hits in the same frame are summed up.
+    pub fn bash(&mut self, damage: f32, source: Handle<Node>) {
+        let request = self.bash_request.get_or_insert(BashRequest {
+            damage: 0.0,
+            source,
+        });
+        request.damage += damage;
+    }
+
+    pub fn disable_panel(&mut self, duration: f32) {
+        self.panel_disable_timer = self.panel_disable_timer.max(duration);
+    }
+
+    pub fn is_panel_disabled(&self) -> bool {
+        self.panel_disable_timer > 0.0
+    }
+
+    /// Slows down closing door, so it could be passed through.
+    pub fn apply_stasis(&mut self, duration: f32) {
+        if *self.state == DoorState::Closing {
+            self.stasis_timer = self.stasis_timer.max(duration);
+        }
     }
 }
 
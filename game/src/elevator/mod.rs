@@ -1,4 +1,4 @@
-use crate::current_level_mut;
+use crate::{character::Character, current_level_mut};
 use fyrox::{
     core::{
         inspect::prelude::*,
@@ -24,6 +24,12 @@ pub struct Elevator {
     k: f32,
     pub point_handles: Vec<NodeHandle>,
     pub call_buttons: Vec<NodeHandle>,
+
+    /// Remaining time while elevator is slowed down by stasis.
+    #[inspect(skip)]
+    #[reflect(hidden)]
+    #[visit(optional)]
+    stasis_timer: f32,
 }
 
 impl Elevator {
@@ -32,6 +38,10 @@ impl Elevator {
             self.dest_floor = floor;
         }
     }
+
+    pub fn apply_stasis(&mut self, duration: f32) {
+        self.stasis_timer = self.stasis_timer.max(duration);
+    }
 }
 
 impl_component_provider!(Elevator);
@@ -59,8 +69,15 @@ impl ScriptTrait for Elevator {
     }
 
     fn on_update(&mut self, context: &mut ScriptContext) {
+        let speed_factor = if self.stasis_timer > 0.0 {
+            Character::STASIS_SPEED_FACTOR
+        } else {
+            1.0
+        };
+        self.stasis_timer = (self.stasis_timer - context.dt).max(0.0);
+
         if self.current_floor != self.dest_floor {
-            self.k += 0.5 * context.dt;
+            self.k += 0.5 * speed_factor * context.dt;
 
             if self.k >= 1.0 {
                 self.current_floor = self.dest_floor;
@@ -0,0 +1,142 @@
+//! Factions of characters and turrets. Relations between factions decide who attacks whom, who
+//! helps whom and who is spared by splash damage. Relations are defined in
+//! `data/configs/factions.ron` and can be changed at runtime per level.
+
+use crate::{character::try_get_character_ref, level::turret::Turret};
+use fyrox::{
+    core::{inspect::prelude::*, pool::Handle, reflect::Reflect, visitor::prelude::*},
+    lazy_static::lazy_static,
+    scene::{graph::Graph, node::Node},
+};
+use serde::Deserialize;
+use std::{collections::HashMap, fs::File};
+use strum_macros::{AsRefStr, EnumString, EnumVariantNames};
+
+#[derive(
+    Deserialize,
+    Copy,
+    Clone,
+    PartialEq,
+    Eq,
+    Hash,
+    Debug,
+    Visit,
+    Reflect,
+    Inspect,
+    AsRefStr,
+    EnumString,
+    EnumVariantNames,
+)]
+#[repr(u32)]
+pub enum Faction {
+    Player = 0,
+    Mutants = 1,
+    Parasites = 2,
+    Zombies = 3,
+    /// Security systems of the station, attack the player only.
+    Security = 4,
+    /// Turrets and others that fight on the side of the player.
+    Allies = 5,
+    /// Broken or hacked machines that attack everyone.
+    Rogue = 6,
+}
+
+impl Default for Faction {
+    fn default() -> Self {
+        Self::Player
+    }
+}
+
+#[derive(Deserialize, Copy, Clone, PartialEq, Eq, Debug, Visit)]
+#[repr(u32)]
+pub enum FactionRelation {
+    Hostile = 0,
+    Neutral = 1,
+    Friendly = 2,
+}
+
+impl Default for FactionRelation {
+    fn default() -> Self {
+        Self::Neutral
+    }
+}
+
+#[derive(Deserialize, Debug)]
+pub struct FactionDefinition {
+    /// Relation to factions that are not listed in `relations`.
+    pub default: FactionRelation,
+    #[serde(default)]
+    pub relations: HashMap<Faction, FactionRelation>,
+}
+
+#[derive(Deserialize, Default)]
+pub struct FactionDefinitionContainer {
+    factions: HashMap<Faction, FactionDefinition>,
+}
+
+impl FactionDefinitionContainer {
+    pub fn new() -> Self {
+        let file = File::open("data/configs/factions.ron").unwrap();
+        ron::de::from_reader(file).unwrap()
+    }
+
+    fn relation(&self, from: Faction, to: Faction) -> FactionRelation {
+        self.factions.get(&from).map_or_else(Default::default, |f| {
+            f.relations.get(&to).cloned().unwrap_or(f.default)
+        })
+    }
+}
+
+lazy_static! {
+    static ref DEFINITIONS: FactionDefinitionContainer = FactionDefinitionContainer::new();
+}
+
+#[derive(Default, Debug, Visit, Clone)]
+struct RelationOverride {
+    from: Faction,
+    to: Faction,
+    relation: FactionRelation,
+}
+
+/// Relations between factions on a level. Relations come from the definitions, but scripts can
+/// change them while the level is running, such changes are saved with the level.
+#[derive(Default, Debug, Visit)]
+pub struct FactionRelations {
+    overrides: Vec<RelationOverride>,
+}
+
+impl FactionRelations {
+    /// Returns how members of the `from` faction treat members of the `to` faction. Relations
+    /// are not symmetric.
+    pub fn relation(&self, from: Faction, to: Faction) -> FactionRelation {
+        self.overrides
+            .iter()
+            .find(|o| o.from == from && o.to == to)
+            .map_or_else(|| DEFINITIONS.relation(from, to), |o| o.relation)
+    }
+
+    pub fn set_relation(&mut self, from: Faction, to: Faction, relation: FactionRelation) {
+        self.overrides.retain(|o| o.from != from || o.to != to);
+        self.overrides.push(RelationOverride { from, to, relation });
+    }
+
+    pub fn is_hostile(&self, from: Faction, to: Faction) -> bool {
+        self.relation(from, to) == FactionRelation::Hostile
+    }
+
+    pub fn is_friendly(&self, from: Faction, to: Faction) -> bool {
+        self.relation(from, to) == FactionRelation::Friendly
+    }
+}
+
+/// Returns faction of a character or a turret.
+pub fn try_get_faction(handle: Handle<Node>, graph: &Graph) -> Option<Faction> {
+    if let Some(character) = try_get_character_ref(handle, graph) {
+        Some(character.faction)
+    } else {
+        graph
+            .try_get(handle)
+            .and_then(|n| n.try_get_script::<Turret>())
+            .map(|turret| turret.faction())
+    }
+}
@@ -0,0 +1,100 @@
+//! Health bar of a boss that fights with the player, shown at the top of the screen.
+
+use fyrox::{
+    core::{color::Color, pool::Handle},
+    gui::{
+        brush::Brush,
+        grid::{Column, GridBuilder, Row},
+        message::MessageDirection,
+        progress_bar::{ProgressBarBuilder, ProgressBarMessage},
+        text::{TextBuilder, TextMessage},
+        ttf::SharedFont,
+        widget::{WidgetBuilder, WidgetMessage},
+        HorizontalAlignment, Thickness, UiNode, UserInterface, VerticalAlignment,
+    },
+};
+
+pub struct BossHealthBar {
+    pub root: Handle<UiNode>,
+    name: Handle<UiNode>,
+    progress_bar: Handle<UiNode>,
+    /// Name of the boss that is shown at the moment.
+    boss: Option<String>,
+}
+
+impl BossHealthBar {
+    pub fn new(ui: &mut UserInterface, font: SharedFont) -> Self {
+        let ctx = &mut ui.build_ctx();
+
+        let name;
+        let progress_bar;
+        let root = GridBuilder::new(
+            WidgetBuilder::new()
+                .with_visibility(false)
+                .with_width(500.0)
+                .with_margin(Thickness::top(20.0))
+                .with_horizontal_alignment(HorizontalAlignment::Center)
+                .with_vertical_alignment(VerticalAlignment::Top)
+                .with_child({
+                    name = TextBuilder::new(
+                        WidgetBuilder::new()
+                            .on_row(0)
+                            .with_foreground(Brush::Solid(Color::opaque(200, 0, 0))),
+                    )
+                    .with_horizontal_text_alignment(HorizontalAlignment::Center)
+                    .with_vertical_text_alignment(VerticalAlignment::Center)
+                    .with_font(font)
+                    .build(ctx);
+                    name
+                })
+                .with_child({
+                    progress_bar =
+                        ProgressBarBuilder::new(WidgetBuilder::new().on_row(1)).build(ctx);
+                    progress_bar
+                }),
+        )
+        .add_row(Row::strict(36.0))
+        .add_row(Row::strict(16.0))
+        .add_column(Column::stretch())
+        .build(ctx);
+
+        Self {
+            root,
+            name,
+            progress_bar,
+            boss: None,
+        }
+    }
+
+    /// Shows name and health of the boss (as a fraction of its max health) or hides the bar if
+    /// there's no boss.
+    pub fn update(&mut self, ui: &UserInterface, boss: Option<(&str, f32)>) {
+        let name = boss.map(|(name, _)| name);
+
+        if self.boss.as_deref() != name {
+            ui.send_message(WidgetMessage::visibility(
+                self.root,
+                MessageDirection::ToWidget,
+                name.is_some(),
+            ));
+
+            if let Some(name) = name {
+                ui.send_message(TextMessage::text(
+                    self.name,
+                    MessageDirection::ToWidget,
+                    name.to_owned(),
+                ));
+            }
+
+            self.boss = name.map(|name| name.to_owned());
+        }
+
+        if let Some((_, health)) = boss {
+            ui.send_message(ProgressBarMessage::progress(
+                self.progress_bar,
+                MessageDirection::ToWidget,
+                health,
+            ));
+        }
+    }
+}
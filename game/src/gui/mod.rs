@@ -15,7 +15,7 @@ use fyrox::{
         message::{MessageDirection, UiMessage},
         scroll_bar::ScrollBarBuilder,
         stack_panel::StackPanelBuilder,
-        text::TextBuilder,
+        text::{TextBuilder, TextMessage},
         ttf::SharedFont,
         widget::{WidgetBuilder, WidgetMessage},
         BuildContext, HorizontalAlignment, Orientation, Thickness, UiNode, UserInterface,
@@ -23,6 +23,7 @@ use fyrox::{
     },
 };
 
+pub mod boss_health_bar;
 pub mod inventory;
 pub mod item_display;
 pub mod journal;
@@ -83,6 +84,7 @@ pub fn create_check_box(
 
 pub struct DeathScreen {
     pub root: Handle<UiNode>,
+    killed_by: Handle<UiNode>,
     load_game: Handle<UiNode>,
     exit_to_menu: Handle<UiNode>,
     exit_game: Handle<UiNode>,
@@ -91,6 +93,7 @@ pub struct DeathScreen {
 
 impl DeathScreen {
     pub fn new(ui: &mut UserInterface, font: SharedFont, sender: MessageSender) -> Self {
+        let killed_by;
         let load_game;
         let exit_to_menu;
         let exit_game;
@@ -122,6 +125,21 @@ impl DeathScreen {
                                         .with_vertical_alignment(VerticalAlignment::Top)
                                         .on_row(1)
                                         .on_column(1)
+                                        .with_child({
+                                            killed_by = TextBuilder::new(
+                                                WidgetBuilder::new()
+                                                    .with_margin(Thickness::uniform(2.0))
+                                                    .with_foreground(Brush::Solid(Color::opaque(
+                                                        200, 0, 0,
+                                                    ))),
+                                            )
+                                            .with_horizontal_text_alignment(
+                                                HorizontalAlignment::Center,
+                                            )
+                                            .with_font(font.clone())
+                                            .build(&mut ui.build_ctx());
+                                            killed_by
+                                        })
                                         .with_child({
                                             load_game = ButtonBuilder::new(
                                                 WidgetBuilder::new()
@@ -165,6 +183,7 @@ impl DeathScreen {
 
         Self {
             root,
+            killed_by,
             load_game,
             exit_to_menu,
             exit_game,
@@ -195,6 +214,14 @@ impl DeathScreen {
     pub fn is_visible(&self, ui: &UserInterface) -> bool {
         ui.node(self.root).visibility()
     }
+
+    pub fn set_killed_by(&self, ui: &UserInterface, killed_by: &str) {
+        ui.send_message(TextMessage::text(
+            self.killed_by,
+            MessageDirection::ToWidget,
+            format!("Killed by: {}", killed_by),
+        ));
+    }
 }
 
 pub struct FinalScreen {
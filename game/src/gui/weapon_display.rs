@@ -23,6 +23,8 @@ pub struct WeaponDisplay {
     pub render_target: Texture,
     ammo: Handle<UiNode>,
     grenades: Handle<UiNode>,
+    stasis_energy: Handle<UiNode>,
+    stamina: Handle<UiNode>,
 }
 
 impl WeaponDisplay {
@@ -36,6 +38,8 @@ impl WeaponDisplay {
 
         let ammo;
         let grenades;
+        let stasis_energy;
+        let stamina;
         GridBuilder::new(
             WidgetBuilder::new()
                 .with_width(Self::WIDTH)
@@ -86,15 +90,65 @@ impl WeaponDisplay {
                             .on_row(1)
                             .on_column(1),
                     )
-                    .with_font(font)
+                    .with_font(font.clone())
                     .build(&mut ui.build_ctx());
                     grenades
+                })
+                .with_child(
+                    TextBuilder::new(
+                        WidgetBuilder::new()
+                            .with_vertical_alignment(VerticalAlignment::Center)
+                            .with_foreground(Brush::Solid(Color::opaque(120, 255, 230)))
+                            .on_row(2)
+                            .on_column(0),
+                    )
+                    .with_font(font.clone())
+                    .with_text("SM")
+                    .build(&mut ui.build_ctx()),
+                )
+                .with_child({
+                    stasis_energy = TextBuilder::new(
+                        WidgetBuilder::new()
+                            .with_vertical_alignment(VerticalAlignment::Center)
+                            .with_foreground(Brush::Solid(Color::opaque(120, 255, 230)))
+                            .on_row(2)
+                            .on_column(1),
+                    )
+                    .with_font(font.clone())
+                    .build(&mut ui.build_ctx());
+                    stasis_energy
+                })
+                .with_child(
+                    TextBuilder::new(
+                        WidgetBuilder::new()
+                            .with_vertical_alignment(VerticalAlignment::Center)
+                            .with_foreground(Brush::Solid(Color::opaque(255, 200, 60)))
+                            .on_row(3)
+                            .on_column(0),
+                    )
+                    .with_font(font.clone())
+                    .with_text("ST")
+                    .build(&mut ui.build_ctx()),
+                )
+                .with_child({
+                    stamina = TextBuilder::new(
+                        WidgetBuilder::new()
+                            .with_vertical_alignment(VerticalAlignment::Center)
+                            .with_foreground(Brush::Solid(Color::opaque(255, 200, 60)))
+                            .on_row(3)
+                            .on_column(1),
+                    )
+                    .with_font(font)
+                    .build(&mut ui.build_ctx());
+                    stamina
                 }),
         )
         .add_column(Column::auto())
         .add_column(Column::stretch())
         .add_row(Row::auto())
         .add_row(Row::auto())
+        .add_row(Row::auto())
+        .add_row(Row::auto())
         .add_row(Row::stretch())
         .build(&mut ui.build_ctx());
 
@@ -103,16 +157,15 @@ impl WeaponDisplay {
             render_target,
             ammo,
             grenades,
+            stasis_energy,
+            stamina,
         }
     }
 
     pub fn sync_to_model(&self, player: &Player, graph: &Graph) {
         let ammo = if player.current_weapon().is_some() {
             let total_ammo = player.inventory().item_count(ItemKind::Ammo);
-            total_ammo
-                / weapon_ref(player.current_weapon(), graph)
-                    .definition
-                    .ammo_consumption_per_shot
+            weapon_ref(player.current_weapon(), graph).shots_left(total_ammo)
         } else {
             0
         };
@@ -122,12 +175,30 @@ impl WeaponDisplay {
             format!("{}", ammo),
         ));
 
-        let grenades = player.inventory().item_count(ItemKind::Grenade);
+        let grenades = player.inventory().item_count(player.selected_throwable());
         self.ui.send_message(TextMessage::text(
             self.grenades,
             MessageDirection::ToWidget,
             format!("{}", grenades),
         ));
+
+        self.ui.send_message(TextMessage::text(
+            self.stasis_energy,
+            MessageDirection::ToWidget,
+            format!(
+                "{}%",
+                (100.0 * player.stasis_energy() / Player::STASIS_MAX_ENERGY) as u32
+            ),
+        ));
+
+        self.ui.send_message(TextMessage::text(
+            self.stamina,
+            MessageDirection::ToWidget,
+            format!(
+                "{}%",
+                (100.0 * player.stamina() / Player::MAX_STAMINA) as u32
+            ),
+        ));
     }
 
     pub fn update(&mut self, delta: f32) {
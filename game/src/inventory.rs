@@ -82,7 +82,8 @@ impl Inventory {
         self.items.iter_mut().find(|i| i.kind == item)
     }
 
-    pub fn has_key(&self) -> bool {
-        self.item_count(ItemKind::MasterKey) > 0
+    /// Checks whether the inventory has the given key or the master key that opens every door.
+    pub fn has_key(&self, key: ItemKind) -> bool {
+        self.item_count(key) > 0 || self.item_count(ItemKind::MasterKey) > 0
     }
 }
@@ -0,0 +1,274 @@
+//! Ambush spawners release bots from level geometry (vents, holes in walls, etc.) when the player
+//! comes close, enters a trigger volume or when some other script activates them.
+
+use crate::{
+    bot::{Bot, BotCommand, BotKind},
+    current_level_ref,
+    level::navigation::closest_navmesh_point,
+};
+use fyrox::{
+    core::{
+        inspect::prelude::*,
+        pool::Handle,
+        reflect::Reflect,
+        uuid::{uuid, Uuid},
+        variable::InheritableVariable,
+        visitor::prelude::*,
+    },
+    impl_component_provider,
+    scene::{
+        node::{Node, TypeUuidProvider},
+        rigidbody::{RigidBody, RigidBodyType},
+    },
+    script::{ScriptContext, ScriptTrait},
+    utils::log::Log,
+};
+use strum_macros::{AsRefStr, EnumString, EnumVariantNames};
+
+#[derive(
+    Copy,
+    Clone,
+    PartialEq,
+    Eq,
+    Debug,
+    Visit,
+    Reflect,
+    Inspect,
+    AsRefStr,
+    EnumString,
+    EnumVariantNames,
+)]
+#[repr(u32)]
+pub enum AmbushActivation {
+    /// The ambush starts when the player is closer than the radius of the spawner.
+    Proximity = 0,
+    /// The ambush starts when the player enters bounds of the trigger node.
+    Trigger = 1,
+    /// The ambush is started by other scripts, a `StartAmbush` trigger for example.
+    Script = 2,
+}
+
+impl Default for AmbushActivation {
+    fn default() -> Self {
+        Self::Proximity
+    }
+}
+
+#[derive(Visit, Reflect, Inspect, Default, Debug, Clone)]
+pub struct AmbushBot(pub BotKind);
+
+#[derive(Visit, Reflect, Inspect, Debug, Clone)]
+pub struct AmbushSpawner {
+    activation: InheritableVariable<AmbushActivation>,
+
+    #[inspect(
+        description = "Distance to the player at which the ambush starts.",
+        min_value = "0.0"
+    )]
+    radius: InheritableVariable<f32>,
+
+    #[inspect(description = "A node whose bounds start the ambush when the player enters them.")]
+    trigger: InheritableVariable<Handle<Node>>,
+
+    #[inspect(description = "Kinds of bots to spawn, used one after another in a loop.")]
+    bots: Vec<AmbushBot>,
+
+    #[inspect(description = "Total amount of bots to spawn.")]
+    count: InheritableVariable<u32>,
+
+    #[inspect(
+        description = "Time before the first bot and between bots, in seconds.",
+        min_value = "0.0"
+    )]
+    delay: InheritableVariable<f32>,
+
+    #[inspect(description = "A point where bots appear, it must be close to the navmesh.")]
+    exit_point: InheritableVariable<Handle<Node>>,
+
+    #[inspect(description = "Vent grate or other obstacle that is knocked out by the first bot.")]
+    cover: InheritableVariable<Handle<Node>>,
+
+    #[reflect(hidden)]
+    #[inspect(skip)]
+    active: bool,
+    #[reflect(hidden)]
+    #[inspect(skip)]
+    spawned: u32,
+    #[reflect(hidden)]
+    #[inspect(skip)]
+    timer: f32,
+}
+
+impl Default for AmbushSpawner {
+    fn default() -> Self {
+        Self {
+            activation: Default::default(),
+            radius: 5.0.into(),
+            trigger: Default::default(),
+            bots: Default::default(),
+            count: 1.into(),
+            delay: 1.0.into(),
+            exit_point: Default::default(),
+            cover: Default::default(),
+            active: false,
+            spawned: 0,
+            timer: 0.0,
+        }
+    }
+}
+
+impl_component_provider!(AmbushSpawner);
+
+impl TypeUuidProvider for AmbushSpawner {
+    fn type_uuid() -> Uuid {
+        uuid!("2f6b0e6c-4c1a-4d3e-9a57-6c0b1b3f8a41")
+    }
+}
+
+impl AmbushSpawner {
+    const EMERGE_SOUND: &'static str = "data/sounds/vent_emerge.wav";
+    /// Time that it takes for a bot to get from the spawner to the exit point.
+    const EMERGE_TIME: f32 = 1.5;
+    const COVER_IMPULSE: f32 = 2.0;
+
+    /// Starts the ambush, does nothing if the ambush was already started.
+    pub fn activate(&mut self) {
+        if !self.active {
+            self.active = true;
+            self.timer = *self.delay;
+        }
+    }
+
+    pub fn is_finished(&self) -> bool {
+        self.spawned >= *self.count
+    }
+
+    fn knock_out_cover(&self, ctx: &mut ScriptContext) {
+        let direction = ctx.scene.graph[ctx.handle].look_vector();
+
+        if let Some(cover) = ctx.scene.graph.try_get_mut(*self.cover) {
+            if let Some(rigid_body) = cover.cast_mut::<RigidBody>() {
+                rigid_body.set_body_type(RigidBodyType::Dynamic);
+                rigid_body.wake_up();
+                rigid_body.apply_impulse(direction.scale(Self::COVER_IMPULSE));
+            } else {
+                cover.set_visibility(false);
+            }
+        }
+    }
+
+    fn spawn_bot(&mut self, ctx: &mut ScriptContext) {
+        let kind = self.bots[self.spawned as usize % self.bots.len()].0;
+
+        let exit_point = if ctx.scene.graph.is_valid_handle(*self.exit_point) {
+            *self.exit_point
+        } else {
+            ctx.handle
+        };
+        let (rotation, position) = ctx
+            .scene
+            .graph
+            .global_rotation_position_no_scale(exit_point);
+        let position = closest_navmesh_point(ctx.scene, position);
+        // Bots crawl out of the spawner (a vent for example) to the exit point.
+        let spawner_position = ctx.scene.graph[ctx.handle].global_position();
+
+        if self.spawned == 0 {
+            self.knock_out_cover(ctx);
+        }
+
+        let bot = Bot::add_to_scene(
+            ctx.scene,
+            kind,
+            ctx.resource_manager,
+            spawner_position,
+            rotation,
+        );
+
+        if let Some(bot) = ctx.scene.graph[bot].try_get_script_mut::<Bot>() {
+            bot.emerge(spawner_position, position, Self::EMERGE_TIME);
+        }
+
+        if let Some(level) = current_level_ref(ctx.plugins) {
+            level.sound_manager.play_sound(
+                &mut ctx.scene.graph,
+                Self::EMERGE_SOUND,
+                spawner_position,
+                1.0,
+                1.0,
+                1.0,
+            );
+
+            // Ambushers know where the player is, so they attack immediately.
+            if let Some(player) = ctx.scene.graph.try_get(level.player) {
+                let player_position = player.global_position();
+                if let Some(bot) = ctx.scene.graph[bot].try_get_script_mut::<Bot>() {
+                    bot.commands_queue.push_back(BotCommand::Alert {
+                        target: level.player,
+                        position: player_position,
+                    });
+                }
+            }
+        }
+
+        self.spawned += 1;
+    }
+
+    fn should_activate(&self, ctx: &ScriptContext) -> bool {
+        let player = match current_level_ref(ctx.plugins)
+            .and_then(|level| ctx.scene.graph.try_get(level.player))
+        {
+            Some(player) => player.global_position(),
+            None => return false,
+        };
+
+        match *self.activation {
+            AmbushActivation::Proximity => {
+                ctx.scene.graph[ctx.handle]
+                    .global_position()
+                    .metric_distance(&player)
+                    <= *self.radius
+            }
+            AmbushActivation::Trigger => ctx
+                .scene
+                .graph
+                .try_get(*self.trigger)
+                .map_or(false, |trigger| {
+                    trigger.world_bounding_box().is_contains_point(player)
+                }),
+            AmbushActivation::Script => false,
+        }
+    }
+}
+
+impl ScriptTrait for AmbushSpawner {
+    fn on_start(&mut self, _ctx: &mut ScriptContext) {
+        if self.bots.is_empty() {
+            Log::warn("Ambush spawner has no bots to spawn!")
+        }
+    }
+
+    fn on_update(&mut self, ctx: &mut ScriptContext) {
+        if self.is_finished() || self.bots.is_empty() {
+            return;
+        }
+
+        if !self.active {
+            if self.should_activate(ctx) {
+                self.activate();
+            } else {
+                return;
+            }
+        }
+
+        self.timer -= ctx.dt;
+        if self.timer <= 0.0 {
+            self.timer = *self.delay;
+            self.spawn_bot(ctx);
+        }
+    }
+
+    fn id(&self) -> Uuid {
+        Self::type_uuid()
+    }
+}
@@ -0,0 +1,221 @@
+//! Area effects are left by special grenades: burning areas, EMP pulses and stasis fields.
+
+use crate::{
+    character::{try_get_character_mut, try_get_character_ref, CharacterCommand},
+    current_level_ref,
+    damage::DamageSource,
+    door::door_mut,
+    level::turret::Turret,
+    weapon::projectile::DamageKind,
+};
+use fyrox::{
+    core::{
+        algebra::Vector3,
+        color::Color,
+        inspect::prelude::*,
+        pool::Handle,
+        reflect::Reflect,
+        uuid::{uuid, Uuid},
+        visitor::prelude::*,
+    },
+    impl_component_provider,
+    scene::{
+        base::BaseBuilder,
+        graph::Graph,
+        light::{point::PointLightBuilder, BaseLightBuilder},
+        node::{Node, TypeUuidProvider},
+        transform::TransformBuilder,
+    },
+    script::{Script, ScriptContext, ScriptTrait},
+};
+use serde::Deserialize;
+use strum_macros::{AsRefStr, EnumString, EnumVariantNames};
+
+#[derive(
+    Copy,
+    Clone,
+    PartialEq,
+    Eq,
+    Debug,
+    Deserialize,
+    Hash,
+    Visit,
+    Reflect,
+    Inspect,
+    AsRefStr,
+    EnumString,
+    EnumVariantNames,
+)]
+pub enum AreaEffectKind {
+    /// Burns every actor in the area.
+    Fire,
+    /// Disables turrets and door panels in the area once.
+    Emp,
+    /// Slows down every actor in the area.
+    Stasis,
+}
+
+impl Default for AreaEffectKind {
+    fn default() -> Self {
+        Self::Fire
+    }
+}
+
+impl AreaEffectKind {
+    fn color(self) -> Color {
+        match self {
+            AreaEffectKind::Fire => Color::opaque(255, 120, 20),
+            AreaEffectKind::Emp => Color::opaque(80, 140, 255),
+            AreaEffectKind::Stasis => Color::opaque(120, 255, 230),
+        }
+    }
+}
+
+#[derive(Deserialize, Copy, Clone, Debug)]
+pub struct AreaEffectDefinition {
+    pub kind: AreaEffectKind,
+    pub radius: f32,
+    /// Lifetime of the area in seconds.
+    pub duration: f32,
+    #[serde(default)]
+    pub damage_per_second: f32,
+    /// How long turrets and door panels stay disabled after EMP pulse.
+    #[serde(default)]
+    pub disable_time: f32,
+}
+
+#[derive(Visit, Reflect, Inspect, Default, Debug, Clone)]
+pub struct AreaEffect {
+    kind: AreaEffectKind,
+    radius: f32,
+    lifetime: f32,
+    damage_per_second: f32,
+    disable_time: f32,
+    #[reflect(hidden)]
+    #[inspect(skip)]
+    source: DamageSource,
+    #[reflect(hidden)]
+    #[inspect(skip)]
+    activated: bool,
+}
+
+impl_component_provider!(AreaEffect);
+
+impl TypeUuidProvider for AreaEffect {
+    fn type_uuid() -> Uuid {
+        uuid!("2f7f4d8e-3b0c-4f7a-a4f5-6e8b1c9d0a37")
+    }
+}
+
+impl AreaEffect {
+    /// Time during which actor stays slowed after leaving stasis field.
+    const STASIS_LINGER_TIME: f32 = 0.5;
+
+    pub fn add_to_scene(
+        graph: &mut Graph,
+        definition: &AreaEffectDefinition,
+        position: Vector3<f32>,
+        source: DamageSource,
+    ) -> Handle<Node> {
+        PointLightBuilder::new(
+            BaseLightBuilder::new(
+                BaseBuilder::new()
+                    .with_script(Script::new(AreaEffect {
+                        kind: definition.kind,
+                        radius: definition.radius,
+                        lifetime: definition.duration,
+                        damage_per_second: definition.damage_per_second,
+                        disable_time: definition.disable_time,
+                        source,
+                        activated: false,
+                    }))
+                    .with_local_transform(
+                        TransformBuilder::new()
+                            .with_local_position(position + Vector3::new(0.0, 0.2, 0.0))
+                            .build(),
+                    ),
+            )
+            .with_color(definition.kind.color())
+            .with_scatter_enabled(false)
+            .cast_shadows(false),
+        )
+        .with_radius(definition.radius * 2.0)
+        .build(graph)
+    }
+
+    fn apply_emp(&self, ctx: &mut ScriptContext, center: Vector3<f32>) {
+        let graph = &mut ctx.scene.graph;
+
+        for &door_handle in current_level_ref(ctx.plugins)
+            .unwrap()
+            .doors_container
+            .doors
+            .iter()
+        {
+            let door = door_mut(door_handle, graph);
+            if door.initial_position().metric_distance(&center) <= self.radius {
+                door.disable_panel(self.disable_time);
+            }
+        }
+
+        let turrets = graph
+            .pair_iter()
+            .filter(|(_, n)| {
+                n.has_script::<Turret>()
+                    && n.global_position().metric_distance(&center) <= self.radius
+            })
+            .map(|(h, _)| h)
+            .collect::<Vec<_>>();
+        for turret in turrets {
+            if let Some(turret) = graph[turret].try_get_script_mut::<Turret>() {
+                turret.disable(self.disable_time);
+            }
+        }
+    }
+}
+
+impl ScriptTrait for AreaEffect {
+    fn on_update(&mut self, ctx: &mut ScriptContext) {
+        let center = ctx.scene.graph[ctx.handle].global_position();
+
+        if self.kind == AreaEffectKind::Emp {
+            if !self.activated {
+                self.activated = true;
+                self.apply_emp(ctx, center);
+            }
+        } else {
+            for &actor in current_level_ref(ctx.plugins).unwrap().actors.iter() {
+                let close_enough = try_get_character_ref(actor, &ctx.scene.graph)
+                    .map_or(false, |c| {
+                        c.position(&ctx.scene.graph).metric_distance(&center) <= self.radius
+                    });
+                if !close_enough {
+                    continue;
+                }
+
+                if let Some(character) = try_get_character_mut(actor, &mut ctx.scene.graph) {
+                    match self.kind {
+                        AreaEffectKind::Fire => character.push_command(CharacterCommand::Damage {
+                            source: self.source,
+                            hitbox: None,
+                            amount: self.damage_per_second * ctx.dt,
+                            kind: DamageKind::Fire,
+                            critical_shot_probability: 0.0,
+                        }),
+                        AreaEffectKind::Stasis => character.apply_stasis(Self::STASIS_LINGER_TIME),
+                        AreaEffectKind::Emp => (),
+                    }
+                }
+            }
+        }
+
+        self.lifetime -= ctx.dt;
+        if self.lifetime <= 0.0 {
+            ctx.scene.graph.remove_node(ctx.handle);
+        }
+    }
+
+    fn id(&self) -> Uuid {
+        Self::type_uuid()
+    }
+}
@@ -0,0 +1,204 @@
+//! Bot benchmark spawns a horde of bots around itself, it is used to measure the time spent on
+//! updating bots (see debug info) with and without level of detail of bots. The benchmark switches
+//! level of detail on and off periodically and logs average frame time and bot update time of each
+//! phase, so both numbers are taken from the same run.
+
+use crate::{
+    bot::{Bot, BotKind},
+    current_level_mut,
+    level::navigation::random_navmesh_point,
+};
+use fyrox::{
+    core::{
+        algebra::{UnitQuaternion, Vector3},
+        inspect::prelude::*,
+        rand::{thread_rng, Rng},
+        reflect::Reflect,
+        uuid::{uuid, Uuid},
+        variable::InheritableVariable,
+        visitor::prelude::*,
+    },
+    impl_component_provider,
+    scene::{
+        base::BaseBuilder, node::TypeUuidProvider, pivot::PivotBuilder,
+        transform::TransformBuilder, Scene,
+    },
+    script::{Script, ScriptContext, ScriptTrait},
+    utils::log::Log,
+};
+use std::time::{Duration, Instant};
+
+#[derive(Visit, Reflect, Inspect, Debug, Clone)]
+pub struct BotBenchmark {
+    #[inspect(description = "Kind of bots to spawn.")]
+    kind: InheritableVariable<BotKind>,
+
+    #[inspect(description = "Total amount of bots to spawn.")]
+    count: InheritableVariable<u32>,
+
+    #[inspect(
+        description = "Max distance from the benchmark to spawned bots.",
+        min_value = "0.0"
+    )]
+    radius: InheritableVariable<f32>,
+
+    #[inspect(description = "Whether distant and off-screen bots should update less often.")]
+    use_lod: InheritableVariable<bool>,
+
+    #[inspect(
+        description = "Time in seconds after which level of detail is toggled, zero disables toggling.",
+        min_value = "0.0"
+    )]
+    #[visit(optional)]
+    phase_duration: InheritableVariable<f32>,
+
+    #[reflect(hidden)]
+    #[inspect(skip)]
+    spawned: bool,
+
+    #[reflect(hidden)]
+    #[inspect(skip)]
+    #[visit(skip)]
+    phase: Phase,
+}
+
+#[derive(Default, Debug, Clone)]
+struct Phase {
+    elapsed: f32,
+    frames: u32,
+    frame_time: Duration,
+    last_frame: Option<Instant>,
+}
+
+impl Default for BotBenchmark {
+    fn default() -> Self {
+        Self {
+            kind: Default::default(),
+            count: 200.into(),
+            radius: 100.0.into(),
+            use_lod: true.into(),
+            phase_duration: 20.0.into(),
+            spawned: false,
+            phase: Default::default(),
+        }
+    }
+}
+
+impl BotBenchmark {
+    /// Name of a node of a level at which the benchmark is placed.
+    const SPAWN_POINT: &'static str = "PlayerSpawnPoint";
+
+    /// Adds the benchmark with default settings to a level, so it can be started on any level
+    /// without editing it. Bots are spawned around the player's spawn point.
+    pub fn add_to_scene(scene: &mut Scene) {
+        let spawn_point = scene.graph.find_by_name_from_root(Self::SPAWN_POINT);
+        let position = match scene.graph.try_get(spawn_point) {
+            Some(spawn_point) => spawn_point.global_position(),
+            None => {
+                Log::warn(format!(
+                    "There is no {} in the level, bot benchmark is placed at the origin.",
+                    Self::SPAWN_POINT
+                ));
+                Vector3::default()
+            }
+        };
+
+        PivotBuilder::new(
+            BaseBuilder::new()
+                .with_name("BotBenchmark")
+                .with_local_transform(
+                    TransformBuilder::new()
+                        .with_local_position(position)
+                        .build(),
+                )
+                .with_script(Script::new(Self::default())),
+        )
+        .build(&mut scene.graph);
+    }
+}
+
+impl_component_provider!(BotBenchmark);
+
+impl TypeUuidProvider for BotBenchmark {
+    fn type_uuid() -> Uuid {
+        uuid!("6d3f2a91-0b7e-4c58-8e14-f2a9c5b0d73e")
+    }
+}
+
+impl ScriptTrait for BotBenchmark {
+    fn on_start(&mut self, ctx: &mut ScriptContext) {
+        let level = current_level_mut(ctx.plugins).expect("Level must exist!");
+        level.disable_bot_lod = !*self.use_lod;
+        // Drop statistics gathered before the benchmark has started.
+        level.bot_statistics.take_average_update_time();
+
+        // Bots are saved with the level, so they must be spawned only once.
+        if self.spawned {
+            return;
+        }
+        self.spawned = true;
+
+        let center = ctx.scene.graph[ctx.handle].global_position();
+        let mut rng = thread_rng();
+        let mut count = 0;
+        for _ in 0..*self.count {
+            if let Some(position) = random_navmesh_point(ctx.scene, center, *self.radius) {
+                let rotation = UnitQuaternion::from_axis_angle(
+                    &Vector3::y_axis(),
+                    rng.gen_range(0.0..std::f32::consts::TAU),
+                );
+                Bot::add_to_scene(
+                    ctx.scene,
+                    *self.kind,
+                    ctx.resource_manager,
+                    position,
+                    rotation,
+                );
+                count += 1;
+            }
+        }
+
+        Log::info(format!(
+            "Bot benchmark spawned {} bots, level of detail is {}.",
+            count,
+            if *self.use_lod { "enabled" } else { "disabled" }
+        ));
+    }
+
+    fn on_update(&mut self, ctx: &mut ScriptContext) {
+        let now = Instant::now();
+        if let Some(last_frame) = self.phase.last_frame.replace(now) {
+            self.phase.frame_time += now - last_frame;
+            self.phase.frames += 1;
+        }
+        self.phase.elapsed += ctx.dt;
+
+        if *self.phase_duration <= 0.0 || self.phase.elapsed < *self.phase_duration {
+            return;
+        }
+
+        let level = current_level_mut(ctx.plugins).expect("Level must exist!");
+
+        Log::info(format!(
+            "Bot benchmark, level of detail {}: average frame time {:?}, average bots update time {:?} ({} frames).",
+            if *self.use_lod { "enabled" } else { "disabled" },
+            self.phase
+                .frame_time
+                .checked_div(self.phase.frames)
+                .unwrap_or_default(),
+            level.bot_statistics.take_average_update_time(),
+            self.phase.frames
+        ));
+
+        self.use_lod.set(!*self.use_lod);
+        level.disable_bot_lod = !*self.use_lod;
+        self.phase = Phase {
+            last_frame: Some(now),
+            ..Default::default()
+        };
+    }
+
+    fn id(&self) -> Uuid {
+        Self::type_uuid()
+    }
+}
@@ -0,0 +1,190 @@
+//! Corpses of bots and corpse props placed on levels could be reanimated by some bots, the level
+//! tracks state of every corpse so it won't be reanimated twice or after it was destroyed.
+
+use crate::{bot::Bot, current_level_mut};
+use fyrox::{
+    core::{
+        algebra::Vector3,
+        inspect::prelude::*,
+        pool::Handle,
+        reflect::Reflect,
+        uuid::{uuid, Uuid},
+        visitor::prelude::*,
+    },
+    impl_component_provider,
+    scene::{
+        graph::Graph,
+        node::{Node, TypeUuidProvider},
+    },
+    script::{ScriptContext, ScriptDeinitContext, ScriptTrait},
+};
+use std::cell::RefCell;
+
+#[derive(Copy, Clone, PartialEq, Eq, Debug, Visit)]
+#[repr(u32)]
+pub enum CorpseState {
+    Intact = 0,
+    Reanimating = 1,
+    /// The corpse was stomped or dismembered and can't be reanimated anymore.
+    Destroyed = 2,
+}
+
+impl Default for CorpseState {
+    fn default() -> Self {
+        Self::Intact
+    }
+}
+
+#[derive(Visit, Default, Debug, Clone)]
+pub struct Corpse {
+    pub node: Handle<Node>,
+    pub state: CorpseState,
+    /// Time spent on reanimation of the corpse.
+    pub progress: f32,
+    /// A bot that reanimates the corpse.
+    pub reanimator: Handle<Node>,
+}
+
+/// Corpses are modified by bots and the player during their update, so the container uses
+/// interior mutability.
+#[derive(Visit, Default, Debug)]
+pub struct CorpseContainer {
+    corpses: RefCell<Vec<Corpse>>,
+}
+
+impl CorpseContainer {
+    /// Max distance from the player to a corpse that could be stomped.
+    pub const STOMP_RADIUS: f32 = 1.0;
+
+    /// Adds a corpse to the container, does nothing if the corpse is already registered.
+    pub fn register(&self, node: Handle<Node>) {
+        let mut corpses = self.corpses.borrow_mut();
+        if !corpses.iter().any(|c| c.node == node) {
+            corpses.push(Corpse {
+                node,
+                ..Default::default()
+            });
+        }
+    }
+
+    pub fn unregister(&self, node: Handle<Node>) {
+        self.corpses.borrow_mut().retain(|c| c.node != node);
+    }
+
+    pub fn corpses(&self) -> Vec<Corpse> {
+        self.corpses.borrow().clone()
+    }
+
+    pub fn get(&self, node: Handle<Node>) -> Option<Corpse> {
+        self.corpses
+            .borrow()
+            .iter()
+            .find(|c| c.node == node)
+            .cloned()
+    }
+
+    pub fn destroy(&self, node: Handle<Node>) {
+        if let Some(corpse) = self
+            .corpses
+            .borrow_mut()
+            .iter_mut()
+            .find(|c| c.node == node)
+        {
+            corpse.state = CorpseState::Destroyed;
+        }
+    }
+
+    /// Destroys the closest corpse within the radius, returns `true` if there was such corpse.
+    pub fn destroy_near(&self, graph: &Graph, position: Vector3<f32>, radius: f32) -> bool {
+        let mut corpses = self.corpses.borrow_mut();
+        let closest = corpses
+            .iter_mut()
+            .filter(|c| c.state != CorpseState::Destroyed)
+            .map(|c| (corpse_position(graph, c.node).metric_distance(&position), c))
+            .filter(|(distance, _)| *distance <= radius)
+            .min_by(|(a, _), (b, _)| a.partial_cmp(b).unwrap_or(std::cmp::Ordering::Equal));
+
+        if let Some((_, corpse)) = closest {
+            corpse.state = CorpseState::Destroyed;
+            true
+        } else {
+            false
+        }
+    }
+
+    /// Advances reanimation of the corpse by the given bot and returns total reanimation time,
+    /// returns `None` if the corpse can't be reanimated by the bot.
+    pub fn reanimate(&self, node: Handle<Node>, reanimator: Handle<Node>, dt: f32) -> Option<f32> {
+        let mut corpses = self.corpses.borrow_mut();
+        let corpse = corpses.iter_mut().find(|c| c.node == node)?;
+        match corpse.state {
+            CorpseState::Intact => {
+                corpse.state = CorpseState::Reanimating;
+                corpse.reanimator = reanimator;
+                corpse.progress = dt;
+            }
+            CorpseState::Reanimating if corpse.reanimator == reanimator => {
+                corpse.progress += dt;
+            }
+            _ => return None,
+        }
+        Some(corpse.progress)
+    }
+
+    /// Stops reanimation of the corpse, the corpse could be reanimated again from scratch.
+    pub fn interrupt(&self, node: Handle<Node>, reanimator: Handle<Node>) {
+        if let Some(corpse) = self
+            .corpses
+            .borrow_mut()
+            .iter_mut()
+            .find(|c| c.node == node && c.reanimator == reanimator)
+        {
+            if corpse.state == CorpseState::Reanimating {
+                corpse.state = CorpseState::Intact;
+                corpse.progress = 0.0;
+                corpse.reanimator = Handle::NONE;
+            }
+        }
+    }
+}
+
+/// Returns position of a corpse, bodies of dead bots could be moved by physics.
+pub fn corpse_position(graph: &Graph, node: Handle<Node>) -> Vector3<f32> {
+    match graph.try_get(node) {
+        Some(corpse) => corpse
+            .try_get_script::<Bot>()
+            .map_or_else(|| corpse.global_position(), |bot| bot.position(graph)),
+        None => Default::default(),
+    }
+}
+
+/// Marks a prop (a body, a covered body, etc.) as a corpse that could be reanimated.
+#[derive(Visit, Reflect, Inspect, Default, Debug, Clone)]
+pub struct CorpseProp;
+
+impl_component_provider!(CorpseProp);
+
+impl TypeUuidProvider for CorpseProp {
+    fn type_uuid() -> Uuid {
+        uuid!("7d3c2b8e-51f4-4a6b-8e0d-2f9a1c4b6e73")
+    }
+}
+
+impl ScriptTrait for CorpseProp {
+    fn on_init(&mut self, ctx: &mut ScriptContext) {
+        current_level_mut(ctx.plugins)
+            .expect("Level must exist!")
+            .corpses
+            .register(ctx.handle);
+    }
+
+    fn on_deinit(&mut self, ctx: &mut ScriptDeinitContext) {
+        if let Some(level) = current_level_mut(ctx.plugins) {
+            level.corpses.unregister(ctx.node_handle);
+        }
+    }
+
+    fn id(&self) -> Uuid {
+        Self::type_uuid()
+    }
+}
@@ -1,6 +1,9 @@
 use crate::{
     character::{try_get_character_mut, CharacterCommand},
     current_level_ref,
+    damage::{DamageSource, EnvironmentKind},
+    level::spatial::SpatialCategory,
+    weapon::projectile::DamageKind,
 };
 use fyrox::{
     core::{
@@ -28,17 +31,19 @@ impl TypeUuidProvider for DeathZone {
 impl ScriptTrait for DeathZone {
     fn on_update(&mut self, context: &mut ScriptContext) {
         let self_bounds = context.scene.graph[context.handle].world_bounding_box();
-        for &actor in current_level_ref(context.plugins).unwrap().actors.iter() {
-            let character_position = context.scene.graph[actor].global_position();
+        for actor in current_level_ref(context.plugins)
+            .unwrap()
+            .spatial_index
+            .in_bounds(&self_bounds, SpatialCategory::Actor)
+        {
             if let Some(character) = try_get_character_mut(actor, &mut context.scene.graph) {
-                if self_bounds.is_contains_point(character_position) {
-                    character.push_command(CharacterCommand::Damage {
-                        who: Default::default(),
-                        hitbox: None,
-                        amount: 99999.0,
-                        critical_shot_probability: 0.0,
-                    });
-                }
+                character.push_command(CharacterCommand::Damage {
+                    source: DamageSource::Environment(EnvironmentKind::DeathZone),
+                    hitbox: None,
+                    amount: 99999.0,
+                    kind: DamageKind::Point,
+                    critical_shot_probability: 0.0,
+                });
             }
         }
     }
@@ -0,0 +1,395 @@
+//! AI director paces encounters dynamically. It tracks stress of the player (recent damage,
+//! health, ammo and medkits, time since the last fight) and spawns waves of bots from spawn
+//! candidates placed by designers, gives the player time to recover after intense fights and
+//! biases loot of bots towards what the player lacks. Director tuning is defined per level in
+//! `data/configs/director.ron`.
+
+use crate::{
+    bot::{Bot, BotCommand, BotKind},
+    character::try_get_character_ref,
+    current_level_mut,
+    level::{ambush::AmbushBot, item::ItemKind, navigation::closest_navmesh_point},
+    player::Player,
+};
+use fyrox::{
+    core::{
+        algebra::{UnitQuaternion, Vector3},
+        color::Color,
+        inspect::prelude::*,
+        pool::Handle,
+        reflect::Reflect,
+        uuid::{uuid, Uuid},
+        visitor::prelude::*,
+    },
+    engine::resource_manager::ResourceManager,
+    impl_component_provider,
+    lazy_static::lazy_static,
+    rand::{seq::SliceRandom, thread_rng},
+    scene::{
+        debug::SceneDrawingContext,
+        graph::Graph,
+        node::{Node, TypeUuidProvider},
+        Scene,
+    },
+    script::{ScriptContext, ScriptDeinitContext, ScriptTrait},
+};
+use serde::Deserialize;
+use std::{collections::HashMap, fmt::Display, fs::File};
+
+#[derive(Deserialize, Debug)]
+pub struct DirectorSettings {
+    pub enabled: bool,
+    /// Kinds of bots to spawn, a spawn candidate may override it.
+    pub bots: Vec<BotKind>,
+    /// Min distance from the player to a spawn candidate.
+    pub min_spawn_distance: f32,
+    /// Max distance from the player to a spawn candidate.
+    pub max_spawn_distance: f32,
+    /// Field of view of the player in degrees, candidates inside it are not used.
+    pub view_angle: f32,
+    /// Max amount of spawned bots alive at the same time.
+    pub max_alive: u32,
+    /// Amount of bots in a wave when the player is not stressed at all.
+    pub wave_size: u32,
+    /// Min time between waves, in seconds.
+    pub wave_interval: f32,
+    /// Min time since the last fight before a wave, in seconds.
+    pub calm_time: f32,
+    /// Stress `[0; 1]` at which the director stops spawning and lets the player recover.
+    pub peak_stress: f32,
+    /// Duration of recovery, in seconds.
+    pub recovery_time: f32,
+    /// How fast stress caused by damage fades, per second.
+    pub stress_decay: f32,
+    /// Amount of ammo at which the player does not need more ammo.
+    pub ammo_comfort: u32,
+    /// Amount of medkits and medpacks at which the player does not need more of them.
+    pub medkit_comfort: u32,
+    /// How much loot of bots is biased towards items that the player lacks.
+    pub loot_bias: f32,
+}
+
+#[derive(Deserialize)]
+pub struct DirectorSettingsContainer {
+    default: DirectorSettings,
+    /// Settings of levels, keyed by path of a level.
+    levels: HashMap<String, DirectorSettings>,
+}
+
+impl DirectorSettingsContainer {
+    pub fn new() -> Self {
+        let file = File::open("data/configs/director.ron").unwrap();
+        ron::de::from_reader(file).unwrap()
+    }
+}
+
+lazy_static! {
+    static ref SETTINGS: DirectorSettingsContainer = DirectorSettingsContainer::new();
+}
+
+/// Returns director settings of a level, levels without own settings use the default ones.
+pub fn director_settings(map_path: &str) -> &'static DirectorSettings {
+    SETTINGS.levels.get(map_path).unwrap_or(&SETTINGS.default)
+}
+
+/// Returns `[0; 1]` shortage of some resource, `0` means the player has enough of it.
+fn shortage(amount: u32, comfort: u32) -> f32 {
+    if comfort == 0 {
+        0.0
+    } else {
+        1.0 - (amount as f32 / comfort as f32).min(1.0)
+    }
+}
+
+#[derive(Visit, Default, Debug)]
+pub struct Director {
+    pub spawn_points: Vec<Handle<Node>>,
+    /// Alive bots spawned by the director.
+    spawned: Vec<Handle<Node>>,
+    stress: f32,
+    /// Stress caused by recent damage, fades over time.
+    damage_stress: f32,
+    last_player_health: f32,
+    time_since_fight: f32,
+    wave_timer: f32,
+    recovery_timer: f32,
+    ammo_need: f32,
+    health_need: f32,
+}
+
+impl Director {
+    /// Stress caused by damage that is equal to max health of the player.
+    const DAMAGE_STRESS_SCALE: f32 = 2.0;
+
+    pub fn is_recovering(&self) -> bool {
+        self.recovery_timer > 0.0
+    }
+
+    /// Returns an item (and its amount) from the candidates for loot of a bot, items that the
+    /// player lacks are more likely to be selected.
+    pub fn choose_loot<'a>(
+        &self,
+        settings: &DirectorSettings,
+        candidates: &'a [(ItemKind, u32)],
+    ) -> Option<&'a (ItemKind, u32)> {
+        candidates
+            .choose_weighted(&mut thread_rng(), |(item, _)| {
+                let need = match item {
+                    ItemKind::Ammo => self.ammo_need,
+                    ItemKind::Medkit | ItemKind::Medpack => self.health_need,
+                    _ => 0.0,
+                };
+                1.0 + settings.loot_bias * need
+            })
+            .ok()
+    }
+
+    fn update_stress(
+        &mut self,
+        settings: &DirectorSettings,
+        graph: &Graph,
+        player: Handle<Node>,
+        actors: &[Handle<Node>],
+        dt: f32,
+    ) {
+        let character = match try_get_character_ref(player, graph) {
+            Some(character) if !character.is_dead() => character,
+            _ => return,
+        };
+
+        let max_health = character.max_health().max(1.0);
+        let health = character.health;
+
+        let damage = (self.last_player_health - health).max(0.0);
+        self.last_player_health = health;
+
+        self.damage_stress = (self.damage_stress + Self::DAMAGE_STRESS_SCALE * damage / max_health
+            - settings.stress_decay * dt)
+            .clamp(0.0, 1.0);
+
+        let inventory = &character.inventory;
+        let medkits =
+            inventory.item_count(ItemKind::Medkit) + inventory.item_count(ItemKind::Medpack);
+        self.ammo_need = shortage(inventory.item_count(ItemKind::Ammo), settings.ammo_comfort);
+        self.health_need = 0.5
+            * ((1.0 - health / max_health).max(0.0) + shortage(medkits, settings.medkit_comfort));
+
+        self.stress =
+            (self.damage_stress + 0.5 * self.health_need + 0.25 * self.ammo_need).clamp(0.0, 1.0);
+
+        let in_fight = damage > 0.0
+            || actors.iter().any(|&actor| {
+                graph
+                    .try_get(actor)
+                    .and_then(|n| n.try_get_script::<Bot>())
+                    .map_or(false, |bot| !bot.is_dead() && bot.is_targeting(player))
+            });
+        if in_fight {
+            self.time_since_fight = 0.0;
+        } else {
+            self.time_since_fight += dt;
+        }
+
+        self.recovery_timer -= dt;
+        if self.stress >= settings.peak_stress && !self.is_recovering() {
+            self.recovery_timer = settings.recovery_time;
+        }
+    }
+
+    /// Returns spawn candidates that are far enough from the player and out of the player's
+    /// view.
+    fn spawn_candidates(
+        &self,
+        settings: &DirectorSettings,
+        graph: &Graph,
+        player: Handle<Node>,
+    ) -> Vec<Handle<Node>> {
+        let (eye, look) = match graph
+            .try_get(player)
+            .and_then(|n| n.try_get_script::<Player>())
+            .and_then(|p| p.view(graph))
+        {
+            Some(view) => view,
+            None => return Default::default(),
+        };
+
+        let half_view_angle = (settings.view_angle * 0.5).to_radians();
+
+        self.spawn_points
+            .iter()
+            .filter(|&&point| {
+                let position = match graph.try_get(point) {
+                    Some(point) => point.global_position(),
+                    None => return false,
+                };
+                let to_point = position - eye;
+                let distance = to_point.norm();
+
+                distance >= settings.min_spawn_distance
+                    && distance <= settings.max_spawn_distance
+                    && to_point.angle(&look) > half_view_angle
+            })
+            .cloned()
+            .collect()
+    }
+
+    fn spawn_wave(
+        &mut self,
+        settings: &DirectorSettings,
+        scene: &mut Scene,
+        resource_manager: &ResourceManager,
+        player: Handle<Node>,
+        count: usize,
+    ) {
+        let mut candidates = self.spawn_candidates(settings, &scene.graph, player);
+        candidates.shuffle(&mut thread_rng());
+
+        let player_position = scene.graph[player].global_position();
+
+        for point in candidates.into_iter().cycle().take(count) {
+            let kind = match scene.graph[point]
+                .try_get_script::<DirectorSpawnPoint>()
+                .and_then(|p| p.bots.choose(&mut thread_rng()))
+                .map(|bot| bot.0)
+                .or_else(|| settings.bots.choose(&mut thread_rng()).cloned())
+            {
+                Some(kind) => kind,
+                None => return,
+            };
+
+            let position = closest_navmesh_point(scene, scene.graph[point].global_position());
+            let look = player_position - position;
+            let rotation = UnitQuaternion::face_towards(
+                &Vector3::new(look.x, 0.0, look.z)
+                    .try_normalize(f32::EPSILON)
+                    .unwrap_or_else(Vector3::z),
+                &Vector3::y(),
+            );
+
+            let bot = Bot::add_to_scene(scene, kind, resource_manager, position, rotation);
+
+            // The wave is sent to the player, otherwise it could wander away.
+            if let Some(bot) = scene.graph[bot].try_get_script_mut::<Bot>() {
+                bot.commands_queue.push_back(BotCommand::Alert {
+                    target: player,
+                    position: player_position,
+                });
+            }
+
+            self.spawned.push(bot);
+        }
+    }
+
+    pub fn update(
+        &mut self,
+        map_path: &str,
+        scene: &mut Scene,
+        resource_manager: &ResourceManager,
+        player: Handle<Node>,
+        actors: &[Handle<Node>],
+        dt: f32,
+    ) {
+        let settings = director_settings(map_path);
+        if !settings.enabled || !scene.graph.is_valid_handle(player) {
+            return;
+        }
+
+        self.update_stress(settings, &scene.graph, player, actors, dt);
+
+        let graph = &scene.graph;
+        self.spawned.retain(|&bot| {
+            graph
+                .try_get(bot)
+                .and_then(|n| n.try_get_script::<Bot>())
+                .map_or(false, |bot| !bot.is_dead())
+        });
+
+        self.wave_timer -= dt;
+
+        let alive = self.spawned.len() as u32;
+        if self.is_recovering()
+            || self.wave_timer > 0.0
+            || self.time_since_fight < settings.calm_time
+            || alive >= settings.max_alive
+        {
+            return;
+        }
+
+        // Stressed player gets smaller waves.
+        let size = ((settings.wave_size as f32 * (1.0 - self.stress)).ceil() as u32)
+            .clamp(1, settings.max_alive - alive);
+
+        self.spawn_wave(settings, scene, resource_manager, player, size as usize);
+        self.wave_timer = settings.wave_interval;
+    }
+
+    pub fn debug_draw(&self, graph: &Graph, context: &mut SceneDrawingContext) {
+        for &point in self.spawn_points.iter() {
+            if let Some(point) = graph.try_get(point) {
+                let position = point.global_position();
+                context.add_line(fyrox::scene::debug::Line {
+                    begin: position,
+                    end: position + Vector3::new(0.0, 2.0, 0.0),
+                    color: Color::opaque(255, 0, 255),
+                });
+            }
+        }
+    }
+}
+
+impl Display for Director {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "Director: stress {:.2} (damage {:.2}, health need {:.2}, ammo need {:.2})\n\
+            Since fight: {:.1}s, next wave: {:.1}s, recovery: {:.1}s, alive: {}",
+            self.stress,
+            self.damage_stress,
+            self.health_need,
+            self.ammo_need,
+            self.time_since_fight,
+            self.wave_timer.max(0.0),
+            self.recovery_timer.max(0.0),
+            self.spawned.len()
+        )
+    }
+}
+
+/// A place where the director can spawn bots, it must be close to the navmesh.
+#[derive(Visit, Reflect, Inspect, Default, Debug, Clone)]
+pub struct DirectorSpawnPoint {
+    #[inspect(
+        description = "Kinds of bots that can appear here, empty means kinds from director settings of the level."
+    )]
+    bots: Vec<AmbushBot>,
+}
+
+impl_component_provider!(DirectorSpawnPoint);
+
+impl TypeUuidProvider for DirectorSpawnPoint {
+    fn type_uuid() -> Uuid {
+        uuid!("b4e1c7a2-93d5-4f68-a0b9-5e2d8c1f7a36")
+    }
+}
+
+impl ScriptTrait for DirectorSpawnPoint {
+    fn on_init(&mut self, ctx: &mut ScriptContext) {
+        let level = current_level_mut(ctx.plugins).expect("Level must exist!");
+        if !level.director.spawn_points.contains(&ctx.handle) {
+            level.director.spawn_points.push(ctx.handle);
+        }
+    }
+
+    fn on_deinit(&mut self, ctx: &mut ScriptDeinitContext) {
+        if let Some(level) = current_level_mut(ctx.plugins) {
+            level
+                .director
+                .spawn_points
+                .retain(|&point| point != ctx.node_handle);
+        }
+    }
+
+    fn id(&self) -> Uuid {
+        Self::type_uuid()
+    }
+}
@@ -1,4 +1,7 @@
-use crate::{block_on, current_level_mut, weapon::definition::WeaponKind};
+use crate::{
+    block_on, current_level_mut,
+    weapon::{definition::WeaponKind, projectile::ProjectileKind},
+};
 use fyrox::{
     core::{
         algebra::{Point3, Vector3},
@@ -60,6 +63,24 @@ pub enum ItemKind {
 
     // Keys
     MasterKey,
+    SecurityKeycard,
+    MedicalKeycard,
+    EngineeringKeycard,
+
+    // Currency
+    UpgradeModule,
+
+    // Armor
+    ArmorPlate,
+    SuitUpgrade,
+
+    // Throwables
+    IncendiaryGrenade,
+    EmpGrenade,
+    StasisGrenade,
+
+    // Energy
+    StasisCell,
 }
 
 impl Default for ItemKind {
@@ -69,6 +90,14 @@ impl Default for ItemKind {
 }
 
 impl ItemKind {
+    /// All items that can be thrown, in the order of cycling.
+    pub const THROWABLES: [ItemKind; 4] = [
+        ItemKind::Grenade,
+        ItemKind::IncendiaryGrenade,
+        ItemKind::EmpGrenade,
+        ItemKind::StasisGrenade,
+    ];
+
     pub fn associated_weapon(&self) -> Option<WeaponKind> {
         match self {
             ItemKind::PlasmaGun => Some(WeaponKind::PlasmaRifle),
@@ -80,7 +109,42 @@ impl ItemKind {
             | ItemKind::Medpack
             | ItemKind::Ammo
             | ItemKind::Grenade
-            | ItemKind::MasterKey => None,
+            | ItemKind::MasterKey
+            | ItemKind::SecurityKeycard
+            | ItemKind::MedicalKeycard
+            | ItemKind::EngineeringKeycard
+            | ItemKind::UpgradeModule
+            | ItemKind::ArmorPlate
+            | ItemKind::SuitUpgrade
+            | ItemKind::IncendiaryGrenade
+            | ItemKind::EmpGrenade
+            | ItemKind::StasisGrenade
+            | ItemKind::StasisCell => None,
+        }
+    }
+
+    pub fn associated_throwable(&self) -> Option<ProjectileKind> {
+        match self {
+            ItemKind::Grenade => Some(ProjectileKind::Grenade),
+            ItemKind::IncendiaryGrenade => Some(ProjectileKind::Incendiary),
+            ItemKind::EmpGrenade => Some(ProjectileKind::Emp),
+            ItemKind::StasisGrenade => Some(ProjectileKind::Stasis),
+            ItemKind::Medkit
+            | ItemKind::Medpack
+            | ItemKind::Ammo
+            | ItemKind::PlasmaGun
+            | ItemKind::Ak47
+            | ItemKind::M4
+            | ItemKind::Glock
+            | ItemKind::RailGun
+            | ItemKind::MasterKey
+            | ItemKind::SecurityKeycard
+            | ItemKind::MedicalKeycard
+            | ItemKind::EngineeringKeycard
+            | ItemKind::UpgradeModule
+            | ItemKind::ArmorPlate
+            | ItemKind::SuitUpgrade
+            | ItemKind::StasisCell => None,
         }
     }
 }
@@ -187,6 +251,12 @@ pub struct ItemDefinition {
     pub name: String,
     pub consumable: bool,
     pub preview: String,
+    /// Amount of armor restored by the item.
+    #[serde(default)]
+    pub armor: f32,
+    /// Amount of stasis energy restored by the item.
+    #[serde(default)]
+    pub energy: f32,
 }
 
 #[derive(Deserialize, Default)]
@@ -255,7 +325,10 @@ impl Item {
 
         let item_ref = &mut scene.graph[item];
 
-        assert!(item_ref.has_script::<Item>());
+        // Some items share the same model, so force the kind explicitly.
+        let item_script = item_ref.try_get_script_mut::<Item>().unwrap();
+        item_script.kind = kind;
+        item_script.definition = Self::get_definition(kind);
 
         item_ref.local_transform_mut().set_position(position);
     }
@@ -1,25 +1,43 @@
 use crate::{
-    bot::Bot,
-    character::{character_ref, try_get_character_mut, CharacterCommand},
+    bot::{lod::BotStatistics, Bot},
+    character::{try_get_character_mut, try_get_character_ref, CharacterCommand},
     config::SoundConfig,
-    door::DoorContainer,
-    level::item::ItemContainer,
+    damage::DamageSource,
+    door::{Door, DoorContainer},
+    faction::{try_get_faction, FactionRelations},
+    level::{
+        corpse::CorpseContainer,
+        director::Director,
+        item::ItemContainer,
+        navigation::NavigationLink,
+        spatial::{SpatialCategory, SpatialIndex},
+    },
     message::Message,
-    sound::SoundManager,
+    sound::{Noise, NoiseKind, SoundManager},
     utils::use_hrtf,
+    weapon::projectile::DamageKind,
     MessageSender,
 };
 use fyrox::{
-    core::{algebra::Vector3, math::PositionProvider, pool::Handle, visitor::prelude::*},
+    core::{
+        algebra::Vector3, color::Color, math::PositionProvider, pool::Handle, visitor::prelude::*,
+    },
     engine::resource_manager::ResourceManager,
     plugin::PluginContext,
-    scene::{self, node::Node, Scene},
+    scene::{self, graph::Graph, node::Node, Scene},
 };
 use std::path::Path;
 
+pub mod ambush;
+pub mod area_effect;
+pub mod benchmark;
+pub mod corpse;
 pub mod death_zone;
 pub mod decal;
+pub mod director;
 pub mod item;
+pub mod navigation;
+pub mod spatial;
 pub mod spawn;
 pub mod trail;
 pub mod trigger;
@@ -34,6 +52,24 @@ pub struct Level {
     pub items: ItemContainer,
     pub doors_container: DoorContainer,
     pub elevators: Vec<Handle<Node>>,
+    #[visit(optional)]
+    pub workbenches: Vec<Handle<Node>>,
+    #[visit(optional)]
+    pub navigation_links: Vec<Handle<Node>>,
+    #[visit(optional)]
+    pub corpses: CorpseContainer,
+    #[visit(optional)]
+    pub director: Director,
+    #[visit(optional)]
+    pub factions: FactionRelations,
+    #[visit(skip)]
+    pub spatial_index: SpatialIndex,
+    #[visit(skip)]
+    pub bot_statistics: BotStatistics,
+    /// Forces every bot to update each frame, used to compare performance with and without
+    /// level of detail of bots.
+    #[visit(skip)]
+    pub disable_bot_lod: bool,
 
     #[visit(skip)]
     pub sound_manager: SoundManager,
@@ -74,6 +110,14 @@ impl Level {
             doors_container: Default::default(),
             map_path: Default::default(),
             elevators: Default::default(),
+            workbenches: Default::default(),
+            navigation_links: Default::default(),
+            corpses: Default::default(),
+            director: Default::default(),
+            factions: Default::default(),
+            spatial_index: Default::default(),
+            bot_statistics: Default::default(),
+            disable_bot_lod: false,
         }
     }
 
@@ -114,6 +158,14 @@ impl Level {
             doors_container: Default::default(),
             map_path: map,
             elevators: Default::default(),
+            workbenches: Default::default(),
+            navigation_links: Default::default(),
+            corpses: Default::default(),
+            director: Default::default(),
+            factions: Default::default(),
+            spatial_index: Default::default(),
+            bot_statistics: Default::default(),
+            disable_bot_lod: false,
         };
 
         (level, scene)
@@ -127,32 +179,103 @@ impl Level {
         self.player
     }
 
+    fn update_spatial_index(&mut self, graph: &Graph) {
+        let index = &mut self.spatial_index;
+
+        index.clear();
+
+        for &actor in self.actors.iter() {
+            if let Some(character) = try_get_character_ref(actor, graph) {
+                index.insert(actor, SpatialCategory::Actor, character.position(graph));
+            }
+        }
+
+        for &item in self.items.iter() {
+            if let Some(node) = graph.try_get(item) {
+                index.insert(item, SpatialCategory::Item, node.global_position());
+            }
+        }
+
+        for &door in self.doors_container.doors.iter() {
+            if let Some(door_script) = graph
+                .try_get(door)
+                .and_then(|node| node.try_get_script::<Door>())
+            {
+                index.insert(door, SpatialCategory::Door, door_script.initial_position());
+            }
+        }
+
+        for &workbench in self.workbenches.iter() {
+            if let Some(node) = graph.try_get(workbench) {
+                index.insert(
+                    workbench,
+                    SpatialCategory::Interactable,
+                    node.global_position(),
+                );
+            }
+        }
+    }
+
+    pub fn update(&mut self, context: &mut PluginContext) {
+        self.bot_statistics.flush();
+        self.update_spatial_index(&context.scenes[self.scene].graph);
+
+        self.director.update(
+            &self.map_path,
+            &mut context.scenes[self.scene],
+            context.resource_manager,
+            self.player,
+            &self.actors,
+            context.dt,
+        );
+    }
+
     fn apply_splash_damage(
         &mut self,
         engine: &mut PluginContext,
         amount: f32,
         radius: f32,
         center: Vector3<f32>,
-        who: Handle<Node>,
+        source: DamageSource,
         critical_shot_probability: f32,
     ) {
+        self.sound_manager.emit_noise(Noise::new(
+            NoiseKind::Explosion,
+            center,
+            Noise::EXPLOSION_LOUDNESS,
+            source.responsible_actor(),
+        ));
+
         let scene = &mut engine.scenes[self.scene];
+        let source_faction = source.faction(&scene.graph);
+        let responsible_actor = source.responsible_actor();
         // Just find out actors which must be damaged and re-cast damage message for each.
-        for &actor_handle in self.actors.iter() {
-            let character = character_ref(actor_handle, &scene.graph);
-            // TODO: Add occlusion test. This will hit actors through walls.
-            let position = character.position(&scene.graph);
-            if position.metric_distance(&center) <= radius {
-                if let Some(character) = try_get_character_mut(actor_handle, &mut scene.graph) {
-                    character.push_command(CharacterCommand::Damage {
-                        who,
-                        hitbox: None,
-                        /// TODO: Maybe collect all hitboxes?
-                        amount,
-                        critical_shot_probability,
-                    });
+        // TODO: Add occlusion test. This will hit actors through walls.
+        for actor_handle in self
+            .spatial_index
+            .in_radius(center, radius, SpatialCategory::Actor)
+        {
+            // Splash damage spares friends of the attacker, but not the attacker itself.
+            if actor_handle != responsible_actor {
+                if let (Some(source_faction), Some(faction)) =
+                    (source_faction, try_get_faction(actor_handle, &scene.graph))
+                {
+                    if self.factions.is_friendly(source_faction, faction) {
+                        continue;
+                    }
                 }
             }
+
+            if let Some(character) = try_get_character_mut(actor_handle, &mut scene.graph) {
+                character.push_command(CharacterCommand::Damage {
+                    source,
+                    hitbox: None,
+                    /// TODO: Maybe collect all hitboxes?
+                    amount,
+                    kind: DamageKind::Splash,
+                    critical_shot_probability,
+                });
+            }
         }
     }
 
@@ -162,14 +285,14 @@ impl Level {
                 amount,
                 radius,
                 center,
-                who,
+                source,
                 critical_shot_probability,
             } => self.apply_splash_damage(
                 engine,
                 amount,
                 radius,
                 center,
-                who,
+                source,
                 critical_shot_probability,
             ),
             _ => (),
@@ -212,5 +335,18 @@ impl Level {
                 }
             }
         }
+
+        for &link_handle in self.navigation_links.iter() {
+            if let Some(link) = scene.graph[link_handle].try_get_script::<NavigationLink>() {
+                let ends = link.ends(link_handle, &scene.graph, false);
+                drawing_context.add_line(scene::debug::Line {
+                    begin: ends.entrance,
+                    end: ends.exit,
+                    color: Color::opaque(0, 200, 255),
+                });
+            }
+        }
+
+        self.director.debug_draw(&scene.graph, drawing_context);
     }
 }
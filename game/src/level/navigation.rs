@@ -0,0 +1,279 @@
+//! Navigation layer on top of scene navmeshes. A level may have any number of navmeshes (one per
+//! deck for example), they are connected by off-mesh links - doors, elevators, gaps to jump over
+//! and vents. Bots use links to follow their targets from one navmesh to another.
+
+use crate::current_level_mut;
+use fyrox::{
+    core::{
+        algebra::Vector3,
+        inspect::prelude::*,
+        pool::Handle,
+        reflect::Reflect,
+        uuid::{uuid, Uuid},
+        variable::InheritableVariable,
+        visitor::prelude::*,
+    },
+    impl_component_provider,
+    rand::{seq::SliceRandom, thread_rng},
+    scene::{
+        graph::Graph,
+        node::{Node, TypeUuidProvider},
+        Scene,
+    },
+    script::{ScriptContext, ScriptDeinitContext, ScriptTrait},
+    utils::navmesh::Navmesh,
+};
+use std::collections::VecDeque;
+use strum_macros::{AsRefStr, EnumString, EnumVariantNames};
+
+#[derive(
+    Copy,
+    Clone,
+    PartialEq,
+    Eq,
+    Debug,
+    Visit,
+    Reflect,
+    Inspect,
+    AsRefStr,
+    EnumString,
+    EnumVariantNames,
+)]
+#[repr(u32)]
+pub enum NavigationLinkKind {
+    /// Just walk to the other end.
+    Walk = 0,
+    /// Jump over a gap.
+    Jump = 1,
+    /// Open the door and walk through it.
+    Door = 2,
+    /// Call the elevator, ride it to the other floor and get off.
+    Elevator = 3,
+    /// Crawl through a vent, bots are not affected by vent geometry.
+    Vent = 4,
+}
+
+impl Default for NavigationLinkKind {
+    fn default() -> Self {
+        Self::Walk
+    }
+}
+
+/// Off-mesh link, the node of the script is the start of the link.
+#[derive(Visit, Reflect, Inspect, Debug, Clone)]
+pub struct NavigationLink {
+    pub kind: InheritableVariable<NavigationLinkKind>,
+
+    #[inspect(description = "The other end of the link.")]
+    pub end: InheritableVariable<Handle<Node>>,
+
+    #[inspect(description = "Whether the link can be traversed from the end to the start.")]
+    pub bidirectional: InheritableVariable<bool>,
+
+    #[inspect(description = "A door or an elevator that is used to traverse the link.")]
+    pub device: InheritableVariable<Handle<Node>>,
+
+    #[inspect(description = "Elevator floor at the start of the link.")]
+    pub start_floor: InheritableVariable<u32>,
+
+    #[inspect(description = "Elevator floor at the end of the link.")]
+    pub end_floor: InheritableVariable<u32>,
+
+    #[reflect(hidden)]
+    #[inspect(skip)]
+    #[visit(skip)]
+    start_navmesh: Option<usize>,
+
+    #[reflect(hidden)]
+    #[inspect(skip)]
+    #[visit(skip)]
+    end_navmesh: Option<usize>,
+}
+
+impl Default for NavigationLink {
+    fn default() -> Self {
+        Self {
+            kind: Default::default(),
+            end: Default::default(),
+            bidirectional: true.into(),
+            device: Default::default(),
+            start_floor: Default::default(),
+            end_floor: Default::default(),
+            start_navmesh: None,
+            end_navmesh: None,
+        }
+    }
+}
+
+/// Ends of a link in the direction of traversal.
+#[derive(Copy, Clone, Debug)]
+pub struct LinkEnds {
+    pub entrance: Vector3<f32>,
+    pub exit: Vector3<f32>,
+    pub entrance_floor: u32,
+    pub exit_floor: u32,
+}
+
+impl NavigationLink {
+    /// Returns ends of the link, `reversed` means traversal from the end to the start.
+    pub fn ends(&self, self_handle: Handle<Node>, graph: &Graph, reversed: bool) -> LinkEnds {
+        let start = graph[self_handle].global_position();
+        let end = graph
+            .try_get(*self.end)
+            .map_or(start, |end| end.global_position());
+
+        if reversed {
+            LinkEnds {
+                entrance: end,
+                exit: start,
+                entrance_floor: *self.end_floor,
+                exit_floor: *self.start_floor,
+            }
+        } else {
+            LinkEnds {
+                entrance: start,
+                exit: end,
+                entrance_floor: *self.start_floor,
+                exit_floor: *self.end_floor,
+            }
+        }
+    }
+
+    /// Returns indices of navmeshes connected by the link in every allowed direction.
+    fn directions(&self) -> impl Iterator<Item = (bool, usize, usize)> {
+        let forward = self.start_navmesh.zip(self.end_navmesh);
+        let backward = if *self.bidirectional {
+            self.end_navmesh.zip(self.start_navmesh)
+        } else {
+            None
+        };
+
+        forward
+            .map(|(a, b)| (false, a, b))
+            .into_iter()
+            .chain(backward.map(|(a, b)| (true, a, b)))
+    }
+}
+
+impl_component_provider!(NavigationLink);
+
+impl TypeUuidProvider for NavigationLink {
+    fn type_uuid() -> Uuid {
+        uuid!("0d5f4f7e-8a3b-4f0c-b1e2-3c9a6d2e7b58")
+    }
+}
+
+impl ScriptTrait for NavigationLink {
+    fn on_init(&mut self, ctx: &mut ScriptContext) {
+        current_level_mut(ctx.plugins)
+            .expect("Level must exist!")
+            .navigation_links
+            .push(ctx.handle);
+    }
+
+    fn on_start(&mut self, ctx: &mut ScriptContext) {
+        // Navmeshes are static, so it is enough to find them once.
+        let ends = self.ends(ctx.handle, &ctx.scene.graph, false);
+        self.start_navmesh = closest_navmesh(ctx.scene, ends.entrance).map(|(i, _)| i);
+        self.end_navmesh = closest_navmesh(ctx.scene, ends.exit).map(|(i, _)| i);
+    }
+
+    fn on_deinit(&mut self, ctx: &mut ScriptDeinitContext) {
+        if let Some(level) = current_level_mut(ctx.plugins) {
+            if let Some(position) = level
+                .navigation_links
+                .iter()
+                .position(|l| *l == ctx.node_handle)
+            {
+                level.navigation_links.remove(position);
+            }
+        }
+    }
+
+    fn id(&self) -> Uuid {
+        Self::type_uuid()
+    }
+}
+
+/// Returns index of a navmesh that has the closest vertex to the given point and the vertex
+/// position.
+pub fn closest_navmesh(scene: &Scene, point: Vector3<f32>) -> Option<(usize, Vector3<f32>)> {
+    scene
+        .navmeshes
+        .iter()
+        .enumerate()
+        .filter_map(|(index, navmesh)| {
+            navmesh
+                .query_closest(point)
+                .map(|i| (index, navmesh.vertices()[i].position))
+        })
+        .min_by(|(_, a), (_, b)| {
+            a.metric_distance(&point)
+                .partial_cmp(&b.metric_distance(&point))
+                .unwrap_or(std::cmp::Ordering::Equal)
+        })
+}
+
+/// Returns the closest navmesh vertex to the given point, or the point itself if there's no
+/// navmesh.
+pub fn closest_navmesh_point(scene: &Scene, point: Vector3<f32>) -> Vector3<f32> {
+    closest_navmesh(scene, point).map_or(point, |(_, position)| position)
+}
+
+/// Returns a random navmesh vertex around the center on the navmesh of the center.
+pub fn random_navmesh_point(
+    scene: &Scene,
+    center: Vector3<f32>,
+    radius: f32,
+) -> Option<Vector3<f32>> {
+    let (index, _) = closest_navmesh(scene, center)?;
+    let points = scene
+        .navmeshes
+        .iter()
+        .nth(index)?
+        .vertices()
+        .iter()
+        .map(|v| v.position)
+        .filter(|p| p.metric_distance(&center) <= radius)
+        .collect::<Vec<_>>();
+    points.choose(&mut thread_rng()).cloned()
+}
+
+pub fn navmesh_mut(scene: &mut Scene, index: usize) -> Option<&mut Navmesh> {
+    scene.navmeshes.iter_mut().nth(index)
+}
+
+/// Finds a route over links from one navmesh to another and returns the first link of the route
+/// and the direction in which it must be traversed.
+pub fn first_link_on_route(
+    graph: &Graph,
+    links: &[Handle<Node>],
+    from: usize,
+    to: usize,
+) -> Option<(Handle<Node>, bool)> {
+    let mut visited = vec![from];
+    let mut queue = VecDeque::new();
+    queue.push_back((from, None));
+
+    while let Some((navmesh, first_link)) = queue.pop_front() {
+        if navmesh == to {
+            return first_link;
+        }
+
+        for &handle in links {
+            if let Some(link) = graph
+                .try_get(handle)
+                .and_then(|n| n.try_get_script::<NavigationLink>())
+            {
+                for (reversed, start, end) in link.directions() {
+                    if start == navmesh && !visited.contains(&end) {
+                        visited.push(end);
+                        queue.push_back((end, first_link.or(Some((handle, reversed)))));
+                    }
+                }
+            }
+        }
+    }
+
+    None
+}
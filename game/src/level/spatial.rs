@@ -0,0 +1,180 @@
+//! Spatial index of actors, items, doors and other interactive objects of a level. The index is a
+//! uniform grid that is rebuilt by the level every frame, scripts use it to find objects around a
+//! point, inside bounds, a frustum or along a ray instead of checking every object of the level.
+
+use fyrox::{
+    core::{
+        algebra::Vector3,
+        math::{aabb::AxisAlignedBoundingBox, frustum::Frustum},
+        pool::Handle,
+    },
+    scene::node::Node,
+};
+use std::collections::HashMap;
+
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub enum SpatialCategory {
+    Actor,
+    Item,
+    Door,
+    /// Workbenches and other objects the player can use.
+    Interactable,
+}
+
+#[derive(Copy, Clone, Debug)]
+struct SpatialEntry {
+    node: Handle<Node>,
+    category: SpatialCategory,
+    position: Vector3<f32>,
+}
+
+#[derive(Default, Debug)]
+pub struct SpatialIndex {
+    entries: Vec<SpatialEntry>,
+    cells: HashMap<Vector3<i32>, Vec<usize>>,
+}
+
+impl SpatialIndex {
+    const CELL_SIZE: f32 = 4.0;
+
+    fn cell(position: Vector3<f32>) -> Vector3<i32> {
+        position.map(|c| (c / Self::CELL_SIZE).floor() as i32)
+    }
+
+    pub fn clear(&mut self) {
+        self.entries.clear();
+        self.cells.clear();
+    }
+
+    pub fn insert(
+        &mut self,
+        node: Handle<Node>,
+        category: SpatialCategory,
+        position: Vector3<f32>,
+    ) {
+        self.cells
+            .entry(Self::cell(position))
+            .or_default()
+            .push(self.entries.len());
+        self.entries.push(SpatialEntry {
+            node,
+            category,
+            position,
+        });
+    }
+
+    /// Returns entries of the category from the cells that intersect given bounds, the entries
+    /// must be filtered precisely by the caller.
+    fn candidates<'a>(
+        &'a self,
+        min: Vector3<f32>,
+        max: Vector3<f32>,
+        category: SpatialCategory,
+    ) -> Box<dyn Iterator<Item = &'a SpatialEntry> + 'a> {
+        let min = Self::cell(min);
+        let max = Self::cell(max);
+        let size = (max - min).map(|c| c as i64 + 1);
+
+        if size.x * size.y * size.z > self.entries.len() as i64 {
+            // It is faster to check every entry than every cell of huge bounds.
+            Box::new(self.entries.iter().filter(move |e| e.category == category))
+        } else {
+            Box::new(
+                (min.x..=max.x)
+                    .flat_map(move |x| {
+                        (min.y..=max.y)
+                            .flat_map(move |y| (min.z..=max.z).map(move |z| Vector3::new(x, y, z)))
+                    })
+                    .filter_map(move |cell| self.cells.get(&cell))
+                    .flatten()
+                    .map(move |&i| &self.entries[i])
+                    .filter(move |e| e.category == category),
+            )
+        }
+    }
+
+    /// Returns objects of the category within the radius around the center.
+    pub fn in_radius(
+        &self,
+        center: Vector3<f32>,
+        radius: f32,
+        category: SpatialCategory,
+    ) -> impl Iterator<Item = Handle<Node>> + '_ {
+        let extent = Vector3::repeat(radius);
+        self.candidates(center - extent, center + extent, category)
+            .filter(move |e| e.position.metric_distance(&center) <= radius)
+            .map(|e| e.node)
+    }
+
+    /// Returns the closest object of the category within the radius around the center.
+    pub fn closest(
+        &self,
+        center: Vector3<f32>,
+        radius: f32,
+        category: SpatialCategory,
+    ) -> Option<Handle<Node>> {
+        let extent = Vector3::repeat(radius);
+        self.candidates(center - extent, center + extent, category)
+            .map(|e| (e.node, e.position.metric_distance(&center)))
+            .filter(|(_, distance)| *distance <= radius)
+            .min_by(|(_, a), (_, b)| a.partial_cmp(b).unwrap_or(std::cmp::Ordering::Equal))
+            .map(|(node, _)| node)
+    }
+
+    /// Returns objects of the category inside the bounds.
+    pub fn in_bounds<'a>(
+        &'a self,
+        bounds: &'a AxisAlignedBoundingBox,
+        category: SpatialCategory,
+    ) -> impl Iterator<Item = Handle<Node>> + 'a {
+        self.candidates(bounds.min, bounds.max, category)
+            .filter(move |e| bounds.is_contains_point(e.position))
+            .map(|e| e.node)
+    }
+
+    /// Returns objects of the category inside the frustum.
+    pub fn in_frustum<'a>(
+        &'a self,
+        frustum: &'a Frustum,
+        category: SpatialCategory,
+    ) -> impl Iterator<Item = Handle<Node>> + 'a {
+        let bounds = AxisAlignedBoundingBox::from_points(&frustum.corners());
+        self.candidates(bounds.min, bounds.max, category)
+            .filter(move |e| frustum.is_contains_point(e.position))
+            .map(|e| e.node)
+    }
+
+    /// Returns objects of the category that are closer than the radius to the segment between
+    /// the points, sorted by distance from the beginning of the segment.
+    pub fn along_ray(
+        &self,
+        begin: Vector3<f32>,
+        end: Vector3<f32>,
+        radius: f32,
+        category: SpatialCategory,
+    ) -> Vec<Handle<Node>> {
+        let extent = Vector3::repeat(radius);
+        let min = begin.inf(&end) - extent;
+        let max = begin.sup(&end) + extent;
+
+        let segment = end - begin;
+        let length_sqr = segment.norm_squared().max(f32::EPSILON);
+
+        let mut hits = self
+            .candidates(min, max, category)
+            .filter_map(|e| {
+                let t = ((e.position - begin).dot(&segment) / length_sqr).clamp(0.0, 1.0);
+                let closest = begin + segment.scale(t);
+                if closest.metric_distance(&e.position) <= radius {
+                    Some((e.node, t))
+                } else {
+                    None
+                }
+            })
+            .collect::<Vec<_>>();
+
+        hits.sort_by(|(_, a), (_, b)| a.partial_cmp(b).unwrap_or(std::cmp::Ordering::Equal));
+
+        hits.into_iter().map(|(node, _)| node).collect()
+    }
+}
@@ -1,4 +1,10 @@
-use crate::{character::character_ref, current_level_ref, game_ref, message::Message};
+use crate::{
+    current_level_ref,
+    door::{Door, DoorLock},
+    game_ref,
+    level::{ambush::AmbushSpawner, spatial::SpatialCategory},
+    message::Message,
+};
 use fyrox::{
     core::{
         inspect::prelude::*,
@@ -7,7 +13,7 @@ use fyrox::{
         visitor::prelude::*,
     },
     impl_component_provider,
-    scene::node::TypeUuidProvider,
+    scene::node::{NodeHandle, TypeUuidProvider},
     script::{ScriptContext, ScriptTrait},
 };
 use strum_macros::{AsRefStr, EnumString, EnumVariantNames};
@@ -16,6 +22,13 @@ use strum_macros::{AsRefStr, EnumString, EnumVariantNames};
 pub enum TriggerKind {
     NextLevel,
     EndGame,
+    /// Locks the doors of the trigger, so they can't be opened even with a key (a quarantine
+    /// lockdown for example).
+    LockDoors,
+    /// Unlocks the doors of the trigger, including doors that were locked in the editor.
+    UnlockDoors,
+    /// Starts the ambushes of the trigger.
+    StartAmbush,
 }
 
 impl Default for TriggerKind {
@@ -27,6 +40,14 @@ impl Default for TriggerKind {
 #[derive(Visit, Reflect, Inspect, Debug, Default, Clone)]
 pub struct Trigger {
     kind: TriggerKind,
+
+    #[inspect(description = "Doors that are locked or unlocked by the trigger.")]
+    #[visit(optional)]
+    doors: Vec<NodeHandle>,
+
+    #[inspect(description = "Ambush spawners that are started by the trigger.")]
+    #[visit(optional)]
+    spawners: Vec<NodeHandle>,
 }
 
 impl_component_provider!(Trigger);
@@ -44,15 +65,45 @@ impl ScriptTrait for Trigger {
         let position = context.scene.graph[context.handle].global_position();
 
         if let Some(level) = current_level_ref(context.plugins) {
-            for actor in level.actors.iter() {
-                let actor_position =
-                    character_ref(*actor, &context.scene.graph).position(&context.scene.graph);
-
-                if actor_position.metric_distance(&position) < 1.0 {
-                    match self.kind {
-                        TriggerKind::NextLevel => game.message_sender.send(Message::LoadNextLevel),
-                        TriggerKind::EndGame => game.message_sender.send(Message::EndGame),
+            for actor in level
+                .spatial_index
+                .in_radius(position, 1.0, SpatialCategory::Actor)
+            {
+                match self.kind {
+                    TriggerKind::NextLevel => game.message_sender.send(Message::LoadNextLevel),
+                    TriggerKind::EndGame => game.message_sender.send(Message::EndGame),
+                    // Bots wandering around must not lock or unlock doors behind the player.
+                    TriggerKind::LockDoors | TriggerKind::UnlockDoors if actor == level.player => {
+                        for &door in self.doors.iter() {
+                            if let Some(door) = context
+                                .scene
+                                .graph
+                                .try_get_mut(*door)
+                                .and_then(|n| n.try_get_script_mut::<Door>())
+                            {
+                                if matches!(self.kind, TriggerKind::LockDoors) {
+                                    door.set_locked(DoorLock::Trigger, true);
+                                } else {
+                                    door.unlock();
+                                }
+                            }
+                        }
+                    }
+                    TriggerKind::StartAmbush if actor == level.player => {
+                        for &spawner in self.spawners.iter() {
+                            if let Some(spawner) = context
+                                .scene
+                                .graph
+                                .try_get_mut(*spawner)
+                                .and_then(|n| n.try_get_script_mut::<AmbushSpawner>())
+                            {
+                                spawner.activate();
+                            }
+                        }
                     }
+                    TriggerKind::LockDoors
+                    | TriggerKind::UnlockDoors
+                    | TriggerKind::StartAmbush => (),
                 }
             }
         }
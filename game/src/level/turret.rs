@@ -1,9 +1,11 @@
 use crate::{
     character::{character_ref, try_get_character_ref},
     current_level_ref,
-    sound::SoundManager,
+    faction::{Faction, FactionRelations},
+    level::spatial::{SpatialCategory, SpatialIndex},
+    sound::{Noise, NoiseKind, SoundManager},
     weapon::{definition::ShotEffect, projectile::Damage},
-    Player, Weapon,
+    Weapon,
 };
 use fyrox::core::variable::InheritableVariable;
 use fyrox::{
@@ -65,35 +67,6 @@ impl Default for ShootMode {
     }
 }
 
-#[derive(
-    Copy,
-    Clone,
-    Hash,
-    PartialOrd,
-    PartialEq,
-    Eq,
-    Ord,
-    Visit,
-    Inspect,
-    Reflect,
-    AsRefStr,
-    EnumString,
-    EnumVariantNames,
-    Debug,
-)]
-#[repr(u32)]
-pub enum Hostility {
-    Player,
-    Monsters,
-    All,
-}
-
-impl Default for Hostility {
-    fn default() -> Self {
-        Self::Player
-    }
-}
-
 #[derive(Visit, Reflect, Inspect, Debug, Clone)]
 pub struct Turret {
     model: Handle<Node>,
@@ -101,7 +74,8 @@ pub struct Turret {
     barrel_stand: Handle<Node>,
     barrels: Vec<Barrel>,
     shoot_mode: ShootMode,
-    hostility: Hostility,
+    #[visit(optional)]
+    faction: Faction,
     yaw: SmoothAngle,
     pitch: SmoothAngle,
     projector: Handle<Node>,
@@ -133,6 +107,12 @@ pub struct Turret {
     #[inspect(skip)]
     #[visit(skip)]
     frustum: Frustum,
+
+    /// Remaining time while turret is disabled (by EMP for example).
+    #[reflect(hidden)]
+    #[inspect(skip)]
+    #[visit(optional)]
+    disable_timer: f32,
 }
 
 impl Default for Turret {
@@ -148,7 +128,7 @@ impl Default for Turret {
             shoot_timer: Default::default(),
             barrel_index: Default::default(),
             frustum: Default::default(),
-            hostility: Default::default(),
+            faction: Faction::Security,
             yaw: SmoothAngle {
                 angle: 0.0,
                 target: 0.0,
@@ -162,6 +142,7 @@ impl Default for Turret {
             target_check_timer: 0.0,
             collider: Default::default(),
             shoot_interval: 0.2,
+            disable_timer: 0.0,
         }
     }
 }
@@ -178,13 +159,23 @@ impl ScriptTrait for Turret {
     fn on_update(&mut self, ctx: &mut ScriptContext) {
         let level_ref = current_level_ref(ctx.plugins).expect("Level must exist!");
 
+        if self.projector.is_some() {
+            ctx.scene.graph[self.projector].set_visibility(!self.is_disabled());
+        }
+
+        if self.is_disabled() {
+            self.disable_timer -= ctx.dt;
+            self.target = Default::default();
+            return;
+        }
+
         self.update_frustum(ctx.scene);
 
         self.shoot_timer -= ctx.dt;
         self.target_check_timer -= ctx.dt;
 
         if self.target_check_timer <= 0.0 {
-            self.select_target(ctx.scene, &level_ref.actors);
+            self.select_target(ctx.scene, &level_ref.spatial_index, &level_ref.factions);
             self.target_check_timer = 0.15;
         }
 
@@ -349,6 +340,13 @@ impl Barrel {
             1.0,
             3.0,
         );
+
+        sound_manager.emit_noise(Noise::new(
+            NoiseKind::Gunshot,
+            shot_position,
+            Noise::GUNSHOT_LOUDNESS,
+            owner_handle,
+        ));
     }
 
     fn update(&mut self, scene: &mut Scene) {
@@ -361,6 +359,14 @@ impl Barrel {
 }
 
 impl Turret {
+    pub fn disable(&mut self, duration: f32) {
+        self.disable_timer = self.disable_timer.max(duration);
+    }
+
+    pub fn is_disabled(&self) -> bool {
+        self.disable_timer > 0.0
+    }
+
     pub fn debug_draw(&self, context: &mut SceneDrawingContext) {
         context.draw_frustum(&self.frustum, Color::from_rgba(0, 200, 0, 255));
     }
@@ -379,7 +385,23 @@ impl Turret {
         self.frustum = Frustum::from(projection_matrix * view_matrix).unwrap();
     }
 
-    fn select_target(&mut self, scene: &Scene, actors: &[Handle<Node>]) {
+    pub fn faction(&self) -> Faction {
+        self.faction
+    }
+
+    /// Moves the turret to another faction (when it is hacked for example), current target is
+    /// forgotten.
+    pub fn set_faction(&mut self, faction: Faction) {
+        self.faction = faction;
+        self.target = Default::default();
+    }
+
+    fn select_target(
+        &mut self,
+        scene: &Scene,
+        spatial_index: &SpatialIndex,
+        factions: &FactionRelations,
+    ) {
         let self_position = scene.graph[self.model].global_position();
 
         if !scene.graph.is_valid_handle(self.target)
@@ -387,17 +409,15 @@ impl Turret {
         {
             let mut closest = Handle::NONE;
             let mut closest_distance = f32::MAX;
-            'target_loop: for &handle in actors.iter() {
+            let frustum = self.frustum.clone();
+            'target_loop: for handle in spatial_index.in_frustum(&frustum, SpatialCategory::Actor) {
                 let actor = character_ref(handle, &scene.graph);
 
                 if actor.is_dead() {
                     continue 'target_loop;
                 }
 
-                let is_player = scene.graph[handle].has_script::<Player>();
-                if self.hostility == Hostility::Player && !is_player
-                    || self.hostility == Hostility::Monsters && is_player
-                {
+                if !factions.is_hostile(self.faction, actor.faction) {
                     continue;
                 }
 
@@ -405,10 +425,6 @@ impl Turret {
 
                 let actor_position = actor.position(&scene.graph);
 
-                if !self.frustum.is_contains_point(actor_position) {
-                    continue 'target_loop;
-                }
-
                 let ray = Ray::from_two_points(actor_position, self_position);
                 scene.graph.physics.cast_ray(
                     RayCastOptions {
@@ -1,12 +1,15 @@
 #![allow(clippy::too_many_arguments)]
 
+pub mod armor;
 pub mod bot;
 pub mod character;
 pub mod config;
 pub mod control_scheme;
+pub mod damage;
 pub mod door;
 pub mod effects;
 pub mod elevator;
+pub mod faction;
 pub mod gui;
 pub mod inventory;
 pub mod level;
@@ -20,20 +23,25 @@ pub mod sound;
 pub mod ui_container;
 pub mod utils;
 pub mod weapon;
+pub mod workbench;
 
 use crate::{
-    bot::Bot,
+    bot::{
+        patrol::{PatrolRoute, Waypoint},
+        Bot,
+    },
     config::{Config, SoundConfig},
     control_scheme::ControlScheme,
     door::{ui::DoorUiContainer, Door},
     elevator::{call_button::CallButton, ui::CallButtonUiContainer, Elevator},
     gui::{
-        inventory::InventoryInterface, item_display::ItemDisplay, journal::JournalDisplay,
-        weapon_display::WeaponDisplay, DeathScreen, FinalScreen,
+        boss_health_bar::BossHealthBar, inventory::InventoryInterface, item_display::ItemDisplay,
+        journal::JournalDisplay, weapon_display::WeaponDisplay, DeathScreen, FinalScreen,
     },
     level::{
-        death_zone::DeathZone, decal::Decal, item::Item, spawn::CharacterSpawnPoint,
-        turret::Turret, Level,
+        ambush::AmbushSpawner, area_effect::AreaEffect, benchmark::BotBenchmark,
+        corpse::CorpseProp, death_zone::DeathZone, decal::Decal, director::DirectorSpawnPoint,
+        item::Item, navigation::NavigationLink, spawn::CharacterSpawnPoint, turret::Turret, Level,
     },
     light::AnimatedLight,
     loading_screen::LoadingScreen,
@@ -42,6 +50,7 @@ use crate::{
     player::{camera::CameraController, Player},
     utils::use_hrtf,
     weapon::Weapon,
+    workbench::{ui::WorkbenchUiContainer, Workbench},
 };
 use fyrox::{
     core::{
@@ -101,12 +110,14 @@ pub struct Game {
     loading_screen: LoadingScreen,
     death_screen: DeathScreen,
     final_screen: FinalScreen,
+    boss_health_bar: BossHealthBar,
     weapon_display: WeaponDisplay,
     inventory_interface: InventoryInterface,
     item_display: ItemDisplay,
     journal_display: JournalDisplay,
     door_ui_container: DoorUiContainer,
     call_button_ui_container: CallButtonUiContainer,
+    workbench_ui_container: WorkbenchUiContainer,
     // We're storing sound config separately because we can adjust sound
     // setting in the options but don't have a level loaded. This field
     // is data-model for options menu.
@@ -114,6 +125,8 @@ pub struct Game {
     update_duration: Duration,
     show_debug_info: bool,
     smaller_font: SharedFont,
+    // Whether bot benchmark should be added to the level that is being loaded.
+    start_bot_benchmark: bool,
 }
 
 pub fn game_ref(plugins: &[Box<dyn Plugin>]) -> &Game {
@@ -281,6 +294,7 @@ impl Game {
                 font.clone(),
                 message_sender.clone(),
             ),
+            boss_health_bar: BossHealthBar::new(context.user_interface, font.clone()),
             final_screen: FinalScreen::new(context.user_interface, font, message_sender.clone()),
             control_scheme,
             debug_text: Handle::NONE,
@@ -298,6 +312,8 @@ impl Game {
             update_duration: Default::default(),
             door_ui_container: Default::default(),
             call_button_ui_container: Default::default(),
+            workbench_ui_container: Default::default(),
+            start_bot_benchmark: false,
         };
 
         game.create_debug_ui(&mut context);
@@ -359,6 +375,7 @@ impl Game {
 
         self.door_ui_container.render(context.renderer);
         self.call_button_ui_container.render(context.renderer);
+        self.workbench_ui_container.render(context.renderer);
     }
 
     fn debug_render(&mut self, context: &mut PluginContext) {
@@ -419,6 +436,7 @@ impl Game {
         self.final_screen.set_visible(context.user_interface, false);
         self.door_ui_container.clear();
         self.call_button_ui_container.clear();
+        self.workbench_ui_container.clear();
 
         // Set control scheme for player.
         if let Some(level) = &mut self.level {
@@ -434,6 +452,7 @@ impl Game {
         if let Some(ref mut level) = self.level.take() {
             self.door_ui_container.clear();
             self.call_button_ui_container.clear();
+            self.workbench_ui_container.clear();
             level.destroy(context);
             Log::info("Current level destroyed!");
         }
@@ -441,6 +460,7 @@ impl Game {
 
     pub fn load_level<S: AsRef<str>>(&mut self, map: S, context: &mut PluginContext) {
         self.destroy_level(context);
+        self.start_bot_benchmark = false;
 
         let ctx = Arc::new(Mutex::new(LoadContext { level: None }));
 
@@ -506,7 +526,11 @@ impl Game {
 
         if let Some(load_context) = self.load_context.clone() {
             if let Some(mut load_context) = load_context.try_lock() {
-                if let Some((mut level, scene)) = load_context.level.take() {
+                if let Some((mut level, mut scene)) = load_context.level.take() {
+                    if std::mem::take(&mut self.start_bot_benchmark) {
+                        BotBenchmark::add_to_scene(&mut scene);
+                    }
+
                     level.scene = ctx.scenes.add(scene);
 
                     self.level = Some(level);
@@ -531,7 +555,12 @@ impl Game {
         }
 
         if let Some(ref mut level) = self.level {
-            ctx.scenes[level.scene].enabled = !self.menu.is_visible(ctx.user_interface);
+            let enabled = !self.menu.is_visible(ctx.user_interface);
+            ctx.scenes[level.scene].enabled = enabled;
+            if enabled {
+                level.sound_manager.update(ctx.dt);
+                level.update(ctx);
+            }
         }
 
         self.menu.scene.update(ctx, ctx.dt);
@@ -540,6 +569,18 @@ impl Game {
         self.item_display.update(ctx.dt);
         self.door_ui_container.update(ctx.dt);
         self.call_button_ui_container.update(ctx.dt);
+        self.workbench_ui_container.update(ctx.dt);
+
+        let boss = self.level.as_ref().and_then(|level| {
+            let graph = &ctx.scenes[level.scene].graph;
+            level.actors.iter().find_map(|&actor| {
+                graph
+                    .try_get(actor)
+                    .and_then(|node| node.try_get_script::<Bot>())
+                    .and_then(|bot| bot.boss_status())
+            })
+        });
+        self.boss_health_bar.update(ctx.user_interface, boss);
 
         self.handle_messages(ctx);
 
@@ -561,6 +602,10 @@ impl Game {
                 Message::LoadTestbed => {
                     self.load_level(Level::TESTBED_PATH, context);
                 }
+                Message::LoadBotBenchmark => {
+                    self.load_level(Level::TESTBED_PATH, context);
+                    self.start_bot_benchmark = true;
+                }
                 Message::SaveGame => match self.save_game(context) {
                     Ok(_) => Log::info("Successfully saved"),
                     Err(e) => Log::err(format!("Failed to make a save, reason: {}", e)),
@@ -586,8 +631,10 @@ impl Game {
                     self.destroy_level(context);
                     self.running = false;
                 }
-                Message::EndMatch => {
+                Message::EndMatch { killed_by } => {
                     self.destroy_level(context);
+                    self.death_screen
+                        .set_killed_by(context.user_interface, killed_by);
                     self.death_screen.set_visible(context.user_interface, true);
                     self.menu.sync_to_model(context, false);
                 }
@@ -700,6 +747,15 @@ impl Game {
             )
             .unwrap();
 
+            if let Some(level) = self.level.as_ref() {
+                write!(
+                    self.debug_string,
+                    "\n{}\n{}",
+                    level.director, level.bot_statistics
+                )
+                .unwrap();
+            }
+
             context.user_interface.send_message(TextMessage::text(
                 self.debug_text,
                 MessageDirection::ToWidget,
@@ -776,6 +832,7 @@ impl PluginConstructor for GameConstructor {
             .add::<Weapon>("Weapon")
             .add::<Item>("Item")
             .add::<Decal>("Decal")
+            .add::<AreaEffect>("Area Effect")
             .add::<Player>("Player")
             .add::<CameraController>("Camera Controller")
             .add::<Bot>("Bot")
@@ -783,7 +840,15 @@ impl PluginConstructor for GameConstructor {
             .add::<DeathZone>("Death Zone")
             .add::<AnimatedLight>("Animated Light")
             .add::<Elevator>("Elevator")
-            .add::<CallButton>("Call Button");
+            .add::<CallButton>("Call Button")
+            .add::<Workbench>("Workbench")
+            .add::<PatrolRoute>("Patrol Route")
+            .add::<Waypoint>("Waypoint")
+            .add::<AmbushSpawner>("Ambush Spawner")
+            .add::<NavigationLink>("Navigation Link")
+            .add::<CorpseProp>("Corpse Prop")
+            .add::<DirectorSpawnPoint>("Director Spawn Point")
+            .add::<BotBenchmark>("Bot Benchmark");
     }
 
     fn create_instance(
@@ -16,7 +16,7 @@ use fyrox::{
         ttf::SharedFont,
         widget::{WidgetBuilder, WidgetMessage},
         window::{WindowBuilder, WindowMessage, WindowTitle},
-        HorizontalAlignment, Thickness, UiNode, UserInterface,
+        HorizontalAlignment, Thickness, UiNode, UserInterface, VerticalAlignment,
     },
     plugin::PluginContext,
     scene::{
@@ -32,6 +32,7 @@ pub struct Menu {
     sender: MessageSender,
     root: Handle<UiNode>,
     btn_load_test_bed: Handle<UiNode>,
+    btn_bot_benchmark: Handle<UiNode>,
     btn_new_game: Handle<UiNode>,
     btn_save_game: Handle<UiNode>,
     btn_settings: Handle<UiNode>,
@@ -124,6 +125,7 @@ impl Menu {
         let ctx = &mut context.user_interface.build_ctx();
 
         let btn_load_test_bed;
+        let btn_bot_benchmark;
         let btn_new_game;
         let btn_settings;
         let btn_save_game;
@@ -147,6 +149,21 @@ impl Menu {
                     .build(ctx);
                     btn_load_test_bed
                 })
+                .with_child({
+                    btn_bot_benchmark = ButtonBuilder::new(
+                        WidgetBuilder::new()
+                            .with_width(300.0)
+                            .with_height(64.0)
+                            .with_horizontal_alignment(HorizontalAlignment::Center)
+                            .with_vertical_alignment(VerticalAlignment::Top)
+                            .on_column(1)
+                            .on_row(1)
+                            .with_margin(Thickness::uniform(4.0)),
+                    )
+                    .with_text_and_font("Bot Benchmark", font.clone())
+                    .build(ctx);
+                    btn_bot_benchmark
+                })
                 .with_child(
                     WindowBuilder::new(WidgetBuilder::new().on_row(1).on_column(0))
                         .can_resize(false)
@@ -241,6 +258,7 @@ impl Menu {
             btn_load_game,
             btn_quit_game,
             btn_load_test_bed,
+            btn_bot_benchmark,
             options_menu: OptionsMenu::new(
                 context,
                 control_scheme,
@@ -327,6 +345,8 @@ impl Menu {
                 self.sender.send(Message::QuitGame);
             } else if message.destination() == self.btn_load_test_bed {
                 self.sender.send(Message::LoadTestbed);
+            } else if message.destination() == self.btn_bot_benchmark {
+                self.sender.send(Message::LoadBotBenchmark);
             } else if message.destination() == self.btn_settings {
                 let is_visible = engine
                     .user_interface
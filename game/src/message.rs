@@ -3,10 +3,8 @@
 //! required entity. This is very effective decoupling mechanism that works perfectly with
 //! strict ownership rules of Rust.
 
-use fyrox::{
-    core::{algebra::Vector3, pool::Handle},
-    scene::node::Node,
-};
+use crate::damage::DamageSource;
+use fyrox::core::algebra::Vector3;
 use std::path::PathBuf;
 
 #[derive(Debug)]
@@ -20,7 +18,7 @@ pub enum Message {
         radius: f32,
         center: Vector3<f32>,
         /// Damage initiator
-        who: Handle<Node>,
+        source: DamageSource,
         critical_shot_probability: f32,
     },
     /// Save game state to a file. TODO: Add filename field.
@@ -29,10 +27,15 @@ pub enum Message {
     LoadGame,
     StartNewGame,
     LoadTestbed,
+    /// Loads the testbed and adds bot benchmark to it.
+    LoadBotBenchmark,
     QuitGame,
     LoadNextLevel,
     ToggleMainMenu,
-    EndMatch,
+    EndMatch {
+        /// Description of whatever has killed the player.
+        killed_by: String,
+    },
     EndGame,
     SyncInventory,
     SyncJournal,
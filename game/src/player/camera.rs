@@ -30,6 +30,8 @@ pub struct CameraController {
     shake_offset: Vector3<f32>,
     target_shake_offset: Vector3<f32>,
     shake_timer: f32,
+    #[visit(optional)]
+    shake_strength: f32,
     #[visit(skip)]
     #[reflect(hidden)]
     #[inspect(skip)]
@@ -41,8 +43,11 @@ impl CameraController {
         self.camera
     }
 
-    pub fn request_shake_camera(&mut self) {
-        self.shake_timer = 0.24;
+    /// Shakes the camera, both duration and amplitude of the shake are scaled by `strength`,
+    /// 1.0 is a light shake from a shot.
+    pub fn request_shake_camera(&mut self, strength: f32) {
+        self.shake_timer = self.shake_timer.max(0.24 * strength);
+        self.shake_strength = self.shake_strength.max(strength);
     }
 
     fn check_occlusion(&mut self, owner_collider: Handle<Node>, scene: &mut Scene) {
@@ -79,8 +84,9 @@ impl CameraController {
     }
 
     fn update_shake(&mut self, dt: f32) {
-        let xy_range = -0.027..0.027;
-        let z_range = 0.01..0.05;
+        let strength = self.shake_strength.max(f32::EPSILON);
+        let xy_range = (-0.027 * strength)..(0.027 * strength);
+        let z_range = (0.01 * strength)..(0.05 * strength);
         if self.shake_timer > 0.0 {
             self.shake_timer -= dt;
             let mut rnd = rand::thread_rng();
@@ -91,6 +97,7 @@ impl CameraController {
             );
         } else {
             self.shake_timer = 0.0;
+            self.shake_strength = 0.0;
             self.target_shake_offset = Vector3::new(0.0, 0.0, 0.0);
         }
         self.shake_offset.follow(&self.target_shake_offset, 0.5);
@@ -0,0 +1,112 @@
+//! Dodge is a short burst of movement that makes the player immune to melee attacks for a moment.
+//! Every dodge costs stamina, stamina regenerates over time.
+
+use fyrox::core::{algebra::Vector3, visitor::prelude::*};
+
+#[derive(Copy, Clone, PartialEq, Eq, Debug, Visit)]
+#[repr(u32)]
+pub enum DodgeDirection {
+    Forward = 0,
+    Backward = 1,
+    Left = 2,
+    Right = 3,
+}
+
+impl Default for DodgeDirection {
+    fn default() -> Self {
+        Self::Backward
+    }
+}
+
+#[derive(Visit, Debug, Clone)]
+pub struct Dodge {
+    stamina: f32,
+    /// Time left until the end of the dodge.
+    timer: f32,
+    /// Time left until the next dodge is possible.
+    cooldown: f32,
+    /// Horizontal direction of the dodge in world space.
+    velocity: Vector3<f32>,
+    direction: DodgeDirection,
+}
+
+impl Default for Dodge {
+    fn default() -> Self {
+        Self {
+            stamina: Self::MAX_STAMINA,
+            timer: 0.0,
+            cooldown: 0.0,
+            velocity: Default::default(),
+            direction: Default::default(),
+        }
+    }
+}
+
+impl Dodge {
+    pub const MAX_STAMINA: f32 = 100.0;
+    const STAMINA_COST: f32 = 30.0;
+    /// Stamina per second.
+    const STAMINA_REGENERATION: f32 = 20.0;
+    const DURATION: f32 = 0.35;
+    /// Time since the beginning of a dodge during which melee attacks can't hit the player.
+    const INVULNERABILITY_TIME: f32 = 0.25;
+    /// Time between dodges, stamina does not regenerate until the dodge has cooled down.
+    const COOLDOWN: f32 = 0.6;
+    /// Speed (m/s) of the dodge.
+    const SPEED: f32 = 9.0;
+
+    /// Starts a dodge in the given direction (world space) if there is enough stamina and the
+    /// previous dodge has cooled down. Returns true if the dodge has started.
+    pub fn try_begin(&mut self, velocity: Vector3<f32>, direction: DodgeDirection) -> bool {
+        let velocity = match Vector3::new(velocity.x, 0.0, velocity.z).try_normalize(f32::EPSILON) {
+            Some(velocity) => velocity,
+            None => return false,
+        };
+
+        if self.cooldown > 0.0 || self.stamina < Self::STAMINA_COST {
+            return false;
+        }
+
+        self.stamina -= Self::STAMINA_COST;
+        self.timer = Self::DURATION;
+        self.cooldown = Self::COOLDOWN;
+        self.velocity = velocity;
+        self.direction = direction;
+
+        true
+    }
+
+    pub fn update(&mut self, dt: f32) {
+        self.timer = (self.timer - dt).max(0.0);
+        self.cooldown = (self.cooldown - dt).max(0.0);
+
+        if self.cooldown <= 0.0 {
+            self.stamina = (self.stamina + Self::STAMINA_REGENERATION * dt).min(Self::MAX_STAMINA);
+        }
+    }
+
+    pub fn is_dodging(&self) -> bool {
+        self.timer > 0.0
+    }
+
+    pub fn is_invulnerable(&self) -> bool {
+        Self::DURATION - self.timer < Self::INVULNERABILITY_TIME && self.is_dodging()
+    }
+
+    /// Horizontal velocity (m/s) of the dodge, it is zero when the player is not dodging.
+    pub fn velocity(&self) -> Vector3<f32> {
+        if self.is_dodging() {
+            self.velocity.scale(Self::SPEED)
+        } else {
+            Default::default()
+        }
+    }
+
+    pub fn direction(&self) -> DodgeDirection {
+        self.direction
+    }
+
+    pub fn stamina(&self) -> f32 {
+        self.stamina
+    }
+}
@@ -0,0 +1,172 @@
+//! Feedback on incoming damage: directional indicators around the player rig and low-health
+//! effects.
+
+use crate::{damage::DamageSource, sound::SoundManager};
+use fyrox::{
+    core::{algebra::Vector3, color::Color, pool::Handle, visitor::prelude::*},
+    engine::resource_manager::ResourceManager,
+    scene::{base::BaseBuilder, graph::Graph, node::Node, sprite::SpriteBuilder},
+};
+
+#[derive(Debug, Clone)]
+struct DamageRecord {
+    source: DamageSource,
+    /// Horizontal direction from the player to the source of damage.
+    direction: Vector3<f32>,
+    intensity: f32,
+    time_remaining: f32,
+}
+
+/// Damage of a source summed up over a short window, continuous damage (fire for example) comes
+/// in small portions every frame.
+#[derive(Debug, Clone)]
+struct PendingDamage {
+    source: DamageSource,
+    amount: f32,
+    time_remaining: f32,
+}
+
+#[derive(Visit, Default, Debug, Clone)]
+pub struct HitFeedback {
+    indicators: Vec<Handle<Node>>,
+    #[visit(skip)]
+    history: Vec<DamageRecord>,
+    #[visit(skip)]
+    pending: Vec<PendingDamage>,
+    #[visit(skip)]
+    heartbeat_timer: f32,
+}
+
+impl HitFeedback {
+    /// Max amount of simultaneously shown damage directions.
+    const MAX_INDICATORS: usize = 4;
+    const INDICATOR_TIME: f32 = 1.5;
+    /// Time during which damage of a source is summed up.
+    const DAMAGE_WINDOW: f32 = 0.5;
+    /// Summed damage of a source that is needed to show feedback.
+    const DAMAGE_THRESHOLD: f32 = 5.0;
+    /// Health fraction below which low health effects are active.
+    const LOW_HEALTH_THRESHOLD: f32 = 0.3;
+    const HEARTBEAT_SOUND: &'static str = "data/sounds/heartbeat.wav";
+
+    /// Creates indicators if they weren't created before (they're saved with the scene).
+    pub fn init(&mut self, graph: &mut Graph, resource_manager: &ResourceManager) {
+        if self.indicators.iter().any(|i| graph.try_get(*i).is_none()) {
+            self.indicators.clear();
+        }
+
+        while self.indicators.len() < Self::MAX_INDICATORS {
+            let indicator = SpriteBuilder::new(BaseBuilder::new().with_visibility(false))
+                .with_texture(resource_manager.request_texture("data/particles/circle_05.png"))
+                .with_size(0.08)
+                .build(graph);
+            self.indicators.push(indicator);
+        }
+    }
+
+    /// Sums up damage of the source and shows its direction (if known) once the sum passes the
+    /// threshold. Returns the summed damage when the feedback is shown, it is `None` while the
+    /// damage is too small to be noticed.
+    pub fn register_damage(
+        &mut self,
+        source: DamageSource,
+        direction: Option<Vector3<f32>>,
+        amount: f32,
+    ) -> Option<f32> {
+        let index = match self.pending.iter().position(|p| p.source == source) {
+            Some(index) => index,
+            None => {
+                self.pending.push(PendingDamage {
+                    source,
+                    amount: 0.0,
+                    time_remaining: Self::DAMAGE_WINDOW,
+                });
+                self.pending.len() - 1
+            }
+        };
+
+        self.pending[index].amount += amount;
+        if self.pending[index].amount < Self::DAMAGE_THRESHOLD {
+            return None;
+        }
+        let amount = self.pending.remove(index).amount;
+
+        if let Some(direction) =
+            direction.and_then(|d| Vector3::new(d.x, 0.0, d.z).try_normalize(f32::EPSILON))
+        {
+            // A source that keeps hurting the player refreshes its own indicator.
+            if let Some(index) = self.history.iter().position(|r| r.source == source) {
+                self.history.remove(index);
+            } else if self.history.len() >= Self::MAX_INDICATORS {
+                self.history.remove(0);
+            }
+
+            self.history.push(DamageRecord {
+                source,
+                direction,
+                intensity: (amount / 25.0).clamp(0.3, 1.0),
+                time_remaining: Self::INDICATOR_TIME,
+            });
+        }
+
+        Some(amount)
+    }
+
+    pub fn update(
+        &mut self,
+        graph: &mut Graph,
+        position: Vector3<f32>,
+        health_fraction: f32,
+        sound_manager: &SoundManager,
+        dt: f32,
+    ) {
+        for record in self.history.iter_mut() {
+            record.time_remaining -= dt;
+        }
+        self.history.retain(|r| r.time_remaining > 0.0);
+
+        for pending in self.pending.iter_mut() {
+            pending.time_remaining -= dt;
+        }
+        self.pending.retain(|p| p.time_remaining > 0.0);
+
+        for (i, &indicator) in self.indicators.iter().enumerate() {
+            if let Some(node) = graph.try_get_mut(indicator) {
+                if let Some(record) = self.history.get(i) {
+                    let alpha =
+                        255.0 * record.intensity * record.time_remaining / Self::INDICATOR_TIME;
+                    node.set_visibility(true);
+                    node.local_transform_mut().set_position(
+                        position + Vector3::new(0.0, 1.2, 0.0) + record.direction.scale(0.5),
+                    );
+                    node.as_sprite_mut()
+                        .set_color(Color::from_rgba(255, 0, 0, alpha as u8));
+                } else {
+                    node.set_visibility(false);
+                }
+            }
+        }
+
+        if health_fraction > 0.0 && health_fraction < Self::LOW_HEALTH_THRESHOLD {
+            self.heartbeat_timer -= dt;
+            if self.heartbeat_timer <= 0.0 {
+                // The lower the health, the faster the heart beats.
+                self.heartbeat_timer = 0.5 + 0.7 * health_fraction / Self::LOW_HEALTH_THRESHOLD;
+                sound_manager.play_sound(graph, Self::HEARTBEAT_SOUND, position, 0.6, 1.0, 1.0);
+            }
+        } else {
+            self.heartbeat_timer = 0.0;
+        }
+    }
+
+    /// Desaturates given color when health is low, used as a hint on the rig.
+    pub fn low_health_tint(&self, color: Color, health_fraction: f32) -> Color {
+        if health_fraction < Self::LOW_HEALTH_THRESHOLD {
+            let k = 1.0 - health_fraction.max(0.0) / Self::LOW_HEALTH_THRESHOLD;
+            let grey = ((color.r as u32 + color.g as u32 + color.b as u32) / 3) as u8;
+            color.lerp(Color::from_rgba(grey, grey, grey, color.a), k)
+        } else {
+            color
+        }
+    }
+}
@@ -0,0 +1,217 @@
+//! Kinesis allows the player to grab dynamic rigid bodies, carry them in front of the camera and
+//! throw them. Thrown objects hurt actors they hit at high speed.
+
+use crate::{
+    character::{try_get_character_mut, try_get_character_ref, CharacterCommand},
+    damage::DamageSource,
+    weapon::{projectile::DamageKind, Weapon},
+};
+use fyrox::{
+    core::{algebra::Vector3, pool::Handle, visitor::prelude::*},
+    scene::{
+        collider::Collider,
+        graph::Graph,
+        node::Node,
+        rigidbody::{RigidBody, RigidBodyType},
+    },
+};
+
+#[derive(Visit, Default, Debug, Clone)]
+struct ThrownObject {
+    body: Handle<Node>,
+    lifetime: f32,
+}
+
+#[derive(Visit, Default, Debug, Clone)]
+pub struct Kinesis {
+    held_body: Handle<Node>,
+    thrown: Vec<ThrownObject>,
+}
+
+impl Kinesis {
+    /// Max distance at which an object can be grabbed.
+    pub const RANGE: f32 = 8.0;
+    /// Distance from the camera at which an object is being held.
+    pub const HOLD_DISTANCE: f32 = 2.0;
+    /// Heavier objects can't be lifted.
+    const MAX_MASS: f32 = 200.0;
+    /// Stiffness of the spring that pulls held object to the hold point.
+    const STIFFNESS: f32 = 10.0;
+    const MAX_HOLD_SPEED: f32 = 15.0;
+    const THROW_SPEED: f32 = 20.0;
+    /// Thrown objects stop being dangerous after this time.
+    const THROWN_LIFETIME: f32 = 3.0;
+    /// Minimal speed at which thrown object starts to hurt.
+    const MIN_DAMAGE_SPEED: f32 = 5.0;
+    const DAMAGE_PER_IMPULSE: f32 = 0.2;
+
+    pub fn is_holding(&self) -> bool {
+        self.held_body.is_some()
+    }
+
+    /// Tries to grab a dynamic rigid body along the given ray. Returns true if an object was
+    /// grabbed.
+    pub fn try_grab(
+        &mut self,
+        begin: Vector3<f32>,
+        end: Vector3<f32>,
+        owner: Handle<Node>,
+        actors: &[Handle<Node>],
+        graph: &mut Graph,
+    ) -> bool {
+        if let Some(hit) = Weapon::ray_hit(begin, end, owner, actors, graph, Default::default()) {
+            // Actors can't be grabbed.
+            if hit.actor.is_some() {
+                return false;
+            }
+
+            let body_handle = graph[hit.collider].parent();
+            if let Some(body) = graph
+                .try_get_mut(body_handle)
+                .and_then(|n| n.cast_mut::<RigidBody>())
+            {
+                if body.body_type() == RigidBodyType::Dynamic && body.mass() <= Self::MAX_MASS {
+                    body.wake_up();
+                    self.held_body = body_handle;
+                    self.thrown.retain(|t| t.body != body_handle);
+                    return true;
+                }
+            }
+        }
+
+        false
+    }
+
+    pub fn release(&mut self) {
+        self.held_body = Handle::NONE;
+    }
+
+    /// Throws held object in the given direction.
+    pub fn throw(&mut self, graph: &mut Graph, direction: Vector3<f32>) {
+        if let Some(body) = graph
+            .try_get_mut(self.held_body)
+            .and_then(|n| n.cast_mut::<RigidBody>())
+        {
+            body.set_lin_vel(
+                direction
+                    .try_normalize(f32::EPSILON)
+                    .unwrap_or_default()
+                    .scale(Self::THROW_SPEED),
+            );
+            body.wake_up();
+
+            self.thrown.push(ThrownObject {
+                body: self.held_body,
+                lifetime: Self::THROWN_LIFETIME,
+            });
+        }
+
+        self.release();
+    }
+
+    pub fn update(
+        &mut self,
+        graph: &mut Graph,
+        hold_point: Vector3<f32>,
+        owner: Handle<Node>,
+        actors: &[Handle<Node>],
+        dt: f32,
+    ) {
+        // Held object is moved by velocity, not by position, so it still collides with
+        // everything around.
+        if let Some(body) = graph
+            .try_get_mut(self.held_body)
+            .and_then(|n| n.cast_mut::<RigidBody>())
+        {
+            let offset = hold_point - body.global_position();
+            let mut velocity = offset.scale(Self::STIFFNESS);
+            if velocity.norm() > Self::MAX_HOLD_SPEED {
+                velocity = velocity.normalize().scale(Self::MAX_HOLD_SPEED);
+            }
+            body.set_lin_vel(velocity);
+            body.set_ang_vel(body.ang_vel().scale(0.9));
+            body.wake_up();
+        } else {
+            // Body was destroyed.
+            self.held_body = Handle::NONE;
+        }
+
+        let mut i = 0;
+        while i < self.thrown.len() {
+            let thrown = &mut self.thrown[i];
+            thrown.lifetime -= dt;
+
+            let hit =
+                thrown.lifetime > 0.0 && Self::check_thrown_hit(graph, thrown.body, owner, actors);
+
+            if hit || thrown.lifetime <= 0.0 || graph.try_get(thrown.body).is_none() {
+                self.thrown.remove(i);
+            } else {
+                i += 1;
+            }
+        }
+    }
+
+    /// Checks whether thrown object has hit an actor at speed and damages the actor. Returns
+    /// true if there was a hit.
+    fn check_thrown_hit(
+        graph: &mut Graph,
+        body_handle: Handle<Node>,
+        owner: Handle<Node>,
+        actors: &[Handle<Node>],
+    ) -> bool {
+        let (speed, mass, colliders) = match graph
+            .try_get(body_handle)
+            .and_then(|n| n.cast::<RigidBody>())
+        {
+            Some(body) => (body.lin_vel().norm(), body.mass(), body.children().to_vec()),
+            None => return false,
+        };
+
+        if speed < Self::MIN_DAMAGE_SPEED {
+            return false;
+        }
+
+        let mut victim = Handle::NONE;
+        'collider_loop: for collider_handle in colliders {
+            if let Some(collider) = graph
+                .try_get(collider_handle)
+                .and_then(|n| n.cast::<Collider>())
+            {
+                for contact in collider.contacts(&graph.physics) {
+                    if !contact.has_any_active_contact {
+                        continue;
+                    }
+
+                    let other = if contact.collider1 == collider_handle {
+                        contact.collider2
+                    } else {
+                        contact.collider1
+                    };
+
+                    if let Some(&actor) = actors.iter().find(|&&a| {
+                        a != owner
+                            && try_get_character_ref(a, graph)
+                                .map_or(false, |c| c.capsule_collider == other)
+                    }) {
+                        victim = actor;
+                        break 'collider_loop;
+                    }
+                }
+            }
+        }
+
+        if let Some(character) = try_get_character_mut(victim, graph) {
+            character.push_command(CharacterCommand::Damage {
+                source: DamageSource::Actor(owner),
+                hitbox: None,
+                amount: speed * mass * Self::DAMAGE_PER_IMPULSE,
+                kind: DamageKind::Point,
+                critical_shot_probability: 0.0,
+            });
+            true
+        } else {
+            false
+        }
+    }
+}
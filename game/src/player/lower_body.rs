@@ -1,13 +1,17 @@
 use crate::{
     character::Character,
-    player::{make_hit_reaction_state, upper_body::CombatWeaponKind, HitReactionStateDefinition},
-    sound::SoundManager,
+    player::{
+        dodge::DodgeDirection, make_hit_reaction_state, upper_body::CombatWeaponKind,
+        HitReactionStateDefinition,
+    },
+    sound::{Noise, SoundManager},
     utils::create_play_animation_state,
 };
 use fyrox::{
     animation::{
         machine::{
-            node::blend::BlendPose, Machine, Parameter, PoseNode, PoseWeight, State, Transition,
+            node::blend::{BlendPose, IndexedBlendInput},
+            Machine, Parameter, PoseNode, PoseWeight, State, Transition,
         },
         Animation, AnimationSignal,
     },
@@ -60,6 +64,39 @@ fn make_walk_state(
     }
 }
 
+struct DodgeStateDefinition {
+    state: Handle<State>,
+    animations: [Handle<Animation>; 4],
+}
+
+/// Dodge reuses locomotion animations (played faster) in the direction of the dodge, the order of
+/// resources must match the order of `DodgeDirection` variants.
+fn make_dodge_state(
+    machine: &mut Machine,
+    scene: &mut Scene,
+    model: Handle<Node>,
+    index: String,
+    resources: [Model; 4],
+) -> DodgeStateDefinition {
+    let mut animations = [Handle::NONE; 4];
+    let mut inputs = Vec::new();
+    for (animation, resource) in animations.iter_mut().zip(resources) {
+        *animation = *resource.retarget_animations(model, scene).get(0).unwrap();
+        scene.animations[*animation].set_speed(2.0);
+        inputs.push(IndexedBlendInput {
+            blend_time: 0.1,
+            pose_source: machine.add_node(PoseNode::make_play_animation(*animation)),
+        });
+    }
+
+    let handle = machine.add_node(PoseNode::make_blend_animations_by_index(index, inputs));
+
+    DodgeStateDefinition {
+        state: machine.add_state(State::new("Dodge", handle)),
+        animations,
+    }
+}
+
 #[derive(Default, Visit, Debug)]
 pub struct LowerBodyMachine {
     pub machine: Machine,
@@ -70,6 +107,10 @@ pub struct LowerBodyMachine {
     pub dying_animation: Handle<Animation>,
     pub hit_reaction_pistol_animation: Handle<Animation>,
     pub hit_reaction_rifle_animation: Handle<Animation>,
+    #[visit(optional)]
+    pub dodge_animations: [Handle<Animation>; 4],
+    #[visit(optional)]
+    pub struggle_animation: Handle<Animation>,
     pub walk_state: Handle<State>,
     pub jump_state: Handle<State>,
     pub fall_state: Handle<State>,
@@ -86,6 +127,9 @@ pub struct LowerBodyMachineInput {
     pub has_ground_contact: bool,
     pub is_dead: bool,
     pub should_be_stunned: bool,
+    pub is_grabbed: bool,
+    pub is_dodging: bool,
+    pub dodge_direction: DodgeDirection,
     pub weapon_kind: CombatWeaponKind,
 }
 
@@ -112,6 +156,17 @@ impl LowerBodyMachine {
     const HIT_REACTION_TO_WALK: &'static str = "HitReactionToWalk";
     const HIT_REACTION_TO_DYING: &'static str = "HitReactionToDying";
 
+    const IDLE_TO_DODGE: &'static str = "IdleToDodge";
+    const WALK_TO_DODGE: &'static str = "WalkToDodge";
+    const DODGE_TO_IDLE: &'static str = "DodgeToIdle";
+    const DODGE_TO_WALK: &'static str = "DodgeToWalk";
+    const DODGE_TO_DYING: &'static str = "DodgeToDying";
+
+    const IDLE_TO_STRUGGLE: &'static str = "IdleToStruggle";
+    const WALK_TO_STRUGGLE: &'static str = "WalkToStruggle";
+    const STRUGGLE_TO_IDLE: &'static str = "StruggleToIdle";
+    const STRUGGLE_TO_DYING: &'static str = "StruggleToDying";
+
     pub const JUMP_SIGNAL: u64 = 1;
     pub const LANDING_SIGNAL: u64 = 2;
     pub const FOOTSTEP_SIGNAL: u64 = 3;
@@ -119,6 +174,7 @@ impl LowerBodyMachine {
     const RUN_FACTOR: &'static str = "RunFactor";
     const WALK_FACTOR: &'static str = "WalkFactor";
     const HIT_REACTION_WEAPON_KIND: &'static str = "HitReactionWeaponKind";
+    const DODGE_DIRECTION: &'static str = "DodgeDirection";
 
     pub async fn new(
         scene: &mut Scene,
@@ -138,6 +194,10 @@ impl LowerBodyMachine {
             dying_animation_resource,
             hit_reaction_rifle_animation_resource,
             hit_reaction_pistol_animation_resource,
+            walk_back_animation_resource,
+            strafe_left_animation_resource,
+            strafe_right_animation_resource,
+            struggle_animation_resource,
         ) = fyrox::core::futures::join!(
             resource_manager.request_model("data/animations/agent_walking_lower_body.fbx",),
             resource_manager.request_model("data/animations/agent_idle.fbx"),
@@ -148,6 +208,20 @@ impl LowerBodyMachine {
             resource_manager.request_model("data/animations/agent_dying.fbx"),
             resource_manager.request_model("data/animations/agent_hit_reaction_rifle.fbx",),
             resource_manager.request_model("data/animations/agent_hit_reaction_pistol.fbx",),
+            resource_manager.request_model("data/animations/agent_walk_back.fbx"),
+            resource_manager.request_model("data/animations/agent_strafe_left.fbx"),
+            resource_manager.request_model("data/animations/agent_strafe_right.fbx"),
+            resource_manager.request_model("data/animations/agent_struggle.fbx"),
+        );
+        let run_animation_resource = run_animation_resource.unwrap();
+
+        // Struggle is paired with grab animations of bots, it loops in sync with them.
+        let (struggle_animation, struggle_state) = create_play_animation_state(
+            struggle_animation_resource.unwrap(),
+            "Struggle",
+            &mut machine,
+            scene,
+            model,
         );
 
         let HitReactionStateDefinition {
@@ -212,11 +286,27 @@ impl LowerBodyMachine {
             scene,
             model,
             walk_animation_resource.unwrap(),
-            run_animation_resource.unwrap(),
+            run_animation_resource.clone(),
             Self::WALK_FACTOR.to_owned(),
             Self::RUN_FACTOR.to_owned(),
         );
 
+        let DodgeStateDefinition {
+            state: dodge_state,
+            animations: dodge_animations,
+        } = make_dodge_state(
+            &mut machine,
+            scene,
+            model,
+            Self::DODGE_DIRECTION.to_owned(),
+            [
+                run_animation_resource,
+                walk_back_animation_resource.unwrap(),
+                strafe_left_animation_resource.unwrap(),
+                strafe_right_animation_resource.unwrap(),
+            ],
+        );
+
         scene
             .animations
             .get_mut(jump_animation)
@@ -399,6 +489,71 @@ impl LowerBodyMachine {
             Self::HIT_REACTION_TO_DYING,
         ));
 
+        machine.add_transition(Transition::new(
+            "Idle->Dodge",
+            idle_state,
+            dodge_state,
+            0.10,
+            Self::IDLE_TO_DODGE,
+        ));
+        machine.add_transition(Transition::new(
+            "Walk->Dodge",
+            walk_state,
+            dodge_state,
+            0.10,
+            Self::WALK_TO_DODGE,
+        ));
+        machine.add_transition(Transition::new(
+            "Dodge->Idle",
+            dodge_state,
+            idle_state,
+            0.20,
+            Self::DODGE_TO_IDLE,
+        ));
+        machine.add_transition(Transition::new(
+            "Dodge->Walk",
+            dodge_state,
+            walk_state,
+            0.20,
+            Self::DODGE_TO_WALK,
+        ));
+        machine.add_transition(Transition::new(
+            "Dodge->Dying",
+            dodge_state,
+            dying_state,
+            0.20,
+            Self::DODGE_TO_DYING,
+        ));
+
+        machine.add_transition(Transition::new(
+            "Idle->Struggle",
+            idle_state,
+            struggle_state,
+            0.20,
+            Self::IDLE_TO_STRUGGLE,
+        ));
+        machine.add_transition(Transition::new(
+            "Walk->Struggle",
+            walk_state,
+            struggle_state,
+            0.20,
+            Self::WALK_TO_STRUGGLE,
+        ));
+        machine.add_transition(Transition::new(
+            "Struggle->Idle",
+            struggle_state,
+            idle_state,
+            0.20,
+            Self::STRUGGLE_TO_IDLE,
+        ));
+        machine.add_transition(Transition::new(
+            "Struggle->Dying",
+            struggle_state,
+            dying_state,
+            0.20,
+            Self::STRUGGLE_TO_DYING,
+        ));
+
         machine.set_entry_state(idle_state);
 
         Self {
@@ -410,6 +565,8 @@ impl LowerBodyMachine {
             dying_animation,
             hit_reaction_pistol_animation,
             hit_reaction_rifle_animation,
+            dodge_animations,
+            struggle_animation,
             walk_state,
             jump_state,
             fall_state,
@@ -426,6 +583,7 @@ impl LowerBodyMachine {
         dt: f32,
         input: LowerBodyMachineInput,
         has_ground_contact: bool,
+        self_handle: Handle<Node>,
         character: &Character,
         sound_manager: &SoundManager,
     ) {
@@ -479,6 +637,25 @@ impl LowerBodyMachine {
             .set_parameter(Self::HIT_REACTION_TO_IDLE, Parameter::Rule(recovered))
             .set_parameter(Self::HIT_REACTION_TO_WALK, Parameter::Rule(recovered))
             .set_parameter(Self::HIT_REACTION_TO_DYING, Parameter::Rule(input.is_dead))
+            .set_parameter(Self::IDLE_TO_STRUGGLE, Parameter::Rule(input.is_grabbed))
+            .set_parameter(Self::WALK_TO_STRUGGLE, Parameter::Rule(input.is_grabbed))
+            .set_parameter(Self::STRUGGLE_TO_IDLE, Parameter::Rule(!input.is_grabbed))
+            .set_parameter(Self::STRUGGLE_TO_DYING, Parameter::Rule(input.is_dead))
+            .set_parameter(Self::IDLE_TO_DODGE, Parameter::Rule(input.is_dodging))
+            .set_parameter(Self::WALK_TO_DODGE, Parameter::Rule(input.is_dodging))
+            .set_parameter(
+                Self::DODGE_TO_IDLE,
+                Parameter::Rule(!input.is_dodging && !input.is_walking),
+            )
+            .set_parameter(
+                Self::DODGE_TO_WALK,
+                Parameter::Rule(!input.is_dodging && input.is_walking),
+            )
+            .set_parameter(Self::DODGE_TO_DYING, Parameter::Rule(input.is_dead))
+            .set_parameter(
+                Self::DODGE_DIRECTION,
+                Parameter::Index(input.dodge_direction as u32),
+            )
             .set_parameter(Self::WALK_FACTOR, Parameter::Weight(1.0 - input.run_factor))
             .set_parameter(Self::RUN_FACTOR, Parameter::Weight(input.run_factor))
             .evaluate_pose(&scene.animations, dt)
@@ -506,13 +683,25 @@ impl LowerBodyMachine {
                 && walking
                 || input.run_factor >= 0.5 && !walking
             {
-                character.footstep_ray_check(begin, scene, sound_manager);
+                // Running is much louder than walking.
+                let loudness = if walking {
+                    Noise::WALK_LOUDNESS
+                } else {
+                    Noise::RUN_LOUDNESS
+                };
+                character.footstep_ray_check(self_handle, begin, scene, sound_manager, loudness);
             }
         }
 
         while let Some(evt) = scene.animations.get_mut(self.land_animation).pop_event() {
             if evt.signal_id == Self::FOOTSTEP_SIGNAL {
-                character.footstep_ray_check(begin, scene, sound_manager);
+                character.footstep_ray_check(
+                    self_handle,
+                    begin,
+                    scene,
+                    sound_manager,
+                    Noise::RUN_LOUDNESS,
+                );
             }
         }
     }
@@ -1,25 +1,37 @@
 use crate::{
-    character::{Character, CharacterCommand},
+    character::{try_get_character_mut, try_get_character_ref, Character, CharacterCommand},
     control_scheme::ControlButton,
     current_level_mut, current_level_ref,
-    door::{door_mut, DoorContainer},
+    damage::DamageSource,
+    door::{door_mut, Door},
+    effects,
     elevator::call_button::{CallButton, CallButtonKind},
     game_mut, game_ref,
     gui::journal::Journal,
     inventory::Inventory,
-    level::item::ItemKind,
+    level::{
+        corpse::CorpseContainer,
+        item::ItemKind,
+        spatial::{SpatialCategory, SpatialIndex},
+        trail::ShotTrail,
+    },
     message::Message,
     player::{
+        dodge::{Dodge, DodgeDirection},
+        hit_feedback::HitFeedback,
+        kinesis::Kinesis,
         lower_body::{LowerBodyMachine, LowerBodyMachineInput},
+        struggle::{Struggle, StruggleOutcome},
         upper_body::{CombatWeaponKind, UpperBodyMachine, UpperBodyMachineInput},
     },
     sound::SoundManager,
     weapon::{
         definition::WeaponKind,
-        projectile::{Projectile, ProjectileKind},
-        try_weapon_ref, weapon_mut, weapon_ref,
+        projectile::{DamageKind, Projectile, ProjectileKind},
+        try_weapon_ref, weapon_mut, weapon_ref, Weapon,
     },
-    CameraController, Elevator, Game, Item,
+    workbench::Workbench,
+    CameraController, Elevator, Game, Item, MessageSender,
 };
 use fyrox::{
     animation::{
@@ -42,23 +54,29 @@ use fyrox::{
     engine::resource_manager::ResourceManager,
     event::{DeviceEvent, ElementState, Event, MouseScrollDelta, WindowEvent},
     impl_component_provider,
-    material::{shader::SamplerFallback, PropertyValue},
+    material::{shader::SamplerFallback, Material, PropertyValue, SharedMaterial},
     resource::{model::Model, texture::Texture},
     scene::{
         base::BaseBuilder,
         graph::Graph,
         light::BaseLight,
+        mesh::{surface::SurfaceBuilder, Mesh, MeshBuilder},
         node::{Node, TypeUuidProvider},
         sprite::SpriteBuilder,
+        transform::TransformBuilder,
         Scene,
     },
-    script::{ScriptContext, ScriptDeinitContext, ScriptTrait},
+    script::{Script, ScriptContext, ScriptDeinitContext, ScriptTrait},
     utils::log::Log,
 };
 use std::ops::{Deref, DerefMut};
 
 pub mod camera;
+mod dodge;
+mod hit_feedback;
+mod kinesis;
 mod lower_body;
+mod struggle;
 mod upper_body;
 
 pub struct HitReactionStateDefinition {
@@ -130,8 +148,12 @@ pub struct InputController {
     pitch: f32,
     aim: bool,
     toss_grenade: bool,
+    use_stasis: bool,
+    use_kinesis: bool,
     shoot: bool,
     run: bool,
+    dodge: bool,
+    struggle: bool,
     action: bool,
     cursor_up: bool,
     cursor_down: bool,
@@ -175,6 +197,8 @@ impl Default for RequiredWeapon {
 pub struct PlayerPersistentData {
     pub inventory: Inventory,
     pub health: f32,
+    pub armor: f32,
+    pub armor_tier: u32,
     pub current_weapon: u32,
     pub weapons: Vec<WeaponKind>,
 }
@@ -204,6 +228,8 @@ pub struct Player {
     inventory_display: Handle<Node>,
     journal_display: Handle<Node>,
     health_cylinder: Handle<Node>,
+    #[visit(optional)]
+    armor_cylinder: Handle<Node>,
     last_health: f32,
     health_color_gradient: ColorGradient,
     v_recoil: SmoothAngle,
@@ -226,10 +252,47 @@ pub struct Player {
     #[inspect(skip)]
     weapon_change_direction: RequiredWeapon,
 
+    #[reflect(hidden)]
+    #[inspect(skip)]
+    #[visit(optional)]
+    throwable: ItemKind,
+
+    #[reflect(hidden)]
+    #[inspect(skip)]
+    #[visit(optional)]
+    stasis_energy: f32,
+
+    #[reflect(hidden)]
+    #[inspect(skip)]
+    #[visit(optional)]
+    kinesis: Kinesis,
+
+    #[reflect(hidden)]
+    #[inspect(skip)]
+    #[visit(optional)]
+    hit_feedback: HitFeedback,
+
+    #[reflect(hidden)]
+    #[inspect(skip)]
+    #[visit(optional)]
+    dodge: Dodge,
+
+    /// Set while a bot holds the player.
+    #[reflect(hidden)]
+    #[inspect(skip)]
+    #[visit(optional)]
+    struggle: Option<Struggle>,
+
     #[reflect(hidden)]
     #[inspect(skip)]
     pub journal: Journal,
 
+    /// Set by melee hits, plays hit reaction animations on the next update.
+    #[visit(skip)]
+    #[reflect(hidden)]
+    #[inspect(skip)]
+    hit_reaction_requested: bool,
+
     #[visit(skip)]
     #[reflect(hidden)]
     #[inspect(skip)]
@@ -248,6 +311,7 @@ impl Default for Player {
             controller: Default::default(),
             lower_body_machine: Default::default(),
             health_cylinder: Default::default(),
+            armor_cylinder: Default::default(),
             upper_body_machine: Default::default(),
             spine: Default::default(),
             hips: Default::default(),
@@ -263,6 +327,12 @@ impl Default for Player {
                 speed: 10.0,
             },
             weapon_change_direction: RequiredWeapon::None,
+            throwable: ItemKind::Grenade,
+            stasis_energy: Player::STASIS_MAX_ENERGY,
+            kinesis: Default::default(),
+            hit_feedback: Default::default(),
+            dodge: Default::default(),
+            struggle: None,
             weapon_yaw_correction: SmoothAngle {
                 angle: 0.0,
                 target: 30.0f32.to_radians(),
@@ -296,6 +366,7 @@ impl Default for Player {
             journal: Journal::new(),
             model_pivot: Default::default(),
             model_sub_pivot: Default::default(),
+            hit_reaction_requested: false,
         }
     }
 }
@@ -326,6 +397,7 @@ impl Clone for Player {
             journal_display: self.journal_display,
             item_display: self.item_display,
             health_cylinder: self.health_cylinder,
+            armor_cylinder: self.armor_cylinder,
             last_health: self.last_health,
             health_color_gradient: self.health_color_gradient.clone(),
             v_recoil: self.v_recoil.clone(),
@@ -334,7 +406,14 @@ impl Clone for Player {
             lower_body_machine: Default::default(),
             upper_body_machine: Default::default(),
             weapon_change_direction: self.weapon_change_direction,
+            throwable: self.throwable,
+            stasis_energy: self.stasis_energy,
+            kinesis: self.kinesis.clone(),
+            hit_feedback: self.hit_feedback.clone(),
+            dodge: self.dodge.clone(),
+            struggle: self.struggle.clone(),
             journal: Default::default(),
+            hit_reaction_requested: self.hit_reaction_requested,
             controller: Default::default(),
         }
     }
@@ -348,6 +427,20 @@ fn make_color_gradient() -> ColorGradient {
 }
 
 impl Player {
+    pub const STASIS_MAX_ENERGY: f32 = 100.0;
+    pub const MAX_STAMINA: f32 = Dodge::MAX_STAMINA;
+    const STASIS_ENERGY_COST: f32 = 35.0;
+    const STASIS_DURATION: f32 = 5.0;
+    const STASIS_RANGE: f32 = 30.0;
+    /// Min time in air before landing, that is needed to stomp a corpse.
+    const STOMP_AIR_TIME: f32 = 0.3;
+    /// Distance between the player and a grabber during a struggle.
+    const GRAB_DISTANCE: f32 = 0.9;
+    /// Speed (m/s) at which melee hits push the player.
+    const HIT_REACTION_PUSH_SPEED: f32 = 4.0;
+    /// Max distance to an item that the player can pick up.
+    const ITEM_PICKUP_DISTANCE: f32 = 0.75;
+
     pub async fn add_to_scene(
         scene: &mut Scene,
         resource_manager: ResourceManager,
@@ -367,6 +460,8 @@ impl Player {
         PlayerPersistentData {
             inventory: self.inventory.clone(),
             health: self.health,
+            armor: self.armor,
+            armor_tier: self.armor_tier,
             current_weapon: self.current_weapon,
             weapons: self
                 .weapons
@@ -387,51 +482,45 @@ impl Player {
         resource_manager: &ResourceManager,
     ) {
         let sender = &game.message_sender;
-        let items = &game.level.as_ref().unwrap().items;
-        for &item_handle in items.iter() {
-            if let Some(item_node) = scene.graph.try_get(item_handle) {
-                let item = item_node.try_get_script::<Item>().unwrap();
-                let self_position = scene.graph[self.body].global_position();
-                let item_position = item_node.global_position();
-
-                let distance = (item_position - self_position).norm();
-                if distance < 0.75 {
-                    game.item_display.sync_to_model(
-                        resource_manager.clone(),
-                        item.get_kind(),
-                        item.stack_size,
-                    );
-
-                    if self.controller.action {
-                        self.push_command(CharacterCommand::PickupItem(item_handle));
-                        sender.send(Message::SyncInventory);
+        let self_position = scene.graph[self.body].global_position();
+        let closest = game.level.as_ref().unwrap().spatial_index.closest(
+            self_position,
+            Self::ITEM_PICKUP_DISTANCE,
+            SpatialCategory::Item,
+        );
+        if let Some((item_handle, item_node)) =
+            closest.and_then(|item| scene.graph.try_get(item).map(|node| (item, node)))
+        {
+            let item = item_node.try_get_script::<Item>().unwrap();
+            let item_position = item_node.global_position();
 
-                        self.controller.action = false;
-                    }
+            game.item_display.sync_to_model(
+                resource_manager.clone(),
+                item.get_kind(),
+                item.stack_size,
+            );
 
-                    let display = &mut scene.graph[self.item_display];
-                    display
-                        .local_transform_mut()
-                        .set_position(item_position + Vector3::new(0.0, 0.2, 0.0));
-                    display.set_visibility(true);
+            if self.controller.action {
+                self.push_command(CharacterCommand::PickupItem(item_handle));
+                sender.send(Message::SyncInventory);
 
-                    break;
-                }
+                self.controller.action = false;
             }
+
+            let display = &mut scene.graph[self.item_display];
+            display
+                .local_transform_mut()
+                .set_position(item_position + Vector3::new(0.0, 0.2, 0.0));
+            display.set_visibility(true);
         }
     }
 
-    fn check_doors(&mut self, scene: &mut Scene, door_container: &DoorContainer) {
+    fn check_doors(&mut self, scene: &mut Scene, spatial_index: &SpatialIndex) {
         let self_position = self.position(&scene.graph);
 
         if self.controller.action {
-            for &door_handle in &door_container.doors {
-                let door = door_mut(door_handle, &mut scene.graph);
-                let close_enough = self_position.metric_distance(&door.initial_position()) < 1.25;
-                if close_enough {
-                    let has_key = self.inventory.has_key();
-                    door.try_open(has_key);
-                }
+            for door_handle in spatial_index.in_radius(self_position, 1.25, SpatialCategory::Door) {
+                door_mut(door_handle, &mut scene.graph).try_open(&self.inventory);
             }
         }
     }
@@ -503,6 +592,84 @@ impl Player {
         }
     }
 
+    fn check_workbenches(
+        &mut self,
+        scene: &mut Scene,
+        spatial_index: &SpatialIndex,
+        sound_manager: &SoundManager,
+        sender: &MessageSender,
+    ) {
+        let self_position = self.position(&scene.graph);
+        let current_weapon = self.current_weapon();
+
+        for workbench_handle in
+            spatial_index.in_radius(self_position, 1.0, SpatialCategory::Interactable)
+        {
+            if !scene.graph[workbench_handle].has_script::<Workbench>() {
+                continue;
+            }
+
+            let upgrade_count = try_weapon_ref(current_weapon, &scene.graph)
+                .map_or(0, |w| w.definition.upgrades.len());
+
+            let workbench = scene.graph[workbench_handle]
+                .try_get_script_mut::<Workbench>()
+                .unwrap();
+
+            if self.controller.cursor_down {
+                workbench.select_next(upgrade_count);
+                self.controller.cursor_down = false;
+            } else if self.controller.cursor_up {
+                workbench.select_previous();
+                self.controller.cursor_up = false;
+            }
+
+            if self.controller.action {
+                self.controller.action = false;
+
+                let selection = workbench.selection();
+
+                let installed = if let Some(weapon) = try_weapon_ref(current_weapon, &scene.graph) {
+                    let cost = weapon
+                        .definition
+                        .upgrades
+                        .get(selection as usize)
+                        .map_or(0, |u| u.cost);
+
+                    if weapon.can_install_upgrade(selection)
+                        && self.inventory.item_count(ItemKind::UpgradeModule) >= cost
+                    {
+                        self.inventory
+                            .try_extract_exact_items(ItemKind::UpgradeModule, cost);
+                        weapon_mut(current_weapon, &mut scene.graph).install_upgrade(selection)
+                    } else {
+                        false
+                    }
+                } else {
+                    false
+                };
+
+                let sound = if installed {
+                    sender.send(Message::SyncInventory);
+                    "data/sounds/access_granted.ogg"
+                } else {
+                    "data/sounds/door_deny.ogg"
+                };
+
+                sound_manager.play_sound(
+                    &mut scene.graph,
+                    sound,
+                    workbench_position,
+                    1.0,
+                    1.0,
+                    1.0,
+                );
+            }
+
+            break;
+        }
+    }
+
     fn handle_jump_signal(&self, scene: &mut Scene, dt: f32) -> Option<f32> {
         let mut new_y_vel = None;
         while let Some(event) = scene
@@ -571,7 +738,8 @@ impl Player {
             .get_mut(self.upper_body_machine.toss_grenade_animation)
             .pop_event()
         {
-            if event.signal_id == UpperBodyMachine::TOSS_GRENADE_SIGNAL {
+            // The toss could be interrupted by a grab.
+            if event.signal_id == UpperBodyMachine::TOSS_GRENADE_SIGNAL && self.struggle.is_none() {
                 let position = scene.graph[self.weapon_pivot].global_position();
 
                 let direction = scene
@@ -581,9 +749,14 @@ impl Player {
                     .map(|c| scene.graph[c.camera()].look_vector())
                     .unwrap_or_default();
 
-                if self.inventory.try_extract_exact_items(ItemKind::Grenade, 1) == 1 {
+                let projectile = self
+                    .throwable
+                    .associated_throwable()
+                    .unwrap_or(ProjectileKind::Grenade);
+
+                if self.inventory.try_extract_exact_items(self.throwable, 1) == 1 {
                     Projectile::add_to_scene(
-                        ProjectileKind::Grenade,
+                        projectile,
                         resource_manager,
                         scene,
                         direction,
@@ -592,8 +765,197 @@ impl Player {
                         direction.scale(15.0),
                     );
                 }
+
+                // Switch to other throwable when current one has run out.
+                if self.inventory.item_count(self.throwable) == 0 {
+                    self.cycle_throwable();
+                }
+            }
+        }
+    }
+
+    pub fn selected_throwable(&self) -> ItemKind {
+        self.throwable
+    }
+
+    /// Selects next throwable that is present in the inventory.
+    fn cycle_throwable(&mut self) {
+        let throwables = &ItemKind::THROWABLES;
+        let current = throwables
+            .iter()
+            .position(|t| *t == self.throwable)
+            .unwrap_or_default();
+
+        for i in 1..=throwables.len() {
+            let candidate = throwables[(current + i) % throwables.len()];
+            if self.inventory.item_count(candidate) > 0 {
+                self.throwable = candidate;
+                break;
+            }
+        }
+    }
+
+    pub fn stasis_energy(&self) -> f32 {
+        self.stasis_energy
+    }
+
+    /// Returns position and look direction of the camera of the player.
+    pub fn view(&self, graph: &Graph) -> Option<(Vector3<f32>, Vector3<f32>)> {
+        graph
+            .try_get(self.camera_controller)
+            .and_then(|c| c.try_get_script::<CameraController>())
+            .map(|c| &graph[c.camera()])
+            .map(|camera| (camera.global_position(), camera.look_vector()))
+    }
+
+    fn update_stasis_module(
+        &mut self,
+        self_handle: Handle<Node>,
+        scene: &mut Scene,
+        resource_manager: &ResourceManager,
+        actors: &[Handle<Node>],
+        sound_manager: &SoundManager,
+    ) {
+        // Recharge from stasis cells in the inventory.
+        let cell_energy = Item::get_definition(ItemKind::StasisCell).energy;
+        while self.stasis_energy + cell_energy <= Self::STASIS_MAX_ENERGY
+            && self
+                .inventory
+                .try_extract_exact_items(ItemKind::StasisCell, 1)
+                == 1
+        {
+            self.stasis_energy += cell_energy;
+        }
+
+        if !self.controller.use_stasis {
+            return;
+        }
+        self.controller.use_stasis = false;
+
+        // Grabbed player must break free first.
+        if self.struggle.is_some() {
+            return;
+        }
+
+        let begin = scene.graph[self.weapon_pivot].global_position();
+
+        if self.stasis_energy < Self::STASIS_ENERGY_COST {
+            sound_manager.play_sound(
+                &mut scene.graph,
+                "data/sounds/door_deny.ogg",
+                begin,
+                1.0,
+                1.0,
+                1.0,
+            );
+            return;
+        }
+        self.stasis_energy -= Self::STASIS_ENERGY_COST;
+
+        let direction = scene
+            .graph
+            .try_get(self.camera_controller)
+            .and_then(|c| c.try_get_script::<CameraController>())
+            .map(|c| scene.graph[c.camera()].look_vector())
+            .unwrap_or_default();
+        let mut end = begin + direction.scale(Self::STASIS_RANGE);
+
+        if let Some(hit) = Weapon::ray_hit(
+            begin,
+            end,
+            self_handle,
+            actors,
+            &mut scene.graph,
+            Default::default(),
+        ) {
+            end = hit.position;
+
+            if let Some(character) = try_get_character_mut(hit.actor, &mut scene.graph) {
+                character.apply_stasis(Self::STASIS_DURATION);
+            } else {
+                // Collider can be attached somewhere deep in the hierarchy of an object, so
+                // look for a suitable script up to the root.
+                let mut handle = hit.collider;
+                while let Some(node) = scene.graph.try_get_mut(handle) {
+                    if let Some(door) = node.try_get_script_mut::<Door>() {
+                        door.apply_stasis(Self::STASIS_DURATION);
+                        break;
+                    } else if let Some(elevator) = node.try_get_script_mut::<Elevator>() {
+                        elevator.apply_stasis(Self::STASIS_DURATION);
+                        break;
+                    }
+                    handle = node.parent();
+                }
+            }
+        }
+
+        let effect = effects::create_rail(
+            &mut scene.graph,
+            resource_manager,
+            begin,
+            end,
+            Color::opaque(120, 255, 230),
+        );
+        scene.graph[effect].set_script(Some(Script::new(ShotTrail::new(0.5))));
+
+        sound_manager.play_sound(
+            &mut scene.graph,
+            "data/sounds/plasma_shot.ogg",
+            begin,
+            1.0,
+            1.0,
+            1.0,
+        );
+    }
+
+    fn update_kinesis(
+        &mut self,
+        self_handle: Handle<Node>,
+        scene: &mut Scene,
+        actors: &[Handle<Node>],
+        dt: f32,
+    ) {
+        let (origin, direction) = match scene
+            .graph
+            .try_get(self.camera_controller)
+            .and_then(|c| c.try_get_script::<CameraController>())
+            .map(|c| &scene.graph[c.camera()])
+        {
+            Some(camera) => (camera.global_position(), camera.look_vector()),
+            None => return,
+        };
+
+        if self.controller.use_kinesis {
+            self.controller.use_kinesis = false;
+
+            if self.kinesis.is_holding() {
+                self.kinesis.release();
+            } else if self.struggle.is_none() {
+                // Cast the ray from the hands, otherwise it could hit own hit boxes.
+                let begin = scene.graph[self.weapon_pivot].global_position();
+                self.kinesis.try_grab(
+                    begin,
+                    begin + direction.scale(Kinesis::RANGE),
+                    self_handle,
+                    actors,
+                    &mut scene.graph,
+                );
             }
         }
+
+        // Shooting while holding an object throws it instead of firing a weapon.
+        if self.kinesis.is_holding() && self.controller.shoot && self.struggle.is_none() {
+            self.controller.shoot = false;
+            self.kinesis.throw(&mut scene.graph, direction);
+        }
+
+        self.kinesis.update(
+            &mut scene.graph,
+            origin + direction.scale(Kinesis::HOLD_DISTANCE),
+            self_handle,
+            actors,
+            dt,
+        );
     }
 
     fn update_velocity(&mut self, scene: &Scene, can_move: bool, dt: f32) {
@@ -628,7 +990,9 @@ impl Player {
         }
 
         let speed = if can_move {
-            math::lerpf(self.move_speed, self.move_speed * 4.0, self.run_factor) * dt
+            math::lerpf(self.move_speed, self.move_speed * 4.0, self.run_factor)
+                * self.stasis_factor()
+                * dt
         } else {
             0.0
         };
@@ -640,6 +1004,196 @@ impl Player {
             .unwrap_or_default();
 
         self.velocity.follow(&self.target_velocity, 0.15);
+
+        if self.dodge.is_dodging() {
+            // Dodge overrides movement, the velocity is a displacement per frame.
+            self.velocity = self.dodge.velocity().scale(self.stasis_factor() * dt);
+        }
+    }
+
+    fn try_dodge(&mut self, scene: &mut Scene, can_move: bool) {
+        if !std::mem::take(&mut self.controller.dodge)
+            || !can_move
+            || self.in_air_time > 0.0
+            || self.dodge.is_dodging()
+        {
+            return;
+        }
+
+        let model_pivot = &scene.graph[self.model_pivot];
+        let look_vector = model_pivot.look_vector();
+        let side_vector = model_pivot.side_vector();
+
+        // Dodge in the direction of movement, or back if the player stands still.
+        let (direction, vector) = if self.controller.walk_left {
+            (DodgeDirection::Left, side_vector)
+        } else if self.controller.walk_right {
+            (DodgeDirection::Right, -side_vector)
+        } else if self.controller.walk_forward {
+            (DodgeDirection::Forward, look_vector)
+        } else {
+            (DodgeDirection::Backward, -look_vector)
+        };
+
+        if self.dodge.try_begin(vector, direction) {
+            for &animation in self.lower_body_machine.dodge_animations.iter() {
+                scene.animations.get_mut(animation).rewind();
+            }
+        }
+    }
+
+    pub fn stamina(&self) -> f32 {
+        self.dodge.stamina()
+    }
+
+    /// Starts a struggle with the grabber, returns false if the player can't be grabbed now.
+    pub fn try_grab(&mut self, grabber: Handle<Node>, struggle_time: f32, damage: f32) -> bool {
+        if self.is_dead() || self.struggle.is_some() || self.dodge.is_invulnerable() {
+            return false;
+        }
+
+        self.struggle = Some(Struggle::new(grabber, struggle_time, damage));
+
+        true
+    }
+
+    /// Keeps the player grabbed, returns false if the player is not held by the grabber anymore.
+    pub fn hold_grab(&mut self, grabber: Handle<Node>) -> bool {
+        match self.struggle.as_mut() {
+            Some(struggle) if struggle.grabber() == grabber => {
+                struggle.hold();
+                true
+            }
+            _ => false,
+        }
+    }
+
+    /// Puts the player in front of the grabber and turns both of them to face each other, so
+    /// the grab animation of the grabber and the struggle animation of the player line up.
+    fn align_with_grabber(&mut self, scene: &mut Scene, grabber: Handle<Node>) {
+        let grabber_body = match try_get_character_ref(grabber, &scene.graph) {
+            Some(grabber) => grabber.body,
+            None => return,
+        };
+
+        let graph = &mut scene.graph;
+        let grabber_position = graph[grabber_body].global_position();
+        let position = self.position(graph);
+        let to_player = match Vector3::new(
+            position.x - grabber_position.x,
+            0.0,
+            position.z - grabber_position.z,
+        )
+        .try_normalize(f32::EPSILON)
+        {
+            Some(direction) => direction,
+            None => -graph[self.model_pivot].look_vector(),
+        };
+
+        self.set_position(
+            graph,
+            Vector3::new(
+                grabber_position.x + to_player.x * Self::GRAB_DISTANCE,
+                position.y,
+                grabber_position.z + to_player.z * Self::GRAB_DISTANCE,
+            ),
+        );
+        self.velocity = Vector3::default();
+
+        graph[grabber_body]
+            .local_transform_mut()
+            .set_rotation(UnitQuaternion::from_axis_angle(
+                &Vector3::y_axis(),
+                to_player.x.atan2(to_player.z),
+            ));
+
+        self.controller.yaw = (-to_player.x).atan2(-to_player.z);
+        self.model_yaw.angle = 0.0;
+        self.model_yaw.set_target(0.0);
+        graph[self.model_pivot].local_transform_mut().set_rotation(
+            UnitQuaternion::from_axis_angle(&Vector3::y_axis(), self.controller.yaw),
+        );
+        graph[self.model_sub_pivot]
+            .local_transform_mut()
+            .set_rotation(UnitQuaternion::default());
+
+        // Held objects are dropped, the struggle needs both hands.
+        if self.kinesis.is_holding() {
+            self.kinesis.release();
+        }
+
+        for animation in [
+            self.lower_body_machine.struggle_animation,
+            self.upper_body_machine.struggle_animation,
+        ] {
+            scene.animations.get_mut(animation).rewind();
+        }
+    }
+
+    fn update_struggle(&mut self, scene: &mut Scene, dt: f32) {
+        let struggled = std::mem::take(&mut self.controller.struggle);
+
+        if let Some(grabber) = self
+            .struggle
+            .as_mut()
+            .and_then(|struggle| struggle.start().then(|| struggle.grabber()))
+        {
+            self.align_with_grabber(scene, grabber);
+        }
+
+        let struggle = match self.struggle.as_mut() {
+            Some(struggle) => struggle,
+            None => return,
+        };
+
+        if struggled {
+            struggle.struggle();
+
+            // Shaking tells the player that the struggle has an effect.
+            if let Some(camera_controller) = scene
+                .graph
+                .try_get_mut(self.camera_controller)
+                .and_then(|c| c.try_get_script_mut::<CameraController>())
+            {
+                camera_controller.request_shake_camera(0.3);
+            }
+        }
+
+        let grabber = struggle.grabber();
+        let damage = struggle.damage();
+        let outcome = if self.character.is_dead() {
+            StruggleOutcome::Released
+        } else {
+            struggle.update(dt)
+        };
+
+        match outcome {
+            StruggleOutcome::Continues => return,
+            StruggleOutcome::BrokeFree => {
+                // Shove the grabber away.
+                let direction = scene
+                    .graph
+                    .try_get(grabber)
+                    .map(|g| g.global_position() - self.position(&scene.graph))
+                    .unwrap_or_default();
+                if let Some(character) = try_get_character_mut(grabber, &mut scene.graph) {
+                    character.push_command(CharacterCommand::HitReaction { direction });
+                }
+            }
+            StruggleOutcome::Failed => {
+                self.push_command(CharacterCommand::Damage {
+                    source: DamageSource::Actor(grabber),
+                    hitbox: None,
+                    amount: damage,
+                    kind: DamageKind::Point,
+                    critical_shot_probability: 0.0,
+                });
+                self.hit_reaction_requested = true;
+            }
+            StruggleOutcome::Released => (),
+        }
+
+        self.struggle = None;
     }
 
     fn current_weapon_kind(&self, graph: &Graph) -> CombatWeaponKind {
@@ -657,7 +1211,7 @@ impl Player {
     }
 
     fn should_be_stunned(&self) -> bool {
-        self.last_health - self.health >= 15.0
+        self.last_health - self.health >= 15.0 || self.hit_reaction_requested
     }
 
     fn stun(&mut self, scene: &mut Scene) {
@@ -671,6 +1225,7 @@ impl Player {
         }
 
         self.last_health = self.health;
+        self.hit_reaction_requested = false;
     }
 
     fn is_walking(&self) -> bool {
@@ -680,9 +1235,49 @@ impl Player {
             || self.controller.walk_left
     }
 
+    fn on_damage_received(&mut self, source: DamageSource, amount: f32, scene: &mut Scene) {
+        let direction = source
+            .position(&scene.graph)
+            .map(|source_position| source_position - self.position(&scene.graph));
+
+        // Small portions of continuous damage are summed up, so the camera does not shake every
+        // frame.
+        let amount = match self.hit_feedback.register_damage(source, direction, amount) {
+            Some(amount) => amount,
+            None => return,
+        };
+
+        if let Some(camera_controller) = scene
+            .graph
+            .try_get_mut(self.camera_controller)
+            .and_then(|c| c.try_get_script_mut::<CameraController>())
+        {
+            camera_controller.request_shake_camera((amount / 20.0).clamp(0.5, 3.0));
+        }
+    }
+
+    fn on_hit_reaction(&mut self, direction: Vector3<f32>, dt: f32) {
+        if self.is_dead() {
+            return;
+        }
+
+        self.hit_reaction_requested = true;
+
+        // Push the player away a bit, the push fades out as the velocity follows the target one.
+        if let Some(direction) =
+            Vector3::new(direction.x, 0.0, direction.z).try_normalize(f32::EPSILON)
+        {
+            self.velocity += direction.scale(Self::HIT_REACTION_PUSH_SPEED * dt);
+        }
+    }
+
     fn update_health_cylinder(&self, scene: &mut Scene) {
         let mesh = scene.graph[self.health_cylinder].as_mesh_mut();
-        let color = self.health_color_gradient.get_color(self.health / 100.0);
+        let health_fraction = self.health / self.max_health();
+        let color = self.hit_feedback.low_health_tint(
+            self.health_color_gradient.get_color(health_fraction),
+            health_fraction,
+        );
         let surface = mesh.surfaces_mut().first_mut().unwrap();
         let mut material = surface.material().lock();
         Log::verify(material.set_property(
@@ -700,8 +1295,68 @@ impl Player {
             .set_color(color);
     }
 
+    /// Creates the armor cylinder next to the health cylinder, if the model does not have one.
+    /// The cylinder shares the shape of the health cylinder, but has its own material.
+    fn create_armor_cylinder(&mut self, graph: &mut Graph) {
+        if graph.is_valid_handle(self.armor_cylinder) {
+            return;
+        }
+
+        let (data, width) = match graph
+            .try_get(self.health_cylinder)
+            .and_then(|n| n.cast::<Mesh>())
+        {
+            Some(mesh) => {
+                let bounds = mesh.local_bounding_box();
+                (mesh.surfaces()[0].data(), bounds.max.x - bounds.min.x)
+            }
+            None => return,
+        };
+
+        self.armor_cylinder = MeshBuilder::new(
+            BaseBuilder::new()
+                .with_cast_shadows(false)
+                .with_local_transform(
+                    TransformBuilder::new()
+                        .with_local_position(Vector3::new(1.5 * width, 0.0, 0.0))
+                        .build(),
+                ),
+        )
+        .with_surfaces(vec![SurfaceBuilder::new(data)
+            .with_material(SharedMaterial::new(Material::standard()))
+            .build()])
+        .build(graph);
+
+        graph.link_nodes(self.armor_cylinder, self.health_cylinder);
+    }
+
+    fn update_armor_cylinder(&self, scene: &mut Scene) {
+        if let Some(mesh) = scene
+            .graph
+            .try_get_mut(self.armor_cylinder)
+            .and_then(|n| n.cast_mut::<Mesh>())
+        {
+            let armor = self.armor / self.max_armor().max(f32::EPSILON);
+            let color = Color::from_rgba(0, 162, 232, 200);
+
+            mesh.set_visibility(armor > 0.0);
+
+            let surface = mesh.surfaces_mut().first_mut().unwrap();
+            let mut material = surface.material().lock();
+            Log::verify(material.set_property(
+                &ImmutableString::new("diffuseColor"),
+                PropertyValue::Color(color),
+            ));
+            Log::verify(material.set_property(
+                &ImmutableString::new("emissionStrength"),
+                PropertyValue::Vector3(color.as_frgb().scale(10.0 * armor)),
+            ));
+        }
+    }
+
     fn update_animation_machines(
         &mut self,
+        self_handle: Handle<Node>,
         dt: f32,
         scene: &mut Scene,
         is_walking: bool,
@@ -726,9 +1381,13 @@ impl Player {
                 run_factor: self.run_factor,
                 is_dead: self.is_dead(),
                 should_be_stunned: false,
+                is_grabbed: self.struggle.is_some(),
+                is_dodging: self.dodge.is_dodging(),
+                dodge_direction: self.dodge.direction(),
                 weapon_kind,
             },
             has_ground_contact,
+            self_handle,
             &self.character,
             sound_manager,
         );
@@ -742,12 +1401,13 @@ impl Player {
                 is_jumping,
                 has_ground_contact: self.in_air_time <= 0.3,
                 is_aiming: self.controller.aim,
-                toss_grenade: self.controller.toss_grenade,
+                toss_grenade: self.controller.toss_grenade && self.struggle.is_none(),
                 weapon_kind,
                 change_weapon: self.weapon_change_direction != RequiredWeapon::None,
                 run_factor: self.run_factor,
                 is_dead: self.is_dead(),
                 should_be_stunned,
+                is_grabbed: self.struggle.is_some(),
             },
         );
     }
@@ -818,14 +1478,8 @@ impl Player {
                 if self.controller.shoot
                     && weapon_ref(current_weapon_handle, &scene.graph).can_shoot(elapsed_time)
                 {
-                    let ammo_per_shot = weapon_ref(current_weapon_handle, &scene.graph)
-                        .definition
-                        .ammo_consumption_per_shot;
-
-                    if self
-                        .inventory
-                        .try_extract_exact_items(ItemKind::Ammo, ammo_per_shot)
-                        == ammo_per_shot
+                    if weapon_mut(current_weapon_handle, &mut scene.graph)
+                        .try_consume_ammo(&mut self.inventory)
                     {
                         weapon_mut(current_weapon_handle, &mut scene.graph).request_shot(None);
 
@@ -834,17 +1488,13 @@ impl Player {
                             .try_get_mut(self.camera_controller)
                             .and_then(|c| c.try_get_script_mut::<CameraController>())
                         {
-                            camera_controller.request_shake_camera();
+                            camera_controller.request_shake_camera(1.0);
                         }
                         self.v_recoil.set_target(
-                            weapon_ref(current_weapon_handle, &scene.graph)
-                                .definition
-                                .gen_v_recoil_angle(),
+                            weapon_ref(current_weapon_handle, &scene.graph).gen_v_recoil_angle(),
                         );
                         self.h_recoil.set_target(
-                            weapon_ref(current_weapon_handle, &scene.graph)
-                                .definition
-                                .gen_h_recoil_angle(),
+                            weapon_ref(current_weapon_handle, &scene.graph).gen_h_recoil_angle(),
                         );
                     }
                 }
@@ -1008,6 +1658,11 @@ impl ScriptTrait for Player {
             .with_size(0.1)
             .build(&mut context.scene.graph);
 
+        self.hit_feedback
+            .init(&mut context.scene.graph, context.resource_manager);
+
+        self.create_armor_cylinder(&mut context.scene.graph);
+
         // Add default weapon.
         self.push_command(CharacterCommand::AddWeapon(WeaponKind::Glock));
 
@@ -1181,8 +1836,12 @@ impl ScriptTrait for Player {
                 if state == ElementState::Pressed && self.current_weapon > 0 && can_change_weapon {
                     weapon_change_direction = Some(RequiredWeapon::Previous);
                 }
+            } else if button == control_scheme.cycle_throwable.button {
+                if state == ElementState::Pressed {
+                    self.cycle_throwable();
+                }
             } else if button == control_scheme.toss_grenade.button {
-                if self.inventory.item_count(ItemKind::Grenade) > 0 {
+                if self.inventory.item_count(self.throwable) > 0 && self.struggle.is_none() {
                     self.controller.toss_grenade = state == ElementState::Pressed;
                     if state == ElementState::Pressed {
                         context
@@ -1193,6 +1852,22 @@ impl ScriptTrait for Player {
                             .rewind();
                     }
                 }
+            } else if button == control_scheme.dodge.button {
+                if state == ElementState::Pressed {
+                    self.controller.dodge = true;
+                }
+            } else if button == control_scheme.struggle.button {
+                if state == ElementState::Pressed {
+                    self.controller.struggle = true;
+                }
+            } else if button == control_scheme.use_stasis.button {
+                if state == ElementState::Pressed {
+                    self.controller.use_stasis = true;
+                }
+            } else if button == control_scheme.kinesis.button {
+                if state == ElementState::Pressed {
+                    self.controller.use_kinesis = true;
+                }
             } else if button == control_scheme.shoot.button {
                 self.controller.shoot = state == ElementState::Pressed;
             } else if button == control_scheme.cursor_up.button {
@@ -1254,25 +1929,60 @@ impl ScriptTrait for Player {
         let game = game_ref(ctx.plugins);
         let level = current_level_ref(ctx.plugins).unwrap();
 
-        while self
-            .poll_command(
-                ctx.scene,
-                ctx.handle,
-                ctx.resource_manager,
-                &level.sound_manager,
-            )
-            .is_some()
-        {
-            // TODO: Handle commands here
+        self.update_stasis(ctx.dt);
+        self.dodge.update(ctx.dt);
+
+        if self.dodge.is_invulnerable() {
+            // Melee attacks miss the player at the beginning of a dodge.
+            self.commands.retain(|command| {
+                !matches!(
+                    command,
+                    CharacterCommand::Damage {
+                        source: DamageSource::Actor(_),
+                        ..
+                    } | CharacterCommand::HitReaction { .. }
+                )
+            });
+        }
+
+        while let Some(command) = self.poll_command(
+            ctx.scene,
+            ctx.handle,
+            ctx.resource_manager,
+            &level.sound_manager,
+        ) {
+            match command {
+                CharacterCommand::Damage { source, amount, .. } => {
+                    self.on_damage_received(source, amount, ctx.scene)
+                }
+                CharacterCommand::HitReaction { direction } => {
+                    self.on_hit_reaction(direction, ctx.dt)
+                }
+                _ => (),
+            }
         }
 
+        self.update_struggle(ctx.scene, ctx.dt);
+
+        let position = self.position(&ctx.scene.graph);
+        let health_fraction = self.health / self.max_health();
+        self.hit_feedback.update(
+            &mut ctx.scene.graph,
+            position,
+            health_fraction,
+            &level.sound_manager,
+            ctx.dt,
+        );
+
         self.update_health_cylinder(ctx.scene);
+        self.update_armor_cylinder(ctx.scene);
 
         let has_ground_contact = self.has_ground_contact(&ctx.scene.graph);
         let is_walking = self.is_walking();
         let is_jumping = has_ground_contact && self.controller.jump;
 
         self.update_animation_machines(
+            ctx.handle,
             ctx.dt,
             ctx.scene,
             is_walking,
@@ -1291,12 +2001,21 @@ impl ScriptTrait for Player {
             }
             self.run_factor += (self.target_run_factor - self.run_factor) * 0.1;
 
-            let can_move = self.can_move();
+            // Grabbed player can't move until they break free.
+            let can_move = self.can_move() && self.struggle.is_none();
+            self.try_dodge(ctx.scene, can_move);
             self.update_velocity(ctx.scene, can_move, ctx.dt);
             let new_y_vel = self.handle_jump_signal(ctx.scene, ctx.dt);
             self.handle_weapon_grab_signal(ctx.scene);
             self.handle_put_back_weapon_end_signal(ctx.scene);
-            self.handle_toss_grenade_signal(Default::default(), ctx.scene, ctx.resource_manager);
+            self.handle_toss_grenade_signal(ctx.handle, ctx.scene, ctx.resource_manager);
+            self.update_stasis_module(
+                ctx.handle,
+                ctx.scene,
+                ctx.resource_manager,
+                &level.actors,
+                &level.sound_manager,
+            );
 
             let body = ctx.scene.graph[self.body].as_rigid_body_mut();
             body.set_ang_vel(Default::default());
@@ -1409,6 +2128,15 @@ impl ScriptTrait for Player {
             self.apply_weapon_angular_correction(ctx.scene, can_move, ctx.dt);
 
             if has_ground_contact {
+                // Landing on a corpse after a jump stomps it, so it can't be reanimated.
+                if self.in_air_time > Self::STOMP_AIR_TIME {
+                    let position = self.position(&ctx.scene.graph);
+                    level.corpses.destroy_near(
+                        &ctx.scene.graph,
+                        position,
+                        CorpseContainer::STOMP_RADIUS,
+                    );
+                }
                 self.in_air_time = 0.0;
             } else {
                 self.in_air_time += ctx.dt;
@@ -1425,9 +2153,18 @@ impl ScriptTrait for Player {
 
             ctx.scene.graph[self.item_display].set_visibility(false);
 
-            self.check_doors(ctx.scene, &level.doors_container);
+            self.check_doors(ctx.scene, &level.spatial_index);
             self.check_elevators(ctx.scene, &level.elevators);
-            self.update_shooting(ctx.scene, ctx.dt, ctx.elapsed_time);
+            self.check_workbenches(
+                ctx.scene,
+                &level.spatial_index,
+                &level.sound_manager,
+                &game.message_sender,
+            );
+            self.update_kinesis(ctx.handle, ctx.scene, &level.actors, ctx.dt);
+            if self.struggle.is_none() {
+                self.update_shooting(ctx.scene, ctx.dt, ctx.elapsed_time);
+            }
             self.check_items(game_mut(ctx.plugins), ctx.scene, ctx.resource_manager);
 
             let spine_transform = ctx.scene.graph[self.spine].local_transform_mut();
@@ -1454,7 +2191,9 @@ impl ScriptTrait for Player {
             body.set_lin_vel(Vector3::new(0.0, body.lin_vel().y, 0.0));
 
             if self.is_completely_dead(ctx.scene) {
-                game.message_sender.send(Message::EndMatch);
+                game.message_sender.send(Message::EndMatch {
+                    killed_by: self.last_damage_source.description(&ctx.scene.graph),
+                });
             }
         }
     }
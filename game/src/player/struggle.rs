@@ -0,0 +1,88 @@
+//! Struggle of the player grabbed by a bot. The player must break free by repeatedly pressing the
+//! struggle button before the time runs out, otherwise the bot deals heavy damage.
+
+use fyrox::{
+    core::{pool::Handle, visitor::prelude::*},
+    scene::node::Node,
+};
+
+pub enum StruggleOutcome {
+    Continues,
+    BrokeFree,
+    Failed,
+    /// The grabber stopped holding the player (it was killed or stunned for example).
+    Released,
+}
+
+#[derive(Visit, Default, Debug, Clone)]
+pub struct Struggle {
+    grabber: Handle<Node>,
+    time_left: f32,
+    damage: f32,
+    /// Breaks free when reaches 1.0.
+    progress: f32,
+    /// Time since the grabber held the player last time.
+    hold_time: f32,
+    /// Whether the player and the grabber were aligned at the beginning of the struggle.
+    #[visit(optional)]
+    started: bool,
+}
+
+impl Struggle {
+    const PROGRESS_PER_PRESS: f32 = 0.1;
+    /// Progress per second that is lost when the player does not struggle.
+    const PROGRESS_DECAY: f32 = 0.3;
+    /// Max time between holds of the grabber.
+    const HOLD_TIMEOUT: f32 = 0.3;
+
+    pub fn new(grabber: Handle<Node>, duration: f32, damage: f32) -> Self {
+        Self {
+            grabber,
+            time_left: duration,
+            damage,
+            progress: 0.0,
+            hold_time: 0.0,
+            started: false,
+        }
+    }
+
+    /// Returns true only once, at the first update of the struggle.
+    pub fn start(&mut self) -> bool {
+        !std::mem::replace(&mut self.started, true)
+    }
+
+    pub fn grabber(&self) -> Handle<Node> {
+        self.grabber
+    }
+
+    pub fn damage(&self) -> f32 {
+        self.damage
+    }
+
+    /// Must be called by the grabber every update, the grab ends otherwise.
+    pub fn hold(&mut self) {
+        self.hold_time = 0.0;
+    }
+
+    pub fn struggle(&mut self) {
+        self.progress += Self::PROGRESS_PER_PRESS;
+    }
+
+    pub fn update(&mut self, dt: f32) -> StruggleOutcome {
+        self.time_left -= dt;
+        self.hold_time += dt;
+
+        if self.progress >= 1.0 {
+            return StruggleOutcome::BrokeFree;
+        }
+        self.progress = (self.progress - Self::PROGRESS_DECAY * dt).max(0.0);
+
+        if self.hold_time > Self::HOLD_TIMEOUT {
+            StruggleOutcome::Released
+        } else if self.time_left <= 0.0 {
+            StruggleOutcome::Failed
+        } else {
+            StruggleOutcome::Continues
+        }
+    }
+}
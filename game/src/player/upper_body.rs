@@ -163,6 +163,8 @@ pub struct UpperBodyMachine {
     pub dying_animation: Handle<Animation>,
     pub hit_reaction_pistol_animation: Handle<Animation>,
     pub hit_reaction_rifle_animation: Handle<Animation>,
+    #[visit(optional)]
+    pub struggle_animation: Handle<Animation>,
 }
 
 fn disable_leg_tracks(
@@ -196,6 +198,7 @@ pub struct UpperBodyMachineInput {
     pub change_weapon: bool,
     pub is_dead: bool,
     pub should_be_stunned: bool,
+    pub is_grabbed: bool,
 }
 
 impl UpperBodyMachine {
@@ -249,6 +252,12 @@ impl UpperBodyMachine {
     const HIT_REACTION_TO_DYING: &'static str = "HitReactionToDying";
     const HIT_REACTION_TO_AIM: &'static str = "HitReactionToAim";
 
+    const AIM_TO_STRUGGLE: &'static str = "AimToStruggle";
+    const IDLE_TO_STRUGGLE: &'static str = "IdleToStruggle";
+    const WALK_TO_STRUGGLE: &'static str = "WalkToStruggle";
+    const STRUGGLE_TO_IDLE: &'static str = "StruggleToIdle";
+    const STRUGGLE_TO_DYING: &'static str = "StruggleToDying";
+
     const HIT_REACTION_WEAPON_KIND: &'static str = "HitReactionWeaponKind";
     const IDLE_STATE_WEAPON_KIND: &'static str = "IdleStateWeaponKind";
     const WALK_STATE_WEAPON_KIND: &'static str = "IdleStateWeaponKind";
@@ -283,6 +292,7 @@ impl UpperBodyMachine {
             dying_animation_resource,
             hit_reaction_rifle_animation_resource,
             hit_reaction_pistol_animation_resource,
+            struggle_animation_resource,
         ) = fyrox::core::futures::join!(
             resource_manager.request_model("data/animations/agent_walk_rifle.fbx"),
             resource_manager.request_model("data/animations/agent_idle_pistol.fbx"),
@@ -301,6 +311,16 @@ impl UpperBodyMachine {
             resource_manager.request_model("data/animations/agent_dying.fbx"),
             resource_manager.request_model("data/animations/agent_hit_reaction_rifle.fbx"),
             resource_manager.request_model("data/animations/agent_hit_reaction_pistol.fbx"),
+            resource_manager.request_model("data/animations/agent_struggle.fbx"),
+        );
+
+        // Struggle is paired with grab animations of bots, it loops in sync with them.
+        let (struggle_animation, struggle_state) = create_play_animation_state(
+            struggle_animation_resource.unwrap(),
+            "Struggle",
+            &mut machine,
+            scene,
+            model,
         );
 
         let HitReactionStateDefinition {
@@ -757,6 +777,42 @@ impl UpperBodyMachine {
             Self::HIT_REACTION_TO_AIM,
         ));
 
+        machine.add_transition(Transition::new(
+            "Idle->Struggle",
+            idle_state,
+            struggle_state,
+            0.20,
+            Self::IDLE_TO_STRUGGLE,
+        ));
+        machine.add_transition(Transition::new(
+            "Walk->Struggle",
+            walk_state,
+            struggle_state,
+            0.20,
+            Self::WALK_TO_STRUGGLE,
+        ));
+        machine.add_transition(Transition::new(
+            "Aim->Struggle",
+            aim_state,
+            struggle_state,
+            0.20,
+            Self::AIM_TO_STRUGGLE,
+        ));
+        machine.add_transition(Transition::new(
+            "Struggle->Idle",
+            struggle_state,
+            idle_state,
+            0.20,
+            Self::STRUGGLE_TO_IDLE,
+        ));
+        machine.add_transition(Transition::new(
+            "Struggle->Dying",
+            struggle_state,
+            dying_state,
+            0.20,
+            Self::STRUGGLE_TO_DYING,
+        ));
+
         for leg in &["mixamorig:LeftUpLeg", "mixamorig:RightUpLeg"] {
             for &animation in &[
                 aim_pistol_animation,
@@ -776,6 +832,7 @@ impl UpperBodyMachine {
                 dying_animation,
                 hit_reaction_rifle_animation,
                 hit_reaction_pistol_animation,
+                struggle_animation,
             ] {
                 disable_leg_tracks(animation, model, leg, scene);
             }
@@ -798,6 +855,7 @@ impl UpperBodyMachine {
             dying_animation,
             hit_reaction_pistol_animation,
             hit_reaction_rifle_animation,
+            struggle_animation,
         }
     }
 
@@ -944,6 +1002,11 @@ impl UpperBodyMachine {
                 Self::HIT_REACTION_TO_AIM,
                 Parameter::Rule(recovered && input.is_aiming),
             )
+            .set_parameter(Self::IDLE_TO_STRUGGLE, Parameter::Rule(input.is_grabbed))
+            .set_parameter(Self::WALK_TO_STRUGGLE, Parameter::Rule(input.is_grabbed))
+            .set_parameter(Self::AIM_TO_STRUGGLE, Parameter::Rule(input.is_grabbed))
+            .set_parameter(Self::STRUGGLE_TO_IDLE, Parameter::Rule(!input.is_grabbed))
+            .set_parameter(Self::STRUGGLE_TO_DYING, Parameter::Rule(input.is_dead))
             .set_parameter(Self::LAND_TO_DYING, Parameter::Rule(input.is_dead))
             .set_parameter(Self::IDLE_TO_DYING, Parameter::Rule(input.is_dead))
             .set_parameter(Self::FALL_TO_DYING, Parameter::Rule(input.is_dead))
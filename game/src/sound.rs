@@ -20,7 +20,14 @@ use fyrox::{
     utils::log::{Log, MessageKind},
 };
 use serde::Deserialize;
-use std::{collections::HashMap, fs::File, ops::Range, path::Path, path::PathBuf};
+use std::{
+    cell::{Ref, RefCell},
+    collections::HashMap,
+    fs::File,
+    ops::Range,
+    path::Path,
+    path::PathBuf,
+};
 
 #[derive(Debug)]
 pub struct TriangleRange {
@@ -173,12 +180,63 @@ impl SoundMap {
     }
 }
 
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub enum NoiseKind {
+    FootStep,
+    Gunshot,
+    Explosion,
+    Door,
+}
+
+/// Noise is an audible event that bots can hear. It is not bound to an actual sound source,
+/// it just tells that something loud happened at some point for a short period of time.
+#[derive(Clone, Debug)]
+pub struct Noise {
+    pub kind: NoiseKind,
+    pub position: Vector3<f32>,
+    /// Max distance (in meters) at which the noise can be heard in the open.
+    pub loudness: f32,
+    /// An actor that made the noise, can be `Handle::NONE`.
+    pub emitter: Handle<Node>,
+    time_remaining: f32,
+}
+
+impl Noise {
+    pub const WALK_LOUDNESS: f32 = 4.0;
+    pub const RUN_LOUDNESS: f32 = 10.0;
+    pub const GUNSHOT_LOUDNESS: f32 = 30.0;
+    pub const EXPLOSION_LOUDNESS: f32 = 40.0;
+    pub const DOOR_LOUDNESS: f32 = 8.0;
+    pub const DOOR_BASH_LOUDNESS: f32 = 15.0;
+
+    /// How long a noise can be heard.
+    const LIFETIME: f32 = 0.25;
+
+    pub fn new(
+        kind: NoiseKind,
+        position: Vector3<f32>,
+        loudness: f32,
+        emitter: Handle<Node>,
+    ) -> Self {
+        Self {
+            kind,
+            position,
+            loudness,
+            emitter,
+            time_remaining: Self::LIFETIME,
+        }
+    }
+}
+
 #[derive(Default)]
 pub struct SoundManager {
     reverb: Handle<Effect>,
     sound_base: SoundBase,
     sound_map: SoundMap,
     resource_manager: Option<ResourceManager>,
+    // Interior mutability here allows everyone with a shared reference to the sound manager
+    // to make noise.
+    noises: RefCell<Vec<Noise>>,
 }
 
 impl SoundManager {
@@ -196,7 +254,24 @@ impl SoundManager {
             sound_map: SoundMap::new(scene, &sound_base),
             sound_base,
             resource_manager: Some(resource_manager),
+            noises: Default::default(),
+        }
+    }
+
+    pub fn emit_noise(&self, noise: Noise) {
+        self.noises.borrow_mut().push(noise);
+    }
+
+    pub fn noises(&self) -> Ref<Vec<Noise>> {
+        self.noises.borrow()
+    }
+
+    pub fn update(&mut self, dt: f32) {
+        let noises = self.noises.get_mut();
+        for noise in noises.iter_mut() {
+            noise.time_remaining -= dt;
         }
+        noises.retain(|n| n.time_remaining > 0.0);
     }
 
     pub fn play_sound<P: AsRef<Path>>(
@@ -1,6 +1,9 @@
 use crate::{
     level::item::ItemKind,
-    weapon::projectile::{Damage, ProjectileKind},
+    weapon::{
+        projectile::{Damage, ProjectileKind},
+        upgrade::UpgradeDefinition,
+    },
 };
 use fyrox::{
     core::{
@@ -71,6 +74,17 @@ pub enum WeaponProjectile {
     },
 }
 
+/// Heat model of energy weapons. Heat is measured in fractions of the max heat, the weapon
+/// overheats when heat reaches 1.0 and can't shoot until it is vented.
+#[derive(Copy, Clone, Deserialize, Debug)]
+pub struct HeatDefinition {
+    pub heat_per_shot: f32,
+    /// Amount of heat dissipated per second.
+    pub dissipation_rate: f32,
+    /// Duration of the lockout after overheating.
+    pub vent_time: f32,
+}
+
 #[derive(Deserialize, Debug)]
 pub struct WeaponDefinition {
     pub model: String,
@@ -85,6 +99,10 @@ pub struct WeaponDefinition {
     pub h_recoil: (f32, f32),
     pub shot_effect: ShotEffect,
     pub base_critical_shot_probability: f32,
+    #[serde(default)]
+    pub upgrades: Vec<UpgradeDefinition>,
+    #[serde(default)]
+    pub heat: Option<HeatDefinition>,
 }
 
 impl WeaponDefinition {
@@ -6,14 +6,18 @@ use crate::{
         character_mut, character_ref, try_get_character_mut, try_get_character_ref, Character,
         CharacterCommand, HitBox,
     },
-    current_level_mut, current_level_ref, effects,
+    current_level_mut, current_level_ref,
+    damage::DamageSource,
+    effects,
     effects::EffectKind,
-    level::trail::ShotTrail,
-    sound::{SoundKind, SoundManager},
+    inventory::Inventory,
+    level::{item::ItemKind, trail::ShotTrail},
+    sound::{Noise, NoiseKind, SoundKind, SoundManager},
     weapon::{
         definition::{ShotEffect, WeaponDefinition, WeaponKind, WeaponProjectile},
         projectile::{Damage, Projectile},
         sight::{LaserSight, SightReaction},
+        upgrade::{UpgradeDefinition, UpgradeKind},
     },
     CollisionGroups, Decal,
 };
@@ -57,6 +61,7 @@ use std::hash::{Hash, Hasher};
 pub mod definition;
 pub mod projectile;
 pub mod sight;
+pub mod upgrade;
 
 #[derive(Debug, Default, Clone)]
 pub struct ShotRequest {
@@ -88,6 +93,30 @@ pub struct Weapon {
     #[visit(optional)]
     last_shot_time: f32,
 
+    /// Indices of installed upgrades from the weapon definition.
+    #[reflect(hidden)]
+    #[inspect(skip)]
+    #[visit(optional)]
+    upgrades: Vec<u32>,
+
+    #[reflect(hidden)]
+    #[inspect(skip)]
+    #[visit(optional)]
+    heat: f32,
+
+    /// Fraction of ammo that was taken from the inventory in advance by previous shots. Allows
+    /// upgrades to reduce ammo consumption by non-integer amounts.
+    #[reflect(hidden)]
+    #[inspect(skip)]
+    #[visit(optional)]
+    ammo_credit: f32,
+
+    /// Remaining time of overheat lockout.
+    #[reflect(hidden)]
+    #[inspect(skip)]
+    #[visit(optional)]
+    vent_timer: f32,
+
     #[reflect(hidden)]
     #[inspect(skip)]
     #[visit(skip)]
@@ -122,6 +151,10 @@ impl Default for Weapon {
             laser_sight: Default::default(),
             shot_request: None,
             self_handle: Default::default(),
+            upgrades: Default::default(),
+            heat: 0.0,
+            ammo_credit: 0.0,
+            vent_timer: 0.0,
         }
     }
 }
@@ -276,13 +309,15 @@ impl Weapon {
                 0.5,
             );
 
+            let source = DamageSource::from_shooter(hit.who, graph);
             if let Some(character) = try_get_character_mut(hit.actor, graph) {
                 character.push_command(CharacterCommand::Damage {
-                    who: hit.who,
+                    source,
                     hitbox: hit.hit_box,
                     amount: damage
                         .scale(hit.hit_box.map_or(1.0, |h| h.damage_factor))
                         .amount(),
+                    kind: damage.kind(),
                     critical_shot_probability,
                 });
             }
@@ -471,7 +506,139 @@ impl Weapon {
     }
 
     pub fn can_shoot(&self, elapsed_time: f32) -> bool {
-        elapsed_time - self.last_shot_time >= self.definition.shoot_interval
+        !self.is_overheated() && elapsed_time - self.last_shot_time >= self.shoot_interval()
+    }
+
+    /// Current heat in [0; 1] range, always zero for weapons without heat model.
+    pub fn heat(&self) -> f32 {
+        self.heat
+    }
+
+    pub fn is_overheated(&self) -> bool {
+        self.vent_timer > 0.0
+    }
+
+    /// Checks whether next shot will overheat the weapon.
+    pub fn is_about_to_overheat(&self) -> bool {
+        self.definition
+            .heat
+            .map_or(false, |h| self.heat + h.heat_per_shot >= 1.0)
+    }
+
+    fn update_heat(&mut self, dt: f32) {
+        if let Some(heat_definition) = self.definition.heat {
+            if self.vent_timer > 0.0 {
+                self.vent_timer -= dt;
+                // Heat goes down linearly during venting, so it reaches zero when lockout ends.
+                self.heat = if self.vent_timer > 0.0 && heat_definition.vent_time > 0.0 {
+                    self.vent_timer / heat_definition.vent_time
+                } else {
+                    0.0
+                };
+            } else {
+                self.heat = (self.heat - heat_definition.dissipation_rate * dt).max(0.0);
+            }
+        }
+    }
+
+    pub fn upgrades(&self) -> &[u32] {
+        &self.upgrades
+    }
+
+    pub fn is_upgrade_installed(&self, index: u32) -> bool {
+        self.upgrades.contains(&index)
+    }
+
+    /// Checks whether an upgrade with given index exists, is not installed yet and all its
+    /// requirements are met.
+    pub fn can_install_upgrade(&self, index: u32) -> bool {
+        match self.definition.upgrades.get(index as usize) {
+            Some(upgrade) => {
+                !self.is_upgrade_installed(index)
+                    && upgrade
+                        .requires
+                        .map_or(true, |required| self.is_upgrade_installed(required))
+            }
+            None => false,
+        }
+    }
+
+    pub fn install_upgrade(&mut self, index: u32) -> bool {
+        if self.can_install_upgrade(index) {
+            self.upgrades.push(index);
+            true
+        } else {
+            false
+        }
+    }
+
+    fn installed_upgrades(&self) -> impl Iterator<Item = &UpgradeDefinition> {
+        self.upgrades
+            .iter()
+            .filter_map(|&i| self.definition.upgrades.get(i as usize))
+    }
+
+    fn upgrade_value(&self, kind: UpgradeKind) -> f32 {
+        self.installed_upgrades()
+            .filter(|u| u.kind == kind)
+            .map(|u| u.value)
+            .sum()
+    }
+
+    pub fn damage_scale(&self) -> f32 {
+        1.0 + self.upgrade_value(UpgradeKind::Damage)
+    }
+
+    pub fn shoot_interval(&self) -> f32 {
+        self.definition.shoot_interval / (1.0 + self.upgrade_value(UpgradeKind::FireRate))
+    }
+
+    /// Average amount of ammo per shot, it is fractional when magazine upgrades are installed.
+    pub fn ammo_consumption_per_shot(&self) -> f32 {
+        let k = 1.0 + self.upgrade_value(UpgradeKind::MagazineSize);
+        self.definition.ammo_consumption_per_shot as f32 / k
+    }
+
+    /// Amount of ammo that must be taken from the inventory for the next shot.
+    pub fn ammo_required_for_shot(&self) -> u32 {
+        // Small tolerance prevents rounding errors of the credit from taking an extra unit.
+        (self.ammo_consumption_per_shot() - self.ammo_credit - 1.0e-4)
+            .ceil()
+            .max(0.0) as u32
+    }
+
+    /// Amount of shots that can be made with the given amount of ammo.
+    pub fn shots_left(&self, ammo: u32) -> u32 {
+        ((ammo as f32 + self.ammo_credit) / self.ammo_consumption_per_shot()) as u32
+    }
+
+    /// Takes ammo for the next shot from the inventory, returns false if there is not enough
+    /// ammo.
+    pub fn try_consume_ammo(&mut self, inventory: &mut Inventory) -> bool {
+        let required = self.ammo_required_for_shot();
+        if inventory.try_extract_exact_items(ItemKind::Ammo, required) == required {
+            self.ammo_credit += required as f32 - self.ammo_consumption_per_shot();
+            true
+        } else {
+            false
+        }
+    }
+
+    pub fn critical_shot_probability(&self) -> f32 {
+        self.definition.base_critical_shot_probability
+            + self.upgrade_value(UpgradeKind::CriticalChance)
+    }
+
+    fn recoil_scale(&self) -> f32 {
+        (1.0 - self.upgrade_value(UpgradeKind::RecoilReduction)).clamp(0.0, 1.0)
+    }
+
+    pub fn gen_v_recoil_angle(&self) -> f32 {
+        self.definition.gen_v_recoil_angle() * self.recoil_scale()
+    }
+
+    pub fn gen_h_recoil_angle(&self) -> f32 {
+        self.definition.gen_h_recoil_angle() * self.recoil_scale()
     }
 
     pub fn set_sight_reaction(&mut self, reaction: SightReaction) {
@@ -496,6 +663,14 @@ impl Weapon {
 
         let position = self.shot_position(&scene.graph);
 
+        if let Some(heat_definition) = self.definition.heat {
+            self.heat += heat_definition.heat_per_shot;
+            if self.heat >= 1.0 {
+                self.heat = 1.0;
+                self.vent_timer = heat_definition.vent_time;
+            }
+        }
+
         if let Some(random_shot_sound) = self
             .definition
             .shot_sounds
@@ -504,6 +679,13 @@ impl Weapon {
             sound_manager.play_sound(&mut scene.graph, random_shot_sound, position, 1.0, 5.0, 3.0);
         }
 
+        sound_manager.emit_noise(Noise::new(
+            NoiseKind::Gunshot,
+            position,
+            Noise::GUNSHOT_LOUDNESS,
+            self.owner,
+        ));
+
         if self.muzzle_flash.is_some() {
             let muzzle_flash = &mut scene.graph[self.muzzle_flash];
             muzzle_flash.set_visibility(true);
@@ -555,10 +737,10 @@ impl Weapon {
                     self_handle,
                     position,
                     position + direction.scale(1000.0),
-                    damage,
+                    damage.scale(self.damage_scale()),
                     self.definition.shot_effect,
                     sound_manager,
-                    self.definition.base_critical_shot_probability,
+                    self.critical_shot_probability(),
                 ) {
                     if hit.actor.is_some() {
                         self.set_sight_reaction(SightReaction::HitDetected);
@@ -631,6 +813,9 @@ impl ScriptTrait for Weapon {
             }
         }
 
+        self.update_heat(ctx.dt);
+        self.laser_sight.set_heat(self.heat, self.is_overheated());
+
         let dir = self.shot_direction(&ctx.scene.graph);
         let pos = self.shot_position(&ctx.scene.graph);
         self.laser_sight
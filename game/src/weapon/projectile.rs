@@ -1,8 +1,11 @@
 use crate::{
     character::{try_get_character_mut, CharacterCommand},
-    current_level_ref, effects,
+    current_level_ref,
+    damage::DamageSource,
+    effects,
     effects::EffectKind,
     game_ref,
+    level::area_effect::{AreaEffect, AreaEffectDefinition},
     message::Message,
     weapon::{sight::SightReaction, Hit},
     Turret, Weapon,
@@ -54,6 +57,9 @@ use strum_macros::{AsRefStr, EnumString, EnumVariantNames};
 pub enum ProjectileKind {
     Plasma,
     Grenade,
+    Incendiary,
+    Emp,
+    Stasis,
 }
 
 #[derive(Deserialize, Copy, Clone, Debug, Visit)]
@@ -62,6 +68,21 @@ pub enum Damage {
     Point(f32),
 }
 
+/// Type of damage, used to tell how well armor absorbs the damage.
+#[derive(Deserialize, Copy, Clone, PartialEq, Eq, Hash, Debug, Visit)]
+pub enum DamageKind {
+    Splash,
+    Point,
+    /// Damage from burning areas.
+    Fire,
+}
+
+impl Default for DamageKind {
+    fn default() -> Self {
+        Self::Point
+    }
+}
+
 impl Default for Damage {
     fn default() -> Self {
         Self::Point(0.0)
@@ -86,6 +107,13 @@ impl Damage {
             Damage::Point(amount) => amount,
         }
     }
+
+    pub fn kind(&self) -> DamageKind {
+        match self {
+            Damage::Splash { .. } => DamageKind::Splash,
+            Damage::Point(_) => DamageKind::Point,
+        }
+    }
 }
 
 #[derive(Visit, Reflect, Inspect, Debug, Clone)]
@@ -145,6 +173,9 @@ pub struct ProjectileDefinition {
     is_kinematic: bool,
     impact_sound: String,
     model: String,
+    /// An area effect that will be left at the place of projectile's death.
+    #[serde(default)]
+    area_effect: Option<AreaEffectDefinition>,
 }
 
 #[derive(Deserialize, Default)]
@@ -188,6 +219,9 @@ impl Projectile {
         instance_ref.local_transform_mut().set_position(position);
 
         if let Some(projectile) = instance_ref.try_get_script_mut::<Projectile>() {
+            // Some projectiles share the same model, so force the kind explicitly.
+            projectile.kind = kind;
+            projectile.definition = definition;
             projectile.initial_velocity = initial_velocity;
             projectile.dir = dir
                 .try_normalize(std::f32::EPSILON)
@@ -318,31 +352,41 @@ impl ScriptTrait for Projectile {
                 4.0,
                 3.0,
             );
+
+            if let Some(area_effect) = self.definition.area_effect.as_ref() {
+                AreaEffect::add_to_scene(
+                    &mut context.scene.graph,
+                    area_effect,
+                    effect_position,
+                    DamageSource::projectile(self.kind, self.owner, &context.scene.graph),
+                );
+            }
         }
 
+        let source = DamageSource::projectile(self.kind, self.owner, &context.scene.graph);
+
         for hit in self.hits.drain() {
+            let (critical_shot_probability, damage_scale) = context
+                .scene
+                .graph
+                .try_get_mut(self.owner)
+                .map_or((0.0, 1.0), |owner_node| {
+                    if let Some(weapon) = owner_node.try_get_script_mut::<Weapon>() {
+                        if hit.actor.is_some() {
+                            weapon.set_sight_reaction(SightReaction::HitDetected);
+                        }
+                        (weapon.critical_shot_probability(), weapon.damage_scale())
+                    } else if owner_node.has_script::<Turret>() {
+                        (0.01, 1.0)
+                    } else {
+                        (0.0, 1.0)
+                    }
+                });
+
             let damage = self
                 .definition
                 .damage
-                .scale(hit.hit_box.map_or(1.0, |h| h.damage_factor));
-
-            let critical_shot_probability =
-                context
-                    .scene
-                    .graph
-                    .try_get_mut(self.owner)
-                    .map_or(0.0, |owner_node| {
-                        if let Some(weapon) = owner_node.try_get_script_mut::<Weapon>() {
-                            if hit.actor.is_some() {
-                                weapon.set_sight_reaction(SightReaction::HitDetected);
-                            }
-                            weapon.definition.base_critical_shot_probability
-                        } else if owner_node.has_script::<Turret>() {
-                            0.01
-                        } else {
-                            0.0
-                        }
-                    });
+                .scale(hit.hit_box.map_or(1.0, |h| h.damage_factor) * damage_scale);
 
             match damage {
                 Damage::Splash { radius, amount } => {
@@ -350,7 +394,7 @@ impl ScriptTrait for Projectile {
                         amount,
                         radius,
                         center: position,
-                        who: hit.who,
+                        source,
                         critical_shot_probability,
                     })
                 }
@@ -359,9 +403,10 @@ impl ScriptTrait for Projectile {
                         try_get_character_mut(hit.actor, &mut context.scene.graph)
                     {
                         character.push_command(CharacterCommand::Damage {
-                            who: hit.who,
+                            source,
                             hitbox: hit.hit_box,
                             amount,
+                            kind: DamageKind::Point,
                             critical_shot_probability,
                         });
                     }
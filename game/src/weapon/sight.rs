@@ -39,6 +39,21 @@ pub struct LaserSight {
     #[reflect(hidden)]
     #[inspect(skip)]
     reaction_state: Option<ReactionState>,
+
+    #[reflect(hidden)]
+    #[inspect(skip)]
+    #[visit(skip)]
+    heat: f32,
+
+    #[reflect(hidden)]
+    #[inspect(skip)]
+    #[visit(skip)]
+    overheated: bool,
+
+    #[reflect(hidden)]
+    #[inspect(skip)]
+    #[visit(skip)]
+    heat_changed: bool,
 }
 
 #[derive(Visit, Reflect, Inspect, Debug, Clone)]
@@ -73,6 +88,8 @@ pub enum SightReaction {
 }
 
 const NORMAL_COLOR: Color = Color::from_rgba(0, 162, 232, 200);
+const HOT_COLOR: Color = Color::from_rgba(255, 140, 0, 200);
+const OVERHEATED_COLOR: Color = Color::from_rgba(255, 255, 255, 200);
 const NORMAL_RADIUS: f32 = 0.0012;
 const ENEMY_KILLED_TIME: f32 = 0.55;
 const HIT_DETECTED_TIME: f32 = 0.4;
@@ -129,6 +146,9 @@ impl LaserSight {
             light,
             enabled: true,
             reaction_state: None,
+            heat: 0.0,
+            overheated: false,
+            heat_changed: false,
         }
     }
 
@@ -189,6 +209,7 @@ impl LaserSight {
                     *time_remaining -= dt;
                     if *time_remaining <= 0.0 {
                         self.reaction_state = None;
+                        self.heat_changed = true;
                     } else {
                         let t = *time_remaining / HIT_DETECTED_TIME;
                         let color = end_color.lerp(*begin_color, t);
@@ -204,6 +225,7 @@ impl LaserSight {
                     *time_remaining -= dt;
                     if *time_remaining <= 0.0 {
                         self.reaction_state = None;
+                        self.heat_changed = true;
                     } else {
                         let t = *time_remaining / HIT_DETECTED_TIME;
                         let color = end_color.lerp(*begin_color, t);
@@ -213,6 +235,27 @@ impl LaserSight {
                     }
                 }
             }
+        } else if self.heat_changed {
+            self.heat_changed = false;
+            self.set_color(&mut scene.graph, self.heat_color());
+        }
+    }
+
+    /// Sets heat level of the weapon, the sight changes its color accordingly.
+    pub fn set_heat(&mut self, heat: f32, overheated: bool) {
+        let heat = heat.clamp(0.0, 1.0);
+        if heat != self.heat || overheated != self.overheated {
+            self.heat = heat;
+            self.overheated = overheated;
+            self.heat_changed = true;
+        }
+    }
+
+    fn heat_color(&self) -> Color {
+        if self.overheated {
+            OVERHEATED_COLOR
+        } else {
+            NORMAL_COLOR.lerp(HOT_COLOR, self.heat)
         }
     }
 
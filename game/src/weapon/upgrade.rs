@@ -0,0 +1,32 @@
+//! Weapon upgrades. Each weapon kind has its own set of upgrade nodes defined in
+//! `data/configs/weapons.ron`, nodes are bought at workbenches for upgrade modules and
+//! stored per weapon instance.
+
+use serde::Deserialize;
+
+#[derive(Copy, Clone, PartialEq, Eq, Debug, Deserialize, Hash)]
+pub enum UpgradeKind {
+    /// Increases damage of each shot, value is a relative increment.
+    Damage,
+    /// Weapons share the same energy cells, so bigger magazine means less ammo per shot.
+    /// Value is a relative increment of shots per cell.
+    MagazineSize,
+    /// Reduces time between shots, value is a relative increment of fire rate.
+    FireRate,
+    /// Reduces recoil angles, value is a fraction of the recoil that is removed.
+    RecoilReduction,
+    /// Adds absolute value to critical shot probability.
+    CriticalChance,
+}
+
+#[derive(Deserialize, Debug)]
+pub struct UpgradeDefinition {
+    pub name: String,
+    pub kind: UpgradeKind,
+    pub value: f32,
+    /// Amount of upgrade modules required to install the upgrade.
+    pub cost: u32,
+    /// Index of an upgrade that must be installed first.
+    #[serde(default)]
+    pub requires: Option<u32>,
+}
@@ -0,0 +1,180 @@
+//! Workbench is a place where the player can install weapon upgrades in exchange for upgrade
+//! modules.
+
+use crate::{
+    current_level_mut, current_level_ref, game_mut, level::item::ItemKind, weapon::try_weapon_ref,
+    Player,
+};
+use fyrox::{
+    core::{
+        inspect::prelude::*,
+        reflect::Reflect,
+        sstorage::ImmutableString,
+        uuid::{uuid, Uuid},
+        visitor::prelude::*,
+    },
+    engine::resource_manager::ResourceManager,
+    impl_component_provider,
+    material::{Material, PropertyValue, SharedMaterial},
+    resource::texture::Texture,
+    scene::{
+        graph::Graph,
+        mesh::Mesh,
+        node::{NodeHandle, TypeUuidProvider},
+    },
+    script::{ScriptContext, ScriptDeinitContext, ScriptTrait},
+    utils::log::Log,
+};
+use std::fmt::Write;
+
+pub mod ui;
+
+#[derive(Visit, Reflect, Inspect, Default, Debug, Clone)]
+pub struct Workbench {
+    #[inspect(description = "An array of handles to meshes that represents interactive screens.")]
+    screens: Vec<NodeHandle>,
+
+    #[inspect(skip)]
+    #[reflect(hidden)]
+    #[visit(skip)]
+    selection: u32,
+}
+
+impl_component_provider!(Workbench);
+
+impl TypeUuidProvider for Workbench {
+    fn type_uuid() -> Uuid {
+        uuid!("b3e4a0d2-6a53-4c1e-9f0b-5d1f2a7c8e61")
+    }
+}
+
+impl ScriptTrait for Workbench {
+    fn on_init(&mut self, ctx: &mut ScriptContext) {
+        current_level_mut(ctx.plugins)
+            .expect("Level must exist!")
+            .workbenches
+            .push(ctx.handle);
+    }
+
+    fn on_start(&mut self, ctx: &mut ScriptContext) {
+        let game = game_mut(ctx.plugins);
+        let texture = game
+            .workbench_ui_container
+            .create_ui(game.smaller_font.clone(), ctx.handle);
+        self.apply_screen_texture(&mut ctx.scene.graph, ctx.resource_manager.clone(), texture);
+    }
+
+    fn on_deinit(&mut self, ctx: &mut ScriptDeinitContext) {
+        if let Some(level) = current_level_mut(ctx.plugins) {
+            if let Some(position) = level.workbenches.iter().position(|w| *w == ctx.node_handle) {
+                level.workbenches.remove(position);
+            }
+        }
+    }
+
+    fn on_update(&mut self, ctx: &mut ScriptContext) {
+        let player_handle = current_level_ref(ctx.plugins)
+            .map(|l| l.player)
+            .unwrap_or_default();
+
+        let mut title = "Workbench".to_owned();
+        let mut upgrades = String::new();
+        let mut modules = String::new();
+
+        if let Some(player) = ctx
+            .scene
+            .graph
+            .try_get(player_handle)
+            .and_then(|n| n.try_get_script::<Player>())
+        {
+            if let Some(weapon) = try_weapon_ref(player.current_weapon(), &ctx.scene.graph) {
+                title = weapon.kind().as_ref().to_owned();
+
+                for (index, upgrade) in weapon.definition.upgrades.iter().enumerate() {
+                    let index = index as u32;
+                    let marker = if index == self.selection { ">" } else { " " };
+                    let status = if weapon.is_upgrade_installed(index) {
+                        "Installed".to_owned()
+                    } else if weapon.can_install_upgrade(index) {
+                        format!("{} mod.", upgrade.cost)
+                    } else {
+                        "Unavailable".to_owned()
+                    };
+                    let _ = writeln!(upgrades, "{} {} - {}", marker, upgrade.name, status);
+                }
+
+                if weapon.definition.upgrades.is_empty() {
+                    upgrades = "No upgrades available".to_owned();
+                }
+            }
+
+            modules = format!(
+                "Modules: {}",
+                player.inventory().item_count(ItemKind::UpgradeModule)
+            );
+        }
+
+        if let Some(ui) = game_mut(ctx.plugins)
+            .workbench_ui_container
+            .get_ui_mut(ctx.handle)
+        {
+            ui.set_title(title);
+            ui.set_upgrades(upgrades);
+            ui.set_modules(modules);
+        }
+    }
+
+    fn id(&self) -> Uuid {
+        Self::type_uuid()
+    }
+}
+
+impl Workbench {
+    pub fn selection(&self) -> u32 {
+        self.selection
+    }
+
+    pub fn select_next(&mut self, upgrade_count: usize) {
+        self.selection = self
+            .selection
+            .saturating_add(1)
+            .min((upgrade_count as u32).saturating_sub(1));
+    }
+
+    pub fn select_previous(&mut self) {
+        self.selection = self.selection.saturating_sub(1);
+    }
+
+    fn apply_screen_texture(
+        &self,
+        graph: &mut Graph,
+        resource_manager: ResourceManager,
+        texture: Texture,
+    ) {
+        for &node_handle in &self.screens {
+            if let Some(mesh) = graph[*node_handle].cast_mut::<Mesh>() {
+                let mut material = Material::standard();
+
+                Log::verify(material.set_property(
+                    &ImmutableString::new("diffuseTexture"),
+                    PropertyValue::Sampler {
+                        value: Some(texture.clone()),
+                        fallback: Default::default(),
+                    },
+                ));
+
+                Log::verify(material.set_property(
+                    &ImmutableString::new("emissionTexture"),
+                    PropertyValue::Sampler {
+                        value: Some(resource_manager.request_texture("data/ui/white_pixel.bmp")),
+                        fallback: Default::default(),
+                    },
+                ));
+
+                if let Some(first_surface) = mesh.surfaces_mut().get_mut(0) {
+                    first_surface.set_material(SharedMaterial::new(material));
+                }
+            }
+        }
+    }
+}
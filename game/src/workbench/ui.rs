@@ -0,0 +1,155 @@
+use crate::{
+    ui_container::{InteractiveUi, UiContainer},
+    MessageDirection, UiNode, WidgetBuilder,
+};
+use fyrox::{
+    core::{algebra::Vector2, color::Color, pool::Handle},
+    gui::{
+        border::BorderBuilder,
+        brush::Brush,
+        grid::{Column, GridBuilder, Row},
+        text::{TextBuilder, TextMessage},
+        ttf::SharedFont,
+        HorizontalAlignment, Thickness, UserInterface, VerticalAlignment,
+    },
+    resource::texture::Texture,
+    scene::node::Node,
+};
+
+pub struct WorkbenchUi {
+    pub ui: UserInterface,
+    pub render_target: Texture,
+    title: Handle<UiNode>,
+    upgrades: Handle<UiNode>,
+    modules: Handle<UiNode>,
+}
+
+impl InteractiveUi for WorkbenchUi {
+    fn ui(&mut self) -> &mut UserInterface {
+        &mut self.ui
+    }
+
+    fn texture(&self) -> Texture {
+        self.render_target.clone()
+    }
+
+    fn update(&mut self, delta: f32) {
+        self.ui
+            .update(Vector2::new(Self::WIDTH, Self::HEIGHT), delta);
+
+        // Just pump all messages, but ignore them in game code.
+        while self.ui.poll_message().is_some() {}
+    }
+}
+
+impl WorkbenchUi {
+    pub const WIDTH: f32 = 256.0;
+    pub const HEIGHT: f32 = 256.0;
+
+    pub fn new(font: SharedFont) -> Self {
+        let mut ui = UserInterface::new(Vector2::new(Self::WIDTH, Self::HEIGHT));
+        let render_target = Texture::new_render_target(Self::WIDTH as u32, Self::HEIGHT as u32);
+
+        let ctx = &mut ui.build_ctx();
+
+        let title;
+        let upgrades;
+        let modules;
+
+        BorderBuilder::new(
+            WidgetBuilder::new()
+                .with_width(Self::WIDTH)
+                .with_height(Self::HEIGHT)
+                .with_child(
+                    GridBuilder::new(
+                        WidgetBuilder::new()
+                            .with_child({
+                                title = TextBuilder::new(
+                                    WidgetBuilder::new()
+                                        .on_row(0)
+                                        .on_column(0)
+                                        .with_margin(Thickness::uniform(4.0)),
+                                )
+                                .with_font(font.clone())
+                                .with_horizontal_text_alignment(HorizontalAlignment::Center)
+                                .with_vertical_text_alignment(VerticalAlignment::Center)
+                                .with_text("Workbench")
+                                .build(ctx);
+                                title
+                            })
+                            .with_child({
+                                upgrades = TextBuilder::new(
+                                    WidgetBuilder::new()
+                                        .on_row(1)
+                                        .on_column(0)
+                                        .with_margin(Thickness::uniform(4.0))
+                                        .with_foreground(Brush::Solid(Color::opaque(0, 162, 232))),
+                                )
+                                .with_font(font.clone())
+                                .build(ctx);
+                                upgrades
+                            })
+                            .with_child({
+                                modules = TextBuilder::new(
+                                    WidgetBuilder::new()
+                                        .on_row(2)
+                                        .on_column(0)
+                                        .with_margin(Thickness::uniform(4.0))
+                                        .with_foreground(Brush::Solid(Color::GREEN)),
+                                )
+                                .with_horizontal_text_alignment(HorizontalAlignment::Center)
+                                .with_font(font)
+                                .build(ctx);
+                                modules
+                            }),
+                    )
+                    .add_column(Column::stretch())
+                    .add_row(Row::auto())
+                    .add_row(Row::stretch())
+                    .add_row(Row::auto())
+                    .build(ctx),
+                ),
+        )
+        .build(ctx);
+
+        Self {
+            ui,
+            render_target,
+            title,
+            upgrades,
+            modules,
+        }
+    }
+
+    pub fn set_title(&mut self, text: String) {
+        self.ui.send_message(TextMessage::text(
+            self.title,
+            MessageDirection::ToWidget,
+            text,
+        ));
+    }
+
+    pub fn set_upgrades(&mut self, text: String) {
+        self.ui.send_message(TextMessage::text(
+            self.upgrades,
+            MessageDirection::ToWidget,
+            text,
+        ));
+    }
+
+    pub fn set_modules(&mut self, text: String) {
+        self.ui.send_message(TextMessage::text(
+            self.modules,
+            MessageDirection::ToWidget,
+            text,
+        ));
+    }
+}
+
+pub type WorkbenchUiContainer = UiContainer<Node, WorkbenchUi>;
+
+impl WorkbenchUiContainer {
+    pub fn create_ui(&mut self, font: SharedFont, workbench_handle: Handle<Node>) -> Texture {
+        self.add(workbench_handle, WorkbenchUi::new(font))
+    }
+}